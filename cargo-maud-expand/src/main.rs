@@ -0,0 +1,125 @@
+//! Prints the code that Maud generates for a single template.
+//!
+//! ```text
+//! cargo maud-expand FILE:LINE [CARGO_CHECK_ARGS]...
+//! ```
+//!
+//! This checks the current package, and prints the code generated for every
+//! `html!` or `try_html!` invocation that covers the given line. Writes of
+//! static strings and calls that render splices are each marked with a
+//! comment, followed by a count of both, so it's easy to see how much of a
+//! template was merged into constant strings.
+//!
+//! Any further arguments are passed on to `cargo check`, e.g. `-p` to pick a
+//! package in a workspace, or `--features`.
+
+use std::{
+    env,
+    fs::{self, File},
+    path::PathBuf,
+    process::{Command, ExitCode},
+    time::SystemTime,
+};
+
+const USAGE: &str = "usage: cargo maud-expand FILE:LINE [CARGO_CHECK_ARGS]...";
+
+fn main() -> ExitCode {
+    let mut args = env::args().skip(1).peekable();
+    // Cargo passes the name of the subcommand as the first argument
+    if args.peek().map(String::as_str) == Some("maud-expand") {
+        args.next();
+    }
+    let location = match args.next() {
+        Some(arg) if arg == "-h" || arg == "--help" => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Some(arg) => arg,
+        None => {
+            eprintln!("{}", USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(&location, args.collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("cargo-maud-expand: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(location: &str, cargo_args: Vec<String>) -> Result<(), String> {
+    let (path, line) = parse_location(location)?;
+    let path = fs::canonicalize(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    // Proc macro output is cached, so touch the file to make sure the
+    // template is expanded again
+    File::options()
+        .append(true)
+        .open(&path)
+        .and_then(|file| file.set_modified(SystemTime::now()))
+        .map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    let output = env::temp_dir().join(format!("maud-expand-{}.rs", std::process::id()));
+    let _ = fs::remove_file(&output);
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let status = Command::new(cargo)
+        .arg("check")
+        .args(cargo_args)
+        .env("MAUD_EXPAND", format!("{}:{}", path.display(), line))
+        .env("MAUD_EXPAND_OUT", &output)
+        .status()
+        .map_err(|e| format!("could not run cargo: {}", e))?;
+
+    // Print what we found even if the build failed, as the template itself
+    // might be fine
+    match fs::read_to_string(&output) {
+        Ok(listing) => {
+            let _ = fs::remove_file(&output);
+            print!("{}", listing);
+            Ok(())
+        }
+        Err(_) if !status.success() => Err("cargo check failed".into()),
+        Err(_) => Err(format!(
+            "no `html!` invocation found at {}:{}",
+            path.display(),
+            line,
+        )),
+    }
+}
+
+/// Splits `FILE:LINE` into its parts.
+fn parse_location(location: &str) -> Result<(PathBuf, usize), String> {
+    let (path, line) = location
+        .rsplit_once(':')
+        .ok_or_else(|| format!("expected FILE:LINE, found `{}`", location))?;
+    let line = line
+        .parse::<usize>()
+        .ok()
+        .filter(|line| *line > 0)
+        .ok_or_else(|| format!("invalid line number `{}`", line))?;
+    Ok((PathBuf::from(path), line))
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::parse_location;
+
+    #[test]
+    fn location() {
+        assert_eq!(
+            parse_location("src/main.rs:12"),
+            Ok((PathBuf::from("src/main.rs"), 12)),
+        );
+        assert_eq!(
+            parse_location("C:\\src\\main.rs:3"),
+            Ok((PathBuf::from("C:\\src\\main.rs"), 3)),
+        );
+        assert!(parse_location("src/main.rs").is_err());
+        assert!(parse_location("src/main.rs:0").is_err());
+        assert!(parse_location("src/main.rs:x").is_err());
+    }
+}
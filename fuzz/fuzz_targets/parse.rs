@@ -0,0 +1,52 @@
+//! Feeds arbitrary source to the template parser.
+//!
+//! The parser must never panic, other than to abort with an error. And a
+//! template that parses without errors must print back out as one that
+//! parses to the same thing.
+//!
+//! ```text
+//! cargo +nightly fuzz run parse
+//! ```
+
+#![no_main]
+// Only some of what the parser modules define is used here
+#![allow(dead_code)]
+
+use libfuzzer_sys::fuzz_target;
+use proc_macro2::TokenStream;
+
+#[path = "../../maud_macros/src/ast.rs"]
+mod ast;
+#[path = "../../maud_macros/src/diagnostics.rs"]
+mod diagnostics;
+#[path = "../../maud_macros/src/parse.rs"]
+mod parse;
+#[path = "../../maud_macros/src/pretty.rs"]
+mod pretty;
+#[path = "../../maud_macros/src/suggest.rs"]
+mod suggest;
+
+/// Parses a template and prints it back out, if it parsed without errors.
+fn reprint(source: &str) -> Option<String> {
+    let tokens = source.parse::<TokenStream>().ok()?;
+    let (markups, messages) = diagnostics::capture(|| parse::parse(tokens));
+    let markups = markups?;
+    if messages
+        .iter()
+        .any(|message| message.level == diagnostics::Level::Error)
+    {
+        return None;
+    }
+    Some(pretty::print(&markups))
+}
+
+fuzz_target!(|source: &str| {
+    // proc-macro2 lexes this as a literal, to stand in for the ones the
+    // compiler makes when recovering from errors. They never reach a macro.
+    if source.contains("(/*ERROR*/)") {
+        return;
+    }
+    if let Some(printed) = reprint(source) {
+        assert_eq!(reprint(&printed), Some(printed), "source: {}", source);
+    }
+});
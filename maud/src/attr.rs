@@ -0,0 +1,190 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Display;
+
+use crate::{escape, Render};
+
+/// Appends ` name="value"` to the buffer, escaping the value.
+pub fn push_attr(name: &str, value: &str, w: &mut String) {
+    w.push(' ');
+    w.push_str(name);
+    w.push_str("=\"");
+    escape::escape_to_string(value, w);
+    w.push('"');
+}
+
+/// A set of attributes, chosen at runtime.
+///
+/// A component can take one of these as its last argument, and pass it on
+/// to its root element with `..rest`. Attributes written on the element
+/// win over those in the set, except for classes, which are merged.
+///
+/// Attribute names that HTML doesn't allow are left out when rendering.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Attributes, Markup};
+///
+/// fn button(label: &str, rest: Attributes) -> Markup {
+///     html! {
+///         button.btn type="button" ..rest { (label) }
+///     }
+/// }
+///
+/// let markup = button(
+///     "Save",
+///     Attributes::new().class("btn-primary").set("type", "submit").flag("disabled"),
+/// );
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<button class="btn btn-primary" type="button" disabled>Save</button>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Attributes {
+    attrs: Vec<(String, Option<String>)>,
+}
+
+impl Attributes {
+    /// Creates an empty set.
+    pub fn new() -> Attributes {
+        Attributes::default()
+    }
+
+    /// Sets an attribute, replacing any earlier value.
+    pub fn set(mut self, name: &str, value: impl Display) -> Attributes {
+        self.insert(name, Some(value.to_string()));
+        self
+    }
+
+    /// Sets an attribute, but only if `value` is `Some`.
+    pub fn set_opt(self, name: &str, value: Option<impl Display>) -> Attributes {
+        match value {
+            Some(value) => self.set(name, value),
+            None => self,
+        }
+    }
+
+    /// Sets an empty attribute, like `disabled`.
+    pub fn flag(mut self, name: &str) -> Attributes {
+        self.insert(name, None);
+        self
+    }
+
+    /// Adds a class, keeping any there already.
+    pub fn class(mut self, class: &str) -> Attributes {
+        match self.attrs.iter_mut().find(|(name, _)| name == "class") {
+            Some((_, Some(value))) if !value.is_empty() => {
+                value.push(' ');
+                value.push_str(class);
+            }
+            Some((_, value)) => *value = Some(class.to_string()),
+            None => self
+                .attrs
+                .push(("class".to_string(), Some(class.to_string()))),
+        }
+        self
+    }
+
+    /// Returns the value of an attribute. Empty attributes have the value
+    /// `""`.
+    ///
+    /// Names are matched ignoring ASCII case, as they are when inserted.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(this, _)| this.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_deref().unwrap_or_default())
+    }
+
+    /// Removes an attribute, and returns its value.
+    ///
+    /// Names are matched ignoring ASCII case, like in [`get`](Self::get).
+    pub fn remove(&mut self, name: &str) -> Option<String> {
+        let index = self
+            .attrs
+            .iter()
+            .position(|(this, _)| this.eq_ignore_ascii_case(name))?;
+        Some(self.attrs.remove(index).1.unwrap_or_default())
+    }
+
+    /// Returns `true` if there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+
+    fn insert(&mut self, name: &str, value: Option<String>) {
+        let name = name.to_ascii_lowercase();
+        match self.attrs.iter_mut().find(|(this, _)| *this == name) {
+            Some((_, old)) => *old = value,
+            None => self.attrs.push((name, value)),
+        }
+    }
+
+    fn push_except(&self, own: &[&str], w: &mut String) {
+        for (name, value) in &self.attrs {
+            if !is_valid_name(name) || own.contains(&&**name) {
+                continue;
+            }
+            match value {
+                Some(value) => push_attr(name, value, w),
+                None => {
+                    w.push(' ');
+                    w.push_str(name);
+                }
+            }
+        }
+    }
+
+    /// Renders the attributes that the element doesn't set itself. This is
+    /// what `..rest` expands to.
+    #[doc(hidden)]
+    pub fn __except<'a>(&'a self, own: &'a [&'a str]) -> impl Render + 'a {
+        struct Except<'a>(&'a Attributes, &'a [&'a str]);
+
+        impl Render for Except<'_> {
+            fn render_to(&self, w: &mut String) {
+                self.0.push_except(self.1, w);
+            }
+        }
+
+        Except(self, own)
+    }
+
+    /// Renders the classes, each with a space before it, for an element
+    /// that has classes of its own.
+    #[doc(hidden)]
+    pub fn __classes(&self) -> impl Render + '_ {
+        struct Classes<'a>(Option<&'a str>);
+
+        impl Render for Classes<'_> {
+            fn render_to(&self, w: &mut String) {
+                for class in self.0.unwrap_or_default().split_ascii_whitespace() {
+                    w.push(' ');
+                    escape::escape_to_string(class, w);
+                }
+            }
+        }
+
+        Classes(self.get("class"))
+    }
+}
+
+impl Render for Attributes {
+    fn render_to(&self, w: &mut String) {
+        self.push_except(&[], w);
+    }
+}
+
+/// Whether a name can be written as an attribute name as is.
+pub(crate) fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace()
+                || c.is_control()
+                || matches!(c, '"' | '\'' | '>' | '/' | '=' | '<' | '&' | '`')
+        })
+}
@@ -0,0 +1,137 @@
+use alloc::string::String;
+use core::iter::FromIterator;
+
+use crate::{Markup, PreEscaped, Render};
+
+/// Building markup outside of `html!`, for when its shape comes from data.
+impl PreEscaped<String> {
+    /// Renders each item, one after the other.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use maud::{html, Markup};
+    ///
+    /// let markup = Markup::concat([html! { b { "Pinkie" } }, html! { " Pie" }]);
+    /// assert_eq!(markup.into_string(), "<b>Pinkie</b> Pie");
+    /// ```
+    pub fn concat<I>(items: I) -> Markup
+    where
+        I: IntoIterator,
+        I::Item: Render,
+    {
+        items.into_iter().collect()
+    }
+
+    /// Renders each item, with the separator between each pair.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use maud::{html, Markup};
+    ///
+    /// let ponies = ["Applejack", "Rarity", "Fluttershy"];
+    /// let links = ponies.iter().map(|pony| html! { a href={ "/" (pony) } { (pony) } });
+    /// assert_eq!(
+    ///     Markup::join(links, ", ").into_string(),
+    ///     concat!(
+    ///         r#"<a href="/Applejack">Applejack</a>, "#,
+    ///         r#"<a href="/Rarity">Rarity</a>, "#,
+    ///         r#"<a href="/Fluttershy">Fluttershy</a>"#,
+    ///     ),
+    /// );
+    /// ```
+    pub fn join<I>(items: I, separator: impl Render) -> Markup
+    where
+        I: IntoIterator,
+        I::Item: Render,
+    {
+        let separator = separator.render();
+        let mut buffer = String::new();
+        for (i, item) in items.into_iter().enumerate() {
+            if i > 0 {
+                buffer.push_str(&separator.0);
+            }
+            item.render_to(&mut buffer);
+        }
+        PreEscaped(buffer)
+    }
+
+    /// Repeats the markup `n` times.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use maud::html;
+    ///
+    /// let stars = html! { span.star { "★" } }.repeat(3);
+    /// assert_eq!(stars.into_string(), r#"<span class="star">★</span>"#.repeat(3));
+    /// ```
+    pub fn repeat(&self, n: usize) -> Markup {
+        PreEscaped(self.0.repeat(n))
+    }
+
+    /// Wraps the markup in an element with the given name.
+    ///
+    /// # Panics
+    ///
+    /// If the name is empty, or has characters that can't be in a tag
+    /// name, like spaces or `>`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use maud::html;
+    ///
+    /// let item = html! { "Cider" }.wrap_in("li");
+    /// assert_eq!(item.into_string(), "<li>Cider</li>");
+    /// ```
+    pub fn wrap_in(self, tag: &str) -> Markup {
+        assert!(is_valid_tag(tag), "invalid tag name {:?}", tag);
+        let mut buffer = String::with_capacity(self.0.len() + 2 * tag.len() + 5);
+        buffer.push('<');
+        buffer.push_str(tag);
+        buffer.push('>');
+        buffer.push_str(&self.0);
+        buffer.push_str("</");
+        buffer.push_str(tag);
+        buffer.push('>');
+        PreEscaped(buffer)
+    }
+}
+
+/// Renders each item, one after the other.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Markup};
+///
+/// let list: Markup = ["Apples", "Cider"]
+///     .iter()
+///     .map(|item| html! { li { (item) } })
+///     .collect();
+/// assert_eq!(list.into_string(), "<li>Apples</li><li>Cider</li>");
+/// ```
+impl<T: Render> FromIterator<T> for PreEscaped<String> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Markup {
+        let mut markup = PreEscaped(String::new());
+        markup.extend(iter);
+        markup
+    }
+}
+
+impl<T: Render> Extend<T> for PreEscaped<String> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for item in iter {
+            item.render_to(&mut self.0);
+        }
+    }
+}
+
+fn is_valid_tag(tag: &str) -> bool {
+    tag.starts_with(|c: char| c.is_ascii_alphabetic())
+        && tag
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | ':' | '.'))
+}
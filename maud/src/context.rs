@@ -0,0 +1,91 @@
+//! Values that a template provides to everything rendered inside it.
+//!
+//! `@provide(value) { ... }` makes a value available to the code that
+//! renders its body, including any components it calls. Those can read it
+//! with [`get`], or with `@consume(name: Type);` in a template, without it
+//! being passed down through every function in between:
+//!
+//! ```rust
+//! use maud::{html, Markup};
+//!
+//! #[derive(Clone, Copy, PartialEq)]
+//! enum Theme {
+//!     Light,
+//!     Dark,
+//! }
+//!
+//! fn button(label: &str) -> Markup {
+//!     html! {
+//!         @consume(theme: Theme);
+//!         button.btn.dark[theme == Some(Theme::Dark)] { (label) }
+//!     }
+//! }
+//!
+//! let markup = html! {
+//!     @provide(Theme::Dark) {
+//!         (button("Save"))
+//!     }
+//!     (button("Cancel"))
+//! };
+//! assert_eq!(
+//!     markup.into_string(),
+//!     r#"<button class="btn dark">Save</button><button class="btn">Cancel</button>"#,
+//! );
+//! ```
+//!
+//! The values live on the current thread, for as long as the block is
+//! being rendered. So an async template shouldn't `.await` inside a
+//! `@provide`, since another task could run on the thread in the meantime.
+//!
+//! Requires the `context` feature.
+
+extern crate std;
+
+use alloc::{boxed::Box, vec::Vec};
+use core::{any::Any, cell::RefCell, marker::PhantomData};
+
+std::thread_local! {
+    static PROVIDED: RefCell<Vec<Box<dyn Any>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns the innermost value of type `T` provided around the current
+/// render, if there is one.
+pub fn get<T: Clone + 'static>() -> Option<T> {
+    PROVIDED.with(|provided| {
+        provided
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|value| value.downcast_ref::<T>())
+            .cloned()
+    })
+}
+
+/// Calls `f` with `value` provided, like a `@provide` block does.
+pub fn provide<T: 'static, R>(value: T, f: impl FnOnce() -> R) -> R {
+    let _provided = __Provided::new(value);
+    f()
+}
+
+/// Keeps a value provided until it's dropped. This is what `@provide`
+/// expands to.
+#[doc(hidden)]
+pub struct __Provided {
+    // Must be dropped on the thread it was made on
+    _not_send: PhantomData<*const ()>,
+}
+
+impl __Provided {
+    pub fn new<T: 'static>(value: T) -> __Provided {
+        PROVIDED.with(|provided| provided.borrow_mut().push(Box::new(value)));
+        __Provided {
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for __Provided {
+    fn drop(&mut self) {
+        PROVIDED.with(|provided| provided.borrow_mut().pop());
+    }
+}
@@ -0,0 +1,84 @@
+use alloc::string::{String, ToString};
+use serde_dep::Serialize;
+use serde_json::Value;
+
+use crate::{attr::is_valid_name, escape, Render};
+
+/// Renders a serializable value as a set of `data-*` attributes.
+///
+/// This is what the `data..=(value)` attribute syntax expands to. Each field
+/// of the value becomes its own attribute, with field names converted to
+/// kebab case (`user_id` and `userId` both become `data-user-id`):
+///
+/// * strings are rendered as is,
+/// * numbers and booleans are rendered using their JSON representation,
+/// * `None` (or `null`) omits the attribute entirely, and
+/// * arrays and nested objects are rendered as JSON.
+///
+/// Values that don't serialize to a map render nothing, and fields whose
+/// names can't be written as an attribute name are skipped.
+///
+/// Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+/// use serde_json::json;
+///
+/// let card = json!({ "user_id": 42, "role": "admin", "manager": null });
+/// let markup = html! {
+///     div data..=(card) { "Rarity" }
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<div data-role="admin" data-user-id="42">Rarity</div>"#,
+/// );
+/// ```
+pub fn data_attributes<T: Serialize + ?Sized>(value: &T) -> impl Render + '_ {
+    struct DataAttributes<'a, T: ?Sized>(&'a T);
+
+    impl<'a, T: Serialize + ?Sized> Render for DataAttributes<'a, T> {
+        fn render_to(&self, w: &mut String) {
+            let map = match serde_json::to_value(self.0) {
+                Ok(Value::Object(map)) => map,
+                _ => return,
+            };
+            for (key, value) in map {
+                let value = match value {
+                    Value::Null => continue,
+                    Value::String(value) => value,
+                    value => value.to_string(),
+                };
+                let name = kebab_case(&key);
+                if !is_valid_name(&name) {
+                    continue;
+                }
+                w.push_str(" data-");
+                w.push_str(&name);
+                w.push_str("=\"");
+                escape::escape_to_string(&value, w);
+                w.push('"');
+            }
+        }
+    }
+
+    DataAttributes(value)
+}
+
+fn kebab_case(key: &str) -> String {
+    let mut result = String::with_capacity(key.len());
+    for c in key.chars() {
+        match c {
+            '_' => result.push('-'),
+            c if c.is_ascii_uppercase() => {
+                if !result.is_empty() {
+                    result.push('-');
+                }
+                result.push(c.to_ascii_lowercase());
+            }
+            c => result.push(c),
+        }
+    }
+    result
+}
@@ -0,0 +1,251 @@
+use alloc::string::String;
+
+use crate::{attr::push_attr, escape, PreEscaped, Render};
+
+/// A date or time that can be shown in a [`Time`] element.
+///
+/// This is implemented for the types in `chrono` and `time`, behind the
+/// features of the same names.
+pub trait Timestamp {
+    /// Writes the value in the machine-readable format used by the
+    /// `datetime` attribute.
+    fn write_iso(&self, w: &mut String);
+
+    /// Writes the value for people to read.
+    ///
+    /// The format string uses the syntax of the crate that the type comes
+    /// from. If there is no format string, or it's invalid, a default is
+    /// used.
+    fn write_display(&self, format: Option<&str>, w: &mut String);
+}
+
+/// A `time` element, with the timestamp in the `datetime` attribute and a
+/// readable version as its content.
+///
+/// Values are shown in the time zone they carry; convert them first (e.g.
+/// with `chrono`'s `with_timezone`) to show them in the reader's.
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "chrono")] {
+/// # use chrono_dep as chrono;
+/// use chrono::{FixedOffset, TimeZone};
+/// use maud::{html, Time};
+///
+/// let launch = FixedOffset::east_opt(9 * 3600)
+///     .unwrap()
+///     .with_ymd_and_hms(2022, 8, 12, 14, 30, 0)
+///     .unwrap();
+/// assert_eq!(
+///     html! { (Time::new(launch).format("%-d %B %Y")) }.into_string(),
+///     r#"<time datetime="2022-08-12T14:30:00+09:00">12 August 2022</time>"#,
+/// );
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Time<'a, T> {
+    value: T,
+    format: Option<&'a str>,
+    relative: bool,
+}
+
+impl<'a, T: Timestamp> Time<'a, T> {
+    /// Wraps a date or time.
+    pub fn new(value: T) -> Self {
+        Time {
+            value,
+            format: None,
+            relative: false,
+        }
+    }
+
+    /// Sets the format of the readable version.
+    pub fn format(mut self, format: &'a str) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Marks the element to be updated with a relative time, such as
+    /// "3 minutes ago", by [`RELATIVE_TIME_SCRIPT`].
+    ///
+    /// The formatted time is still rendered, for readers without
+    /// JavaScript.
+    pub fn relative(mut self) -> Self {
+        self.relative = true;
+        self
+    }
+}
+
+impl<'a, T: Timestamp> Render for Time<'a, T> {
+    fn render_to(&self, w: &mut String) {
+        let mut buffer = String::new();
+        self.value.write_iso(&mut buffer);
+        w.push_str("<time");
+        push_attr("datetime", &buffer, w);
+        if self.relative {
+            w.push_str(" data-relative");
+        }
+        w.push('>');
+        buffer.clear();
+        self.value.write_display(self.format, &mut buffer);
+        escape::escape_to_string(&buffer, w);
+        w.push_str("</time>");
+    }
+}
+
+/// A script that keeps [`relative`](Time::relative) times up to date.
+///
+/// Include it once per page, after the elements it updates. It uses
+/// `Intl.RelativeTimeFormat` in the language of the page's `lang`
+/// attribute, and refreshes every minute. The original text is kept in the
+/// element's `title`.
+pub const RELATIVE_TIME_SCRIPT: PreEscaped<&str> = PreEscaped(concat!(
+    "<script>(function(){",
+    "var f=new Intl.RelativeTimeFormat(document.documentElement.lang||undefined,{numeric:\"auto\"});",
+    "var u=[[\"year\",31536e6],[\"month\",2592e6],[\"week\",6048e5],[\"day\",864e5],",
+    "[\"hour\",36e5],[\"minute\",6e4],[\"second\",1e3]];",
+    "function r(){document.querySelectorAll(\"time[data-relative]\").forEach(function(t){",
+    "var d=new Date(t.dateTime)-Date.now();if(isNaN(d))return;",
+    "if(!t.title)t.title=t.textContent;",
+    "for(var i=0;i<u.length;i++){if(Math.abs(d)>=u[i][1]||i==u.length-1){",
+    "t.textContent=f.format(Math.round(d/u[i][1]),u[i][0]);break}}})}",
+    "r();setInterval(r,6e4)})()</script>",
+));
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use super::Timestamp;
+    use alloc::string::String;
+    use chrono_dep::{
+        format::{Item, StrftimeItems},
+        DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone,
+    };
+    use core::fmt::{Display, Write};
+
+    const DATE_FORMAT: &str = "%B %-d, %Y";
+    const DATE_TIME_FORMAT: &str = "%B %-d, %Y, %H:%M";
+    const OFFSET_DATE_TIME_FORMAT: &str = "%B %-d, %Y, %H:%M %Z";
+
+    /// Parses a format string, falling back to the default if it's invalid.
+    fn items<'a>(format: Option<&'a str>, default: &'a str) -> StrftimeItems<'a> {
+        match format {
+            Some(format) if !StrftimeItems::new(format).any(|item| item == Item::Error) => {
+                StrftimeItems::new(format)
+            }
+            _ => StrftimeItems::new(default),
+        }
+    }
+
+    impl<Tz: TimeZone> Timestamp for DateTime<Tz>
+    where
+        Tz::Offset: Display,
+    {
+        fn write_iso(&self, w: &mut String) {
+            w.push_str(&self.to_rfc3339_opts(SecondsFormat::AutoSi, true));
+        }
+
+        fn write_display(&self, format: Option<&str>, w: &mut String) {
+            let items = items(format, OFFSET_DATE_TIME_FORMAT);
+            let _ = write!(w, "{}", self.format_with_items(items));
+        }
+    }
+
+    impl Timestamp for NaiveDateTime {
+        fn write_iso(&self, w: &mut String) {
+            let _ = write!(w, "{}", self.format("%Y-%m-%dT%H:%M:%S%.f"));
+        }
+
+        fn write_display(&self, format: Option<&str>, w: &mut String) {
+            let items = items(format, DATE_TIME_FORMAT);
+            let _ = write!(w, "{}", self.format_with_items(items));
+        }
+    }
+
+    impl Timestamp for NaiveDate {
+        fn write_iso(&self, w: &mut String) {
+            let _ = write!(w, "{}", self.format("%Y-%m-%d"));
+        }
+
+        fn write_display(&self, format: Option<&str>, w: &mut String) {
+            let items = items(format, DATE_FORMAT);
+            let _ = write!(w, "{}", self.format_with_items(items));
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_support {
+    use super::Timestamp;
+    use alloc::{string::String, vec::Vec};
+    use time_dep::{
+        format_description::{self, well_known::Rfc3339, BorrowedFormatItem},
+        Date, OffsetDateTime, PrimitiveDateTime,
+    };
+
+    const DATE_FORMAT: &str = "[month repr:long] [day padding:none], [year]";
+    const DATE_TIME_FORMAT: &str = "[month repr:long] [day padding:none], [year], [hour]:[minute]";
+    const OFFSET_DATE_TIME_FORMAT: &str =
+        "[month repr:long] [day padding:none], [year], [hour]:[minute] [offset_hour sign:mandatory]:[offset_minute]";
+
+    /// Parses a format description, falling back to the default if it's
+    /// invalid.
+    fn items<'a>(format: Option<&'a str>, default: &'a str) -> Vec<BorrowedFormatItem<'a>> {
+        format
+            .and_then(|format| format_description::parse_borrowed::<2>(format).ok())
+            .unwrap_or_else(|| format_description::parse_borrowed::<2>(default).unwrap_or_default())
+    }
+
+    impl Timestamp for OffsetDateTime {
+        fn write_iso(&self, w: &mut String) {
+            if let Ok(iso) = self.format(&Rfc3339) {
+                w.push_str(&iso);
+            }
+        }
+
+        fn write_display(&self, format: Option<&str>, w: &mut String) {
+            if let Ok(text) = self.format(&items(format, OFFSET_DATE_TIME_FORMAT)) {
+                w.push_str(&text);
+            }
+        }
+    }
+
+    impl Timestamp for PrimitiveDateTime {
+        fn write_iso(&self, w: &mut String) {
+            let items = items(None, "[year]-[month]-[day]T[hour]:[minute]:[second]");
+            if let Ok(iso) = self.format(&items) {
+                w.push_str(&iso);
+            }
+        }
+
+        fn write_display(&self, format: Option<&str>, w: &mut String) {
+            if let Ok(text) = self.format(&items(format, DATE_TIME_FORMAT)) {
+                w.push_str(&text);
+            }
+        }
+    }
+
+    impl Timestamp for Date {
+        fn write_iso(&self, w: &mut String) {
+            if let Ok(iso) = self.format(&items(None, "[year]-[month]-[day]")) {
+                w.push_str(&iso);
+            }
+        }
+
+        fn write_display(&self, format: Option<&str>, w: &mut String) {
+            if let Ok(text) = self.format(&items(format, DATE_FORMAT)) {
+                w.push_str(&text);
+            }
+        }
+    }
+}
+
+impl<T: Timestamp + ?Sized> Timestamp for &T {
+    fn write_iso(&self, w: &mut String) {
+        T::write_iso(self, w);
+    }
+
+    fn write_display(&self, format: Option<&str>, w: &mut String) {
+        T::write_display(self, format, w);
+    }
+}
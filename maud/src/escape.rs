@@ -7,6 +7,16 @@ extern crate alloc;
 use alloc::string::String;
 
 pub fn escape_to_string(input: &str, output: &mut String) {
+    // Only this copy can be changed with `NonAscii`, since the macro doesn't
+    // know how the template will be rendered
+    #[cfg(feature = "escape-non-ascii")]
+    {
+        let non_ascii = crate::non_ascii::current();
+        if (non_ascii.named || non_ascii.numeric) && !input.is_ascii() {
+            escape_non_ascii(input, output, non_ascii.named, non_ascii.numeric);
+            return;
+        }
+    }
     for b in input.bytes() {
         match b {
             b'&' => output.push_str("&amp;"),
@@ -18,6 +28,37 @@ pub fn escape_to_string(input: &str, output: &mut String) {
     }
 }
 
+/// Escapes `input`, writing non-ASCII characters as named character
+/// references where they have one (if `named` is set), and as numeric ones
+/// otherwise (if `numeric` is set).
+#[cfg(feature = "escape-non-ascii")]
+fn escape_non_ascii(input: &str, output: &mut String, named: bool, numeric: bool) {
+    use core::fmt::Write;
+
+    use crate::entities;
+
+    for c in input.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            c if c.is_ascii() => output.push(c),
+            c => match entities::name(c).filter(|_| named) {
+                Some(name) => {
+                    output.push('&');
+                    output.push_str(name);
+                    output.push(';');
+                }
+                None if numeric => {
+                    let _ = write!(output, "&#x{:X};", u32::from(c));
+                }
+                None => output.push(c),
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     extern crate alloc;
@@ -31,4 +72,18 @@ mod test {
         escape_to_string("<script>launchMissiles()</script>", &mut s);
         assert_eq!(s, "&lt;script&gt;launchMissiles()&lt;/script&gt;");
     }
+
+    #[cfg(feature = "escape-non-ascii")]
+    #[test]
+    fn non_ascii() {
+        let escape = |named, numeric| {
+            let mut s = String::new();
+            super::escape_non_ascii("<café> © 🦀", &mut s, named, numeric);
+            s
+        };
+        assert_eq!(escape(false, false), "&lt;café&gt; © 🦀");
+        assert_eq!(escape(true, false), "&lt;caf&eacute;&gt; &copy; 🦀");
+        assert_eq!(escape(false, true), "&lt;caf&#xE9;&gt; &#xA9; &#x1F980;");
+        assert_eq!(escape(true, true), "&lt;caf&eacute;&gt; &copy; &#x1F980;");
+    }
 }
@@ -0,0 +1,176 @@
+//! Rendering pages to static HTML files.
+//!
+//! See [`export`] for details.
+
+extern crate std;
+
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+use std::{fs, io, path::Path};
+
+use crate::Markup;
+
+/// What [`export`] did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Exported {
+    /// The paths of the pages that were written, in the order they were
+    /// found.
+    pub written: Vec<String>,
+    /// The paths that were linked to, but that `render` had no page for.
+    pub missing: Vec<String>,
+}
+
+/// Renders a site to static HTML files, ready to be served from a CDN.
+///
+/// Starting from the paths in `start`, each page is rendered by calling
+/// `render` with its path, and written under `out_dir`. Every internal link
+/// in the output, that is every `href` starting with a single `/`, is
+/// followed in turn, so only the entry points need to be listed. Queries
+/// and fragments are dropped from links.
+///
+/// A path that ends in `/`, or whose last segment has no extension, is
+/// written as `index.html` in a directory of that name, so `/about` becomes
+/// `about/index.html`. Other paths, like `/feed.xml`, are written as they
+/// are. Paths with `..` segments are skipped.
+///
+/// Requires the `export` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{export::export, html};
+///
+/// let out_dir = std::env::temp_dir().join("maud-export-example");
+/// let exported = export(&out_dir, &["/"], |path| match path {
+///     "/" => Some(html! { a href="/about" { "About" } }),
+///     "/about" => Some(html! { a href="/" { "Home" } a href="/team" { "Team" } }),
+///     _ => None,
+/// })
+/// .unwrap();
+/// assert_eq!(exported.written, ["/", "/about"]);
+/// assert_eq!(exported.missing, ["/team"]);
+/// assert!(out_dir.join("about/index.html").exists());
+/// # std::fs::remove_dir_all(&out_dir).unwrap();
+/// ```
+pub fn export(
+    out_dir: impl AsRef<Path>,
+    start: &[&str],
+    mut render: impl FnMut(&str) -> Option<Markup>,
+) -> io::Result<Exported> {
+    let out_dir = out_dir.as_ref();
+    let mut exported = Exported::default();
+    let mut seen = BTreeSet::new();
+    let mut queue: Vec<String> = start.iter().map(|path| String::from(*path)).collect();
+    let mut next = 0;
+    while let Some(path) = queue.get(next).cloned() {
+        next += 1;
+        if !seen.insert(path.clone()) {
+            continue;
+        }
+        let file = match file_path(&path) {
+            Some(file) => out_dir.join(file),
+            None => continue,
+        };
+        let page = match render(&path) {
+            Some(page) => page.into_string(),
+            None => {
+                exported.missing.push(path);
+                continue;
+            }
+        };
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&file, &page)?;
+        queue.extend(internal_links(&page));
+        exported.written.push(path);
+    }
+    Ok(exported)
+}
+
+/// Returns the file to write a page to, relative to the output directory.
+fn file_path(path: &str) -> Option<String> {
+    let path = path.strip_prefix('/')?;
+    if path.split('/').any(|segment| segment == "..") {
+        return None;
+    }
+    let last = path.rsplit('/').next().unwrap_or_default();
+    let mut file = String::from(path);
+    if last.is_empty() {
+        file.push_str("index.html");
+    } else if !last.contains('.') {
+        file.push_str("/index.html");
+    }
+    Some(file)
+}
+
+/// Finds the paths of the internal links in a page.
+fn internal_links(page: &str) -> Vec<String> {
+    hrefs(page)
+        .into_iter()
+        .filter(|href| href.starts_with('/') && !href.starts_with("//"))
+        .map(|href| String::from(href.split(['?', '#']).next().unwrap_or_default()))
+        .collect()
+}
+
+/// Finds the values of the `href` attributes in a page, whether they're
+/// quoted or not.
+fn hrefs(page: &str) -> Vec<String> {
+    let mut hrefs = Vec::new();
+    let mut rest = page;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start + 1..];
+        if let Some(comment) = rest.strip_prefix("!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+        if !rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            continue;
+        }
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(rest.len());
+        let name = &rest[..name_end];
+        rest = &rest[name_end..];
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace() || c == '/');
+            if rest.is_empty() || rest.starts_with('>') {
+                break;
+            }
+            let attr_end = rest
+                .find(|c: char| c.is_ascii_whitespace() || matches!(c, '=' | '/' | '>'))
+                .unwrap_or(rest.len());
+            let attr = &rest[..attr_end];
+            rest = rest[attr_end..].trim_start();
+            let value = match rest.strip_prefix('=').map(str::trim_start) {
+                Some(value) => match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let value = &value[1..];
+                        let end = value.find(quote).unwrap_or(value.len());
+                        rest = value.get(end + 1..).unwrap_or_default();
+                        &value[..end]
+                    }
+                    _ => {
+                        let end = value
+                            .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                            .unwrap_or(value.len());
+                        rest = &value[end..];
+                        &value[..end]
+                    }
+                },
+                None => "",
+            };
+            if attr.eq_ignore_ascii_case("href") {
+                hrefs.push(value.replace("&amp;", "&"));
+            }
+        }
+        // Skip over the contents of elements that aren't parsed as HTML
+        if name.eq_ignore_ascii_case("script") || name.eq_ignore_ascii_case("style") {
+            let close = alloc::format!("</{}", name.to_ascii_lowercase());
+            rest = rest
+                .to_ascii_lowercase()
+                .find(&close)
+                .map_or("", |end| &rest[end..]);
+        }
+    }
+    hrefs
+}
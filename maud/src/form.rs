@@ -0,0 +1,366 @@
+use alloc::{borrow::Cow, string::String};
+
+use crate::{attr::push_attr, escape, Render};
+
+/// A value that can be chosen in a [`Select`], [`CheckboxGroup`] or
+/// [`RadioGroup`].
+///
+/// This is usually implemented for a fieldless enum:
+///
+/// ```rust
+/// use maud::Choice;
+/// use std::borrow::Cow;
+///
+/// #[derive(PartialEq)]
+/// enum Size {
+///     Small,
+///     Large,
+/// }
+///
+/// impl Choice for Size {
+///     fn value(&self) -> Cow<'_, str> {
+///         match self {
+///             Size::Small => "s".into(),
+///             Size::Large => "l".into(),
+///         }
+///     }
+///
+///     fn label(&self) -> Cow<'_, str> {
+///         match self {
+///             Size::Small => "Small".into(),
+///             Size::Large => "Large".into(),
+///         }
+///     }
+/// }
+/// ```
+pub trait Choice: PartialEq {
+    /// Returns the value that is submitted with the form.
+    fn value(&self) -> Cow<'_, str>;
+
+    /// Returns the text shown to the user. Defaults to the value.
+    fn label(&self) -> Cow<'_, str> {
+        self.value()
+    }
+}
+
+impl Choice for str {
+    fn value(&self) -> Cow<'_, str> {
+        self.into()
+    }
+}
+
+impl Choice for String {
+    fn value(&self) -> Cow<'_, str> {
+        self.as_str().into()
+    }
+}
+
+impl<T: Choice + ?Sized> Choice for &T {
+    fn value(&self) -> Cow<'_, str> {
+        T::value(self)
+    }
+
+    fn label(&self) -> Cow<'_, str> {
+        T::label(self)
+    }
+}
+
+/// A `select` element, with the option that matches the current value
+/// marked as `selected`.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Select};
+///
+/// let select = Select::new("flavor", &["vanilla", "chocolate"]).selected(&"chocolate");
+/// assert_eq!(
+///     html! { (select) }.into_string(),
+///     concat!(
+///         r#"<select name="flavor" id="flavor">"#,
+///         r#"<option value="vanilla">vanilla</option>"#,
+///         r#"<option value="chocolate" selected>chocolate</option>"#,
+///         r#"</select>"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Select<'a, T> {
+    name: Cow<'a, str>,
+    id: Option<Cow<'a, str>>,
+    options: &'a [T],
+    selected: Option<&'a T>,
+    placeholder: Option<Cow<'a, str>>,
+    required: bool,
+    error: Option<Cow<'a, str>>,
+}
+
+impl<'a, T: Choice> Select<'a, T> {
+    /// Creates a select with the given name and options.
+    pub fn new(name: impl Into<Cow<'a, str>>, options: &'a [T]) -> Self {
+        Select {
+            name: name.into(),
+            id: None,
+            options,
+            selected: None,
+            placeholder: None,
+            required: false,
+            error: None,
+        }
+    }
+
+    /// Sets the `id`, which defaults to the name.
+    pub fn id(mut self, id: impl Into<Cow<'a, str>>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the selected option.
+    pub fn selected(mut self, selected: &'a T) -> Self {
+        self.selected = Some(selected);
+        self
+    }
+
+    /// Sets the selected option, if any.
+    pub fn selected_opt(mut self, selected: Option<&'a T>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Adds an empty first option with the given text, which is selected if
+    /// nothing else is.
+    pub fn placeholder(mut self, placeholder: impl Into<Cow<'a, str>>) -> Self {
+        self.placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// Requires a value to be chosen.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Shows a validation error after the select.
+    pub fn error(mut self, error: impl Into<Cow<'a, str>>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+}
+
+impl<'a, T: Choice> Render for Select<'a, T> {
+    fn render_to(&self, w: &mut String) {
+        let id = self.id.as_deref().unwrap_or(&self.name);
+        w.push_str("<select");
+        push_attr("name", &self.name, w);
+        push_attr("id", id, w);
+        if self.required {
+            w.push_str(" required");
+        }
+        push_error_attrs(id, self.error.is_some(), w);
+        w.push('>');
+        if let Some(placeholder) = &self.placeholder {
+            w.push_str("<option value=\"\"");
+            if self.selected.is_none() {
+                w.push_str(" selected");
+            }
+            w.push('>');
+            escape::escape_to_string(placeholder, w);
+            w.push_str("</option>");
+        }
+        for option in self.options {
+            w.push_str("<option");
+            push_attr("value", &option.value(), w);
+            if self.selected == Some(option) {
+                w.push_str(" selected");
+            }
+            w.push('>');
+            escape::escape_to_string(&option.label(), w);
+            w.push_str("</option>");
+        }
+        w.push_str("</select>");
+        push_error(id, self.error.as_deref(), w);
+    }
+}
+
+/// A group of checkboxes, with those that match the current values checked.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, CheckboxGroup};
+///
+/// let toppings = ["sprinkles", "nuts"];
+/// let chosen = ["nuts"];
+/// let group = CheckboxGroup::new("toppings", "Toppings", &toppings).checked(&chosen);
+/// assert!(html! { (group) }.into_string().contains(
+///     r#"<label><input type="checkbox" name="toppings" value="nuts" checked>nuts</label>"#
+/// ));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CheckboxGroup<'a, T> {
+    group: Group<'a, T>,
+    checked: &'a [T],
+}
+
+impl<'a, T: Choice> CheckboxGroup<'a, T> {
+    /// Creates a group with the given name, legend and options.
+    pub fn new(
+        name: impl Into<Cow<'a, str>>,
+        legend: impl Into<Cow<'a, str>>,
+        options: &'a [T],
+    ) -> Self {
+        CheckboxGroup {
+            group: Group::new(name.into(), legend.into(), options),
+            checked: &[],
+        }
+    }
+
+    /// Sets the checked options.
+    pub fn checked(mut self, checked: &'a [T]) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Shows a validation error after the checkboxes.
+    pub fn error(mut self, error: impl Into<Cow<'a, str>>) -> Self {
+        self.group.error = Some(error.into());
+        self
+    }
+}
+
+impl<'a, T: Choice> Render for CheckboxGroup<'a, T> {
+    fn render_to(&self, w: &mut String) {
+        self.group
+            .render_to("checkbox", |option| self.checked.contains(option), w);
+    }
+}
+
+/// A group of radio buttons, with the one that matches the current value
+/// checked.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, RadioGroup};
+///
+/// let group = RadioGroup::new("size", "Size", &["small", "large"])
+///     .checked(&"small")
+///     .error("Large is sold out");
+/// assert_eq!(
+///     html! { (group) }.into_string(),
+///     concat!(
+///         r#"<fieldset aria-invalid="true" aria-describedby="size-error"><legend>Size</legend>"#,
+///         r#"<label><input type="radio" name="size" value="small" checked>small</label>"#,
+///         r#"<label><input type="radio" name="size" value="large">large</label>"#,
+///         r#"<p class="field-error" id="size-error">Large is sold out</p>"#,
+///         r#"</fieldset>"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct RadioGroup<'a, T> {
+    group: Group<'a, T>,
+    checked: Option<&'a T>,
+}
+
+impl<'a, T: Choice> RadioGroup<'a, T> {
+    /// Creates a group with the given name, legend and options.
+    pub fn new(
+        name: impl Into<Cow<'a, str>>,
+        legend: impl Into<Cow<'a, str>>,
+        options: &'a [T],
+    ) -> Self {
+        RadioGroup {
+            group: Group::new(name.into(), legend.into(), options),
+            checked: None,
+        }
+    }
+
+    /// Sets the checked option.
+    pub fn checked(mut self, checked: &'a T) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Sets the checked option, if any.
+    pub fn checked_opt(mut self, checked: Option<&'a T>) -> Self {
+        self.checked = checked;
+        self
+    }
+
+    /// Shows a validation error after the radio buttons.
+    pub fn error(mut self, error: impl Into<Cow<'a, str>>) -> Self {
+        self.group.error = Some(error.into());
+        self
+    }
+}
+
+impl<'a, T: Choice> Render for RadioGroup<'a, T> {
+    fn render_to(&self, w: &mut String) {
+        self.group
+            .render_to("radio", |option| self.checked == Some(option), w);
+    }
+}
+
+/// The parts shared by checkbox and radio groups.
+#[derive(Debug, Clone)]
+struct Group<'a, T> {
+    name: Cow<'a, str>,
+    legend: Cow<'a, str>,
+    options: &'a [T],
+    error: Option<Cow<'a, str>>,
+}
+
+impl<'a, T: Choice> Group<'a, T> {
+    fn new(name: Cow<'a, str>, legend: Cow<'a, str>, options: &'a [T]) -> Self {
+        Group {
+            name,
+            legend,
+            options,
+            error: None,
+        }
+    }
+
+    fn render_to(&self, input_type: &str, is_checked: impl Fn(&T) -> bool, w: &mut String) {
+        w.push_str("<fieldset");
+        push_error_attrs(&self.name, self.error.is_some(), w);
+        w.push_str("><legend>");
+        escape::escape_to_string(&self.legend, w);
+        w.push_str("</legend>");
+        for option in self.options {
+            w.push_str("<label><input");
+            push_attr("type", input_type, w);
+            push_attr("name", &self.name, w);
+            push_attr("value", &option.value(), w);
+            if is_checked(option) {
+                w.push_str(" checked");
+            }
+            w.push('>');
+            escape::escape_to_string(&option.label(), w);
+            w.push_str("</label>");
+        }
+        push_error(&self.name, self.error.as_deref(), w);
+        w.push_str("</fieldset>");
+    }
+}
+
+fn push_error_attrs(id: &str, has_error: bool, w: &mut String) {
+    if has_error {
+        w.push_str(" aria-invalid=\"true\"");
+        let mut error_id = String::from(id);
+        error_id.push_str("-error");
+        push_attr("aria-describedby", &error_id, w);
+    }
+}
+
+fn push_error(id: &str, error: Option<&str>, w: &mut String) {
+    if let Some(error) = error {
+        let mut error_id = String::from(id);
+        error_id.push_str("-error");
+        w.push_str("<p class=\"field-error\"");
+        push_attr("id", &error_id, w);
+        w.push('>');
+        escape::escape_to_string(error, w);
+        w.push_str("</p>");
+    }
+}
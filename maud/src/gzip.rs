@@ -0,0 +1,98 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use miniz_oxide::deflate::core::{
+    compress_to_output, create_comp_flags_from_zip_params, CompressorOxide, TDEFLFlush,
+};
+
+/// A gzip header with no file name or timestamp.
+const HEADER: [u8; 10] = [0x1f, 0x8b, 8, 0, 0, 0, 0, 0, 0, 0xff];
+
+/// An empty final block, which ends the deflate stream.
+const FINAL_BLOCK: [u8; 2] = [0x03, 0x00];
+
+/// A gzip-compressed page, rendered by `html_gzip!`.
+///
+/// Send it with `Content-Encoding: gzip`, to clients that accept it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gzipped(Vec<u8>);
+
+impl Gzipped {
+    /// The compressed bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Converts the page into its compressed bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+/// Puts together the output of `html_gzip!`.
+///
+/// Each static segment was compressed by the macro, into blocks that end on
+/// a byte boundary and don't refer back to anything before them. The text
+/// between them is compressed the same way, so the pieces can be joined as
+/// they are.
+#[doc(hidden)]
+pub struct __GzipWriter {
+    bytes: Vec<u8>,
+    crc: crc32fast::Hasher,
+    len: u64,
+    compressor: Box<CompressorOxide>,
+}
+
+impl __GzipWriter {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            bytes: HEADER.to_vec(),
+            crc: crc32fast::Hasher::new(),
+            len: 0,
+            compressor: Box::new(CompressorOxide::new(create_comp_flags_from_zip_params(
+                6, -15, 0,
+            ))),
+        }
+    }
+
+    /// Compresses what's been rendered so far, then adds a precompressed
+    /// segment after it.
+    pub fn push_static(&mut self, pending: &mut String, deflated: &[u8], crc: u32, len: u64) {
+        self.flush(pending);
+        self.bytes.extend_from_slice(deflated);
+        self.crc
+            .combine(&crc32fast::Hasher::new_with_initial_len(crc, len));
+        self.len += len;
+    }
+
+    fn flush(&mut self, pending: &mut String) {
+        if pending.is_empty() {
+            return;
+        }
+        self.crc.update(pending.as_bytes());
+        self.len += pending.len() as u64;
+        // Start afresh, as the compressor can't see the statics in between
+        self.compressor.reset();
+        let bytes = &mut self.bytes;
+        compress_to_output(
+            &mut self.compressor,
+            pending.as_bytes(),
+            TDEFLFlush::Sync,
+            |out| {
+                bytes.extend_from_slice(out);
+                true
+            },
+        );
+        pending.clear();
+    }
+
+    pub fn finish(mut self, pending: &mut String) -> Gzipped {
+        self.flush(pending);
+        self.bytes.extend_from_slice(&FINAL_BLOCK);
+        self.bytes
+            .extend_from_slice(&self.crc.finalize().to_le_bytes());
+        // The length is stored modulo 2^32
+        self.bytes
+            .extend_from_slice(&(self.len as u32).to_le_bytes());
+        Gzipped(self.bytes)
+    }
+}
@@ -0,0 +1,176 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{attr::push_attr, Render};
+
+/// A responsive image, rendered with `srcset`, `sizes`, and intrinsic
+/// dimensions filled in.
+///
+/// The URL pattern can refer to `{width}` and `{format}`, which are
+/// replaced with each of the given widths and formats in turn. If more than
+/// one format is given, the image is wrapped in a `picture` element with a
+/// `source` for each format but the last, which is used for the fallback
+/// `img`.
+///
+/// Images are lazy-loaded by default; call [`eager`](Self::eager) for images
+/// that appear above the fold.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, ResponsiveImage};
+///
+/// let cat = ResponsiveImage::new("/img/cat-{width}.{format}", "A sleepy cat")
+///     .widths([480, 960])
+///     .formats(["webp", "jpg"])
+///     .size(960, 640)
+///     .sizes("(max-width: 600px) 100vw, 50vw");
+///
+/// assert_eq!(
+///     html! { (cat) }.into_string(),
+///     concat!(
+///         r#"<picture>"#,
+///         r#"<source type="image/webp" srcset="/img/cat-480.webp 480w, /img/cat-960.webp 960w" sizes="(max-width: 600px) 100vw, 50vw">"#,
+///         r#"<img src="/img/cat-960.jpg" srcset="/img/cat-480.jpg 480w, /img/cat-960.jpg 960w" sizes="(max-width: 600px) 100vw, 50vw" width="960" height="640" alt="A sleepy cat" loading="lazy" decoding="async">"#,
+///         r#"</picture>"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct ResponsiveImage<'a> {
+    pattern: Cow<'a, str>,
+    alt: Cow<'a, str>,
+    widths: Vec<u32>,
+    formats: Vec<Cow<'a, str>>,
+    size: Option<(u32, u32)>,
+    sizes: Option<Cow<'a, str>>,
+    lazy: bool,
+}
+
+impl<'a> ResponsiveImage<'a> {
+    /// Creates an image from a URL pattern and alt text.
+    pub fn new(pattern: impl Into<Cow<'a, str>>, alt: impl Into<Cow<'a, str>>) -> Self {
+        ResponsiveImage {
+            pattern: pattern.into(),
+            alt: alt.into(),
+            widths: Vec::new(),
+            formats: Vec::new(),
+            size: None,
+            sizes: None,
+            lazy: true,
+        }
+    }
+
+    /// Sets the widths that the image is available in, in pixels.
+    pub fn widths(mut self, widths: impl IntoIterator<Item = u32>) -> Self {
+        self.widths = widths.into_iter().collect();
+        self.widths.sort_unstable();
+        self.widths.dedup();
+        self
+    }
+
+    /// Sets the formats that the image is available in, most preferred
+    /// first, e.g. `["avif", "webp", "jpg"]`.
+    pub fn formats<F: Into<Cow<'a, str>>>(mut self, formats: impl IntoIterator<Item = F>) -> Self {
+        self.formats = formats.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets the intrinsic width and height of the image, which lets the
+    /// browser reserve space for it before it loads.
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = Some((width, height));
+        self
+    }
+
+    /// Sets the `sizes` attribute, which tells the browser how wide the image
+    /// will be displayed.
+    pub fn sizes(mut self, sizes: impl Into<Cow<'a, str>>) -> Self {
+        self.sizes = Some(sizes.into());
+        self
+    }
+
+    /// Loads the image immediately, rather than when it scrolls into view.
+    pub fn eager(mut self) -> Self {
+        self.lazy = false;
+        self
+    }
+
+    fn url(&self, width: Option<u32>, format: Option<&str>) -> String {
+        let mut width_buffer = itoa::Buffer::new();
+        let url = self
+            .pattern
+            .replace(
+                "{width}",
+                width.map_or("", |width| width_buffer.format(width)),
+            )
+            .replace("{format}", format.unwrap_or(""));
+        // Spaces and commas are significant in `srcset`
+        url.replace(' ', "%20").replace(',', "%2C")
+    }
+
+    fn srcset(&self, format: Option<&str>) -> String {
+        let mut srcset = String::new();
+        for &width in &self.widths {
+            if !srcset.is_empty() {
+                srcset.push_str(", ");
+            }
+            let _ = write!(srcset, "{} {}w", self.url(Some(width), format), width);
+        }
+        srcset
+    }
+
+    fn push_srcset_and_sizes(&self, format: Option<&str>, w: &mut String) {
+        if !self.widths.is_empty() {
+            push_attr("srcset", &self.srcset(format), w);
+            if let Some(sizes) = &self.sizes {
+                push_attr("sizes", sizes, w);
+            }
+        }
+    }
+}
+
+impl<'a> Render for ResponsiveImage<'a> {
+    fn render_to(&self, w: &mut String) {
+        let (fallback, sources) = match self.formats.split_last() {
+            Some((fallback, sources)) => (Some(&**fallback), sources),
+            None => (None, &[][..]),
+        };
+        if !sources.is_empty() {
+            w.push_str("<picture>");
+        }
+        for format in sources {
+            w.push_str("<source");
+            push_attr("type", &mime_type(format), w);
+            self.push_srcset_and_sizes(Some(format), w);
+            w.push('>');
+        }
+        w.push_str("<img");
+        push_attr("src", &self.url(self.widths.last().copied(), fallback), w);
+        self.push_srcset_and_sizes(fallback, w);
+        if let Some((width, height)) = self.size {
+            push_attr("width", itoa::Buffer::new().format(width), w);
+            push_attr("height", itoa::Buffer::new().format(height), w);
+        }
+        push_attr("alt", &self.alt, w);
+        if self.lazy {
+            w.push_str(r#" loading="lazy""#);
+        }
+        w.push_str(r#" decoding="async">"#);
+        if !sources.is_empty() {
+            w.push_str("</picture>");
+        }
+    }
+}
+
+fn mime_type(format: &str) -> Cow<'_, str> {
+    match format {
+        "jpg" | "jpeg" => "image/jpeg".into(),
+        "svg" => "image/svg+xml".into(),
+        format => {
+            let mut mime = String::from("image/");
+            mime.push_str(format);
+            mime.into()
+        }
+    }
+}
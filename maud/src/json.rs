@@ -0,0 +1,148 @@
+use alloc::string::String;
+use serde_dep::Serialize;
+
+use crate::{escape, Render};
+
+/// Renders a serializable value as JSON that is safe to embed in element
+/// content, such as a `<script type="application/json">` block.
+///
+/// `<`, `>` and `&` are written as JSON unicode escapes, so the output can't
+/// contain `</script>` or `<!--`. Line and paragraph separators are escaped
+/// too, so the output is also valid JavaScript. The result parses to the
+/// same value as plain `serde_json` output would.
+///
+/// If the value fails to serialize, this renders `null`.
+///
+/// For attribute values, use [`JsonAttr`] instead.
+///
+/// Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Json};
+/// use serde_json::json;
+///
+/// let state = json!({ "user": "<b>Applejack</b>" });
+/// let markup = html! {
+///     script #state type="application/json" { (Json(&state)) }
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<script id="state" type="application/json">{"user":"\u003cb\u003eApplejack\u003c/b\u003e"}</script>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Json<T>(pub T);
+
+impl<T: Serialize> Render for Json<T> {
+    fn render_to(&self, w: &mut String) {
+        match serde_json::to_string(&self.0) {
+            Ok(json) => escape_json_to_string(&json, w),
+            Err(_) => w.push_str("null"),
+        }
+    }
+}
+
+/// Renders a serializable value as JSON that is safe to embed in an
+/// attribute value.
+///
+/// The JSON is HTML-escaped, so it comes back unchanged when the page reads
+/// the attribute (e.g. through `element.dataset`).
+///
+/// If the value fails to serialize, this renders `null`.
+///
+/// Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, JsonAttr};
+/// use serde_json::json;
+///
+/// let markup = html! {
+///     div data-props=(JsonAttr(json!({ "title": "Tom & Jerry" }))) {}
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<div data-props="{&quot;title&quot;:&quot;Tom &amp; Jerry&quot;}"></div>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct JsonAttr<T>(pub T);
+
+impl<T: Serialize> Render for JsonAttr<T> {
+    fn render_to(&self, w: &mut String) {
+        match serde_json::to_string(&self.0) {
+            Ok(json) => escape::escape_to_string(&json, w),
+            Err(_) => w.push_str("null"),
+        }
+    }
+}
+
+/// Renders a serializable value as a `<script type="application/ld+json">`
+/// block of structured data.
+///
+/// This is what `@json_ld(value)` expands to. The JSON is escaped the same
+/// way as [`Json`], so a value containing `</script>` can't end the block
+/// early.
+///
+/// Values that fail to serialize render nothing.
+///
+/// Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+/// use serde_json::json;
+///
+/// let article = json!({
+///     "@context": "https://schema.org",
+///     "@type": "Article",
+///     "headline": "</script><script>alert(1)</script>",
+/// });
+/// let markup = html! {
+///     @json_ld(article)
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r#"<script type="application/ld+json">"#,
+///         r#"{"@context":"https://schema.org","@type":"Article","headline":"\u003c/script\u003e\u003cscript\u003ealert(1)\u003c/script\u003e"}"#,
+///         r#"</script>"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct JsonLd<T>(pub T);
+
+impl<T: Serialize> Render for JsonLd<T> {
+    fn render_to(&self, w: &mut String) {
+        let json = match serde_json::to_string(&self.0) {
+            Ok(json) => json,
+            Err(_) => return,
+        };
+        w.push_str(r#"<script type="application/ld+json">"#);
+        escape_json_to_string(&json, w);
+        w.push_str("</script>");
+    }
+}
+
+/// Escapes characters in serialized JSON that are special in HTML or
+/// JavaScript.
+///
+/// These can only appear inside JSON strings, where a unicode escape means
+/// the same thing.
+fn escape_json_to_string(json: &str, w: &mut String) {
+    for c in json.chars() {
+        match c {
+            '<' => w.push_str("\\u003c"),
+            '>' => w.push_str("\\u003e"),
+            '&' => w.push_str("\\u0026"),
+            '\u{2028}' => w.push_str("\\u2028"),
+            '\u{2029}' => w.push_str("\\u2029"),
+            c => w.push(c),
+        }
+    }
+}
@@ -11,12 +11,91 @@
 
 extern crate alloc;
 
-use alloc::{borrow::Cow, boxed::Box, string::String};
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, sync::Arc};
 use core::fmt::{self, Arguments, Display, Write};
 
-pub use maud_macros::html;
-
+pub use maud_macros::{asset, html, routes, try_html, xml};
+
+#[cfg(feature = "gzip")]
+pub use maud_macros::html_gzip;
+#[cfg(feature = "futures")]
+pub use maud_macros::html_stream;
+
+mod attr;
+mod combine;
+#[cfg(feature = "context")]
+pub mod context;
+#[cfg(feature = "serde")]
+mod data;
+mod datetime;
+mod entities;
 mod escape;
+#[cfg(feature = "export")]
+pub mod export;
+mod form;
+#[cfg(feature = "gzip")]
+mod gzip;
+mod image;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "limits")]
+mod limits;
+pub mod locale;
+#[cfg(feature = "escape-non-ascii")]
+mod non_ascii;
+mod nonce;
+mod number;
+mod pagination;
+#[cfg(feature = "panic-context")]
+mod panic_context;
+mod portal;
+mod route;
+mod scoped;
+mod social;
+#[cfg(feature = "futures")]
+mod stream;
+pub mod style;
+mod table;
+mod text;
+#[cfg(feature = "context")]
+mod theme;
+mod url;
+mod wizard;
+mod xml;
+
+pub use attr::Attributes;
+#[cfg(feature = "serde")]
+pub use data::data_attributes;
+pub use datetime::{Time, Timestamp, RELATIVE_TIME_SCRIPT};
+pub use form::{CheckboxGroup, Choice, RadioGroup, Select};
+#[cfg(feature = "gzip")]
+pub use gzip::{__GzipWriter, Gzipped};
+pub use image::ResponsiveImage;
+#[cfg(feature = "serde")]
+pub use json::{Json, JsonAttr, JsonLd};
+#[cfg(feature = "limits")]
+pub use limits::{__LimitFrame, LimitExceeded, Limits};
+#[cfg(feature = "escape-non-ascii")]
+pub use non_ascii::{__XmlNonAscii, NonAscii};
+pub use nonce::Nonce;
+pub use number::{Currency, Decimal, Localize, Localized, Number, Percent, PercentDecimal};
+pub use pagination::{Feed, Page, Pagination};
+#[cfg(feature = "panic-context")]
+pub use panic_context::__PanicContext;
+pub use portal::{mount_portals, PORTAL_SCRIPT};
+pub use route::Route;
+pub use scoped::hoist_styles;
+pub use social::{SocialImage, SocialMeta, SocialMetaError};
+#[cfg(feature = "futures")]
+pub use stream::{stream, Sink, Streaming};
+pub use style::Style;
+pub use table::{Column, SortDirection, Table};
+pub use text::{plain_text, EmailBody};
+#[cfg(feature = "context")]
+pub use theme::{Theme, THEME_SCRIPT};
+pub use url::{UrlBuilder, BLOCKED_URL};
+pub use wizard::{Wizard, WizardStep};
+pub use xml::Cdata;
 
 /// An adapter that escapes HTML special characters.
 ///
@@ -132,13 +211,13 @@ impl<'a> Render for Arguments<'a> {
     }
 }
 
-impl<'a, T: Render + ?Sized> Render for &'a T {
+impl<T: Render + ?Sized> Render for &T {
     fn render_to(&self, w: &mut String) {
         T::render_to(self, w);
     }
 }
 
-impl<'a, T: Render + ?Sized> Render for &'a mut T {
+impl<T: Render + ?Sized> Render for &mut T {
     fn render_to(&self, w: &mut String) {
         T::render_to(self, w);
     }
@@ -150,6 +229,139 @@ impl<T: Render + ?Sized> Render for Box<T> {
     }
 }
 
+impl<T: Render + ?Sized> Render for Rc<T> {
+    fn render_to(&self, w: &mut String) {
+        T::render_to(self, w);
+    }
+}
+
+impl<T: Render + ?Sized> Render for Arc<T> {
+    fn render_to(&self, w: &mut String) {
+        T::render_to(self, w);
+    }
+}
+
+/// Represents a type that can be rendered as HTML, but might fail.
+///
+/// Values of these types can be spliced into a [`try_html!`] template,
+/// which stops at the first error and returns it. Types that implement
+/// [`Render`] can be spliced there too.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{try_html, Markup, TryRender};
+/// use std::fmt;
+///
+/// struct Price(&'static str);
+///
+/// impl TryRender for Price {
+///     type Error = fmt::Error;
+///
+///     fn try_render_to(&self, buffer: &mut String) -> Result<(), fmt::Error> {
+///         let cents: u32 = self.0.parse().map_err(|_| fmt::Error)?;
+///         buffer.push_str(&format!("${}.{:02}", cents / 100, cents % 100));
+///         Ok(())
+///     }
+/// }
+///
+/// fn price_tag(price: Price) -> Result<Markup, fmt::Error> {
+///     try_html! {
+///         span.price { (price) }
+///     }
+/// }
+///
+/// assert_eq!(
+///     price_tag(Price("1250")).unwrap().into_string(),
+///     r#"<span class="price">$12.50</span>"#,
+/// );
+/// assert!(price_tag(Price("free")).is_err());
+/// ```
+pub trait TryRender {
+    /// The type of error that rendering can fail with.
+    type Error;
+
+    /// Appends a representation of `self` to the given buffer.
+    ///
+    /// If this fails, anything already written to the buffer is thrown away
+    /// by `try_html!`. As with [`Render::render_to`], no further escaping is
+    /// performed on the data written.
+    fn try_render_to(&self, buffer: &mut String) -> Result<(), Self::Error>;
+
+    /// Renders `self` as a block of `Markup`.
+    fn try_render(&self) -> Result<Markup, Self::Error> {
+        let mut buffer = String::new();
+        self.try_render_to(&mut buffer)?;
+        Ok(PreEscaped(buffer))
+    }
+}
+
+impl<T: TryRender + ?Sized> TryRender for &T {
+    type Error = T::Error;
+
+    fn try_render_to(&self, w: &mut String) -> Result<(), T::Error> {
+        T::try_render_to(self, w)
+    }
+}
+
+impl<T: TryRender + ?Sized> TryRender for Box<T> {
+    type Error = T::Error;
+
+    fn try_render_to(&self, w: &mut String) -> Result<(), T::Error> {
+        T::try_render_to(self, w)
+    }
+}
+
+impl<T: TryRender + ?Sized> TryRender for Rc<T> {
+    type Error = T::Error;
+
+    fn try_render_to(&self, w: &mut String) -> Result<(), T::Error> {
+        T::try_render_to(self, w)
+    }
+}
+
+impl<T: TryRender + ?Sized> TryRender for Arc<T> {
+    type Error = T::Error;
+
+    fn try_render_to(&self, w: &mut String) -> Result<(), T::Error> {
+        T::try_render_to(self, w)
+    }
+}
+
+// Splices in `try_html!` call `(&__TryWrap(&value)).__maud_try_render_to()`.
+// Method resolution tries `__TryRenderKind` first, as its receiver needs one
+// less reference, and falls back to `Render` otherwise.
+
+#[doc(hidden)]
+pub struct __TryWrap<'a, T: ?Sized>(pub &'a T);
+
+#[doc(hidden)]
+pub trait __TryRenderKind {
+    type Error;
+
+    fn __maud_try_render_to<E: From<Self::Error>>(&self, w: &mut String) -> Result<(), E>;
+}
+
+impl<'a, T: TryRender + ?Sized> __TryRenderKind for __TryWrap<'a, T> {
+    type Error = T::Error;
+
+    fn __maud_try_render_to<E: From<T::Error>>(&self, w: &mut String) -> Result<(), E> {
+        self.0.try_render_to(w).map_err(E::from)
+    }
+}
+
+#[doc(hidden)]
+pub trait __RenderKind {
+    fn __maud_try_render_to<E>(&self, w: &mut String) -> Result<(), E>;
+}
+
+impl<'a, T: Render + ?Sized> __RenderKind for &__TryWrap<'a, T> {
+    fn __maud_try_render_to<E>(&self, w: &mut String) -> Result<(), E> {
+        self.0.render_to(w);
+        Ok(())
+    }
+}
+
 macro_rules! impl_render_with_display {
     ($($ty:ty)*) => {
         $(
@@ -265,6 +477,15 @@ impl<T: AsRef<str> + Into<String>> From<PreEscaped<T>> for String {
 /// ```
 pub const DOCTYPE: PreEscaped<&'static str> = PreEscaped("<!DOCTYPE html>");
 
+/// Polls the next item from a stream, for `@for await` loops.
+#[cfg(feature = "futures")]
+#[doc(hidden)]
+pub async fn __stream_next<S: futures_core::Stream + ?Sized>(
+    mut stream: core::pin::Pin<&mut S>,
+) -> Option<S::Item> {
+    core::future::poll_fn(|cx| stream.as_mut().poll_next(cx)).await
+}
+
 #[cfg(feature = "rocket")]
 mod rocket_support {
     extern crate std;
@@ -339,4 +560,66 @@ mod axum_support {
             (headers, self.0).into_response()
         }
     }
+
+    #[cfg(feature = "futures")]
+    mod streaming {
+        use crate::Streaming;
+        use axum_core::{
+            body::{self, BoxBody},
+            response::IntoResponse,
+        };
+        use bytes::Bytes;
+        use core::{
+            convert::Infallible,
+            future::Future,
+            pin::Pin,
+            task::{Context, Poll},
+        };
+        use futures_core::Stream;
+        use http::{header, HeaderMap, HeaderValue, Response};
+
+        impl<Fut: Future<Output = ()>> http_body::Body for Streaming<Fut> {
+            type Data = Bytes;
+            type Error = Infallible;
+
+            fn poll_data(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Bytes, Infallible>>> {
+                self.poll_next(cx)
+                    .map(|chunk| chunk.map(|chunk| Ok(Bytes::from(chunk))))
+            }
+
+            fn poll_trailers(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Result<Option<HeaderMap>, Infallible>> {
+                Poll::Ready(Ok(None))
+            }
+        }
+
+        impl<Fut: Future<Output = ()> + Send + 'static> IntoResponse for Streaming<Fut> {
+            fn into_response(self) -> Response<BoxBody> {
+                let mut response = Response::new(body::boxed(self));
+                response.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("text/html; charset=utf-8"),
+                );
+                response
+            }
+        }
+    }
+
+    #[cfg(feature = "gzip")]
+    impl IntoResponse for crate::Gzipped {
+        fn into_response(self) -> Response<BoxBody> {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                header::CONTENT_TYPE,
+                HeaderValue::from_static("text/html; charset=utf-8"),
+            );
+            headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+            (headers, self.into_bytes()).into_response()
+        }
+    }
 }
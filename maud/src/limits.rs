@@ -0,0 +1,206 @@
+extern crate std;
+
+use alloc::vec::Vec;
+use core::{
+    cell::{Cell, RefCell},
+    fmt,
+};
+
+std::thread_local! {
+    static STATE: RefCell<Option<State>> = const { RefCell::new(None) };
+}
+
+struct State {
+    limits: Limits,
+    /// For each template being rendered, innermost last: how much it has
+    /// written so far, and how deep in its elements it is.
+    frames: Vec<(usize, usize)>,
+    /// The first limit that was gone over, after which every template
+    /// stops.
+    exceeded: Option<LimitExceeded>,
+}
+
+/// Limits on how much markup a page can render, to stop a runaway loop
+/// from building a response gigabytes long.
+///
+/// Templates rendered in [`Limits::render`] stop as soon as their output
+/// goes over [`max_bytes`](Limits::max_bytes), or their elements nest
+/// deeper than [`max_depth`](Limits::max_depth), and `render` returns the
+/// error. Output is counted as each splice and loop iteration starts, so a
+/// template can go over by the size of one splice before it's stopped.
+///
+/// Templates stop by skipping the rest of their content, without
+/// unwinding, so this works with `panic = "abort"` too. Code in `f` outside
+/// of templates keeps running, but any template it renders after a limit
+/// is hit stops straight away.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, LimitExceeded, Limits};
+///
+/// let limits = Limits::new().max_bytes(1024);
+/// let result = limits.render(|| {
+///     html! {
+///         @for i in 0.. {
+///             p { (i) }
+///         }
+///     }
+/// });
+/// assert_eq!(result.unwrap_err(), LimitExceeded::Bytes(1024));
+///
+/// let page = limits.render(|| html! { p { "Hello!" } }).unwrap();
+/// assert_eq!(page.into_string(), "<p>Hello!</p>");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    max_bytes: Option<usize>,
+    max_depth: Option<usize>,
+}
+
+impl Limits {
+    /// No limits, to be added with the methods below.
+    pub fn new() -> Limits {
+        Limits::default()
+    }
+
+    /// Stops rendering once the output is more than `bytes` long.
+    pub fn max_bytes(mut self, bytes: usize) -> Limits {
+        self.max_bytes = Some(bytes);
+        self
+    }
+
+    /// Stops rendering once elements are nested more than `depth` deep,
+    /// counting those in nested templates.
+    pub fn max_depth(mut self, depth: usize) -> Limits {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Calls `f`, stopping any templates it renders once they go over the
+    /// limits.
+    ///
+    /// Returns what `f` returned, or the first limit that was gone over.
+    pub fn render<T>(&self, f: impl FnOnce() -> T) -> Result<T, LimitExceeded> {
+        let state = State {
+            limits: *self,
+            frames: Vec::new(),
+            exceeded: None,
+        };
+        // Put back the outer limits however `f` ends, so a panic doesn't
+        // leave these in place
+        let _outer = Outer(STATE.with(|s| s.borrow_mut().replace(state)));
+        let value = f();
+        match STATE.with(|s| s.borrow().as_ref().and_then(|state| state.exceeded)) {
+            Some(exceeded) => Err(exceeded),
+            None => Ok(value),
+        }
+    }
+}
+
+/// The limits that were in place before a call to [`Limits::render`].
+struct Outer(Option<State>);
+
+impl Drop for Outer {
+    fn drop(&mut self) {
+        STATE.with(|s| *s.borrow_mut() = self.0.take());
+    }
+}
+
+/// The limit that a render went over, returned by [`Limits::render`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The output was longer than this many bytes.
+    Bytes(usize),
+    /// Elements were nested deeper than this.
+    Depth(usize),
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LimitExceeded::Bytes(max) => write!(f, "rendered more than {} bytes", max),
+            LimitExceeded::Depth(max) => write!(f, "nested elements more than {} deep", max),
+        }
+    }
+}
+
+impl std::error::Error for LimitExceeded {}
+
+/// Tracks one template's progress against the limits, for the `limits`
+/// feature.
+#[doc(hidden)]
+pub struct __LimitFrame {
+    active: bool,
+    /// How much has been sent on already, by `@flush` and `@suspend` in
+    /// `html_stream!`, which empty the buffer as they go.
+    sent: Cell<usize>,
+}
+
+impl __LimitFrame {
+    /// Starts a template whose elements nest `depth` deep at most.
+    pub fn enter(depth: usize) -> __LimitFrame {
+        let active = STATE.with(|s| {
+            let mut s = s.borrow_mut();
+            let state = match s.as_mut() {
+                Some(state) => state,
+                None => return false,
+            };
+            let outer = state.frames.iter().map(|&(_, depth)| depth).sum::<usize>();
+            state.frames.push((0, 0));
+            if let Some(max) = state.limits.max_depth {
+                if outer + depth > max {
+                    state.exceeded.get_or_insert(LimitExceeded::Depth(max));
+                }
+            }
+            true
+        });
+        __LimitFrame {
+            active,
+            sent: Cell::new(0),
+        }
+    }
+
+    /// Records that `len` bytes have been sent, and taken out of the
+    /// buffer that [`check`](Self::check) is given the length of.
+    pub fn sent(&self, len: usize) {
+        self.sent.set(self.sent.get() + len);
+    }
+
+    /// Records that the template has `len` bytes in its buffer, and is
+    /// `depth` elements in. Returns whether the template should stop.
+    pub fn check(&self, len: usize, depth: usize) -> bool {
+        if !self.active {
+            return false;
+        }
+        STATE.with(|s| {
+            let mut s = s.borrow_mut();
+            let state = match s.as_mut() {
+                Some(state) => state,
+                None => return false,
+            };
+            if let Some(frame) = state.frames.last_mut() {
+                *frame = (self.sent.get() + len, depth);
+            }
+            let total = state.frames.iter().map(|&(len, _)| len).sum::<usize>();
+            if let Some(max) = state.limits.max_bytes {
+                if total > max {
+                    state.exceeded.get_or_insert(LimitExceeded::Bytes(max));
+                }
+            }
+            state.exceeded.is_some()
+        })
+    }
+}
+
+impl Drop for __LimitFrame {
+    fn drop(&mut self) {
+        if self.active {
+            STATE.with(|s| {
+                if let Some(state) = s.borrow_mut().as_mut() {
+                    state.frames.pop();
+                }
+            });
+        }
+    }
+}
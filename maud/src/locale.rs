@@ -0,0 +1,121 @@
+//! Locales for formatting numbers.
+//!
+//! See [`Number`](crate::Number) for how to use these. To support another
+//! locale, implement [`Locale`] for your own type.
+
+use alloc::string::String;
+
+/// How numbers are written in a particular language and region.
+pub trait Locale {
+    /// The character between the integer and fractional parts, e.g. `.` in
+    /// `1.5`.
+    fn decimal_separator(&self) -> char;
+
+    /// The character between each group of three integer digits, e.g. `,`
+    /// in `1,000`.
+    fn group_separator(&self) -> char;
+
+    /// Writes a formatted amount of money, given the already formatted
+    /// number and an ISO 4217 currency code like `"EUR"`.
+    ///
+    /// The default puts the currency symbol after the number, separated by
+    /// a non-breaking space.
+    fn write_currency(&self, number: &str, code: &str, w: &mut String) {
+        w.push_str(number);
+        w.push('\u{a0}');
+        w.push_str(currency_symbol(code));
+    }
+
+    /// Writes a formatted percentage, given the already formatted number.
+    ///
+    /// The default puts a non-breaking space between the number and `%`.
+    fn write_percent(&self, number: &str, w: &mut String) {
+        w.push_str(number);
+        w.push_str("\u{a0}%");
+    }
+}
+
+/// English, e.g. `1,234.5`, `$1,234.50` and `12%`.
+///
+/// This is the default.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct En;
+
+impl Locale for En {
+    fn decimal_separator(&self) -> char {
+        '.'
+    }
+
+    fn group_separator(&self) -> char {
+        ','
+    }
+
+    fn write_currency(&self, number: &str, code: &str, w: &mut String) {
+        let symbol = currency_symbol(code);
+        w.push_str(symbol);
+        // Codes without a symbol read better with a space, e.g. `CHF 10.00`
+        if symbol == code {
+            w.push('\u{a0}');
+        }
+        w.push_str(number);
+    }
+
+    fn write_percent(&self, number: &str, w: &mut String) {
+        w.push_str(number);
+        w.push('%');
+    }
+}
+
+/// German, e.g. `1.234,5`, `1.234,50 €` and `12 %`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct De;
+
+impl Locale for De {
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn group_separator(&self) -> char {
+        '.'
+    }
+}
+
+/// French, e.g. `1 234,5`, `1 234,50 €` and `12 %`.
+///
+/// Groups are separated by a narrow non-breaking space.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Fr;
+
+impl Locale for Fr {
+    fn decimal_separator(&self) -> char {
+        ','
+    }
+
+    fn group_separator(&self) -> char {
+        '\u{202f}'
+    }
+}
+
+/// Returns the symbol for a currency code, or the code itself if there is
+/// no widely recognized symbol.
+pub fn currency_symbol(code: &str) -> &str {
+    match code {
+        "USD" => "$",
+        "EUR" => "€",
+        "GBP" => "£",
+        "JPY" => "¥",
+        "INR" => "₹",
+        "KRW" => "₩",
+        code => code,
+    }
+}
+
+/// Returns the number of decimal places that amounts in a currency are
+/// usually shown with.
+pub fn currency_decimals(code: &str) -> usize {
+    match code {
+        "JPY" | "KRW" | "ISK" | "CLP" | "VND" => 0,
+        "BHD" | "KWD" | "OMR" | "TND" => 3,
+        _ => 2,
+    }
+}
@@ -0,0 +1,108 @@
+extern crate std;
+
+use core::cell::Cell;
+
+std::thread_local! {
+    static CURRENT: Cell<NonAscii> = const { Cell::new(NonAscii::new()) };
+}
+
+/// How to write non-ASCII characters, for pages that pass through
+/// something that only carries ASCII, such as some email gateways.
+///
+/// They're written as UTF-8 by default. Inside [`NonAscii::render`], text
+/// and attribute values write them as character references instead,
+/// whether they're literals in a template or come from a splice. Markup
+/// that's already escaped, like [`PreEscaped`](crate::PreEscaped), is left
+/// as it is.
+///
+/// Requires the `escape-non-ascii` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, NonAscii};
+///
+/// let crab = "🦀";
+/// let page = NonAscii::new()
+///     .named()
+///     .numeric()
+///     .render(|| html! { p { "Café " (crab) } });
+/// assert_eq!(page.into_string(), "<p>Caf&eacute; &#x1F980;</p>");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NonAscii {
+    pub(crate) named: bool,
+    pub(crate) numeric: bool,
+}
+
+impl NonAscii {
+    /// Writes non-ASCII characters as they are, to be changed with the
+    /// methods below.
+    pub const fn new() -> NonAscii {
+        NonAscii {
+            named: false,
+            numeric: false,
+        }
+    }
+
+    /// Writes characters that have a named reference, like `é` and `©`, as
+    /// that reference: `&eacute;` and `&copy;`.
+    pub fn named(mut self) -> NonAscii {
+        self.named = true;
+        self
+    }
+
+    /// Writes every other non-ASCII character as a numeric reference, like
+    /// `&#x1F980;`.
+    pub fn numeric(mut self) -> NonAscii {
+        self.numeric = true;
+        self
+    }
+
+    /// Calls `f`, writing non-ASCII characters this way in everything it
+    /// renders.
+    pub fn render<T>(&self, f: impl FnOnce() -> T) -> T {
+        let _outer = Outer(CURRENT.with(|current| current.replace(*self)));
+        f()
+    }
+}
+
+/// Puts back the setting from outside [`NonAscii::render`] when it ends,
+/// however it ends.
+struct Outer(NonAscii);
+
+impl Drop for Outer {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.0));
+    }
+}
+
+/// Writes non-ASCII characters as numeric references instead of named ones
+/// while an `xml!` template renders, since XML doesn't know HTML's names.
+#[doc(hidden)]
+pub struct __XmlNonAscii {
+    outer: NonAscii,
+}
+
+impl __XmlNonAscii {
+    pub fn enter() -> __XmlNonAscii {
+        let outer = CURRENT.with(|current| {
+            current.replace(NonAscii {
+                named: false,
+                numeric: current.get().named || current.get().numeric,
+            })
+        });
+        __XmlNonAscii { outer }
+    }
+}
+
+impl Drop for __XmlNonAscii {
+    fn drop(&mut self) {
+        CURRENT.with(|current| current.set(self.outer));
+    }
+}
+
+/// How non-ASCII characters are being written on this thread.
+pub(crate) fn current() -> NonAscii {
+    CURRENT.with(Cell::get)
+}
@@ -0,0 +1,99 @@
+use alloc::string::String;
+
+use crate::{attr::push_attr, PreEscaped, Render};
+
+/// A Content Security Policy nonce, for the scripts and styles on a page.
+///
+/// Provide one around the page with `@provide` (this needs the `context`
+/// feature), and every `@style` block gets a matching `nonce` attribute.
+/// Use [`Nonce::current`] for other elements, and [`Nonce::attach`] for
+/// the scripts that come with Maud, like [`THEME_SCRIPT`].
+///
+/// [`THEME_SCRIPT`]: crate::THEME_SCRIPT
+///
+/// # Example
+///
+/// ```rust
+/// # #[cfg(feature = "context")] {
+/// use maud::{html, Nonce, PORTAL_SCRIPT};
+///
+/// let nonce = Nonce::new("r4nd0m");
+/// let markup = html! {
+///     @provide(nonce) {
+///         @style { "p { color: purple }" }
+///         script nonce=[Nonce::current()] { "start()" }
+///         (Nonce::attach(PORTAL_SCRIPT))
+///     }
+/// };
+/// let markup = markup.into_string();
+/// assert_eq!(markup.matches(r#" nonce="r4nd0m""#).count(), 3);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Nonce(String);
+
+impl Nonce {
+    /// Wraps a nonce, which should be new and random for each response.
+    pub fn new(nonce: impl Into<String>) -> Nonce {
+        Nonce(nonce.into())
+    }
+
+    /// Returns the nonce provided around the current render, if there is
+    /// one.
+    pub fn current() -> Option<Nonce> {
+        #[cfg(feature = "context")]
+        {
+            crate::context::get()
+        }
+        #[cfg(not(feature = "context"))]
+        {
+            None
+        }
+    }
+
+    /// Returns the nonce as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Adds the current nonce, if there is one, to the start tag of a
+    /// script or stylesheet.
+    pub fn attach(element: PreEscaped<&str>) -> PreEscaped<String> {
+        let element = element.0;
+        let nonce = match Nonce::current() {
+            Some(nonce) => nonce,
+            None => return PreEscaped(String::from(element)),
+        };
+        let name_end = element
+            .find(|c: char| c.is_whitespace() || c == '>')
+            .unwrap_or(element.len());
+        let mut result = String::with_capacity(element.len() + nonce.0.len() + 9);
+        result.push_str(&element[..name_end]);
+        push_attr("nonce", &nonce.0, &mut result);
+        result.push_str(&element[name_end..]);
+        PreEscaped(result)
+    }
+
+    /// Renders ` nonce="..."` if there's a current nonce. This is what
+    /// `@style` writes after its other attributes.
+    #[doc(hidden)]
+    pub fn __attr() -> impl Render {
+        struct NonceAttr(Option<Nonce>);
+
+        impl Render for NonceAttr {
+            fn render_to(&self, w: &mut String) {
+                if let Some(nonce) = &self.0 {
+                    push_attr("nonce", &nonce.0, w);
+                }
+            }
+        }
+
+        NonceAttr(Nonce::current())
+    }
+}
+
+impl Render for Nonce {
+    fn render_to(&self, w: &mut String) {
+        self.0.render_to(w);
+    }
+}
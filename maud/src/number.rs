@@ -0,0 +1,237 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{
+    escape,
+    locale::{self, En, Locale},
+    Render,
+};
+
+/// A number with thousands separators.
+///
+/// Numbers are rendered in English by default; call
+/// [`in_locale`](Localize::in_locale) for another locale.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, locale::De, Localize, Number};
+///
+/// assert_eq!(html! { (Number(1234567.891).decimals(2)) }.into_string(), "1,234,567.89");
+/// assert_eq!(html! { (Number(1234567).in_locale(&De)) }.into_string(), "1.234.567");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Number<T>(pub T);
+
+/// A number with a fixed number of decimal places, created by
+/// [`Number::decimals`].
+#[derive(Debug, Clone, Copy)]
+pub struct Decimal {
+    value: f64,
+    decimals: usize,
+}
+
+/// An amount of money, in the currency with the given ISO 4217 code.
+///
+/// The amount is rounded to the usual number of decimal places for the
+/// currency.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, locale::Fr, Currency, Localize};
+///
+/// assert_eq!(html! { (Currency(-1234.5, "USD")) }.into_string(), "-$1,234.50");
+/// assert_eq!(
+///     html! { (Currency(1234.5, "EUR").in_locale(&Fr)) }.into_string(),
+///     "1\u{202f}234,50\u{a0}€",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Currency<'a>(pub f64, pub &'a str);
+
+/// A fraction shown as a percentage, so `Percent(0.25)` renders `25%`.
+///
+/// Call [`decimals`](Self::decimals) to show more precision.
+#[derive(Debug, Clone, Copy)]
+pub struct Percent(pub f64);
+
+/// A value rendered in a particular locale, created by
+/// [`Localize::in_locale`].
+#[derive(Clone, Copy)]
+pub struct Localized<'a, T> {
+    value: T,
+    locale: &'a dyn Locale,
+}
+
+/// Values that can be formatted for a [`Locale`].
+pub trait Localize: Sized {
+    /// Writes the value, formatted for the given locale.
+    fn write_localized(&self, locale: &dyn Locale, w: &mut String);
+
+    /// Renders the value in the given locale rather than English.
+    fn in_locale(self, locale: &dyn Locale) -> Localized<'_, Self> {
+        Localized {
+            value: self,
+            locale,
+        }
+    }
+}
+
+impl<'a, T: Localize> Render for Localized<'a, T> {
+    fn render_to(&self, w: &mut String) {
+        let mut buffer = String::new();
+        self.value.write_localized(self.locale, &mut buffer);
+        escape::escape_to_string(&buffer, w);
+    }
+}
+
+macro_rules! impl_render_with_localize {
+    ($($ty:ty)*) => {
+        $(
+            impl Render for $ty {
+                fn render_to(&self, w: &mut String) {
+                    self.in_locale(&En).render_to(w);
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_localize_for_integers {
+    ($($ty:ty)*) => {
+        $(
+            impl Localize for Number<$ty> {
+                fn write_localized(&self, locale: &dyn Locale, w: &mut String) {
+                    write_number(itoa::Buffer::new().format(self.0), locale, w);
+                }
+            }
+
+            impl_render_with_localize!(Number<$ty>);
+        )*
+    };
+}
+
+impl_localize_for_integers! {
+    i8 i16 i32 i64 i128 isize
+    u8 u16 u32 u64 u128 usize
+}
+
+impl Number<f64> {
+    /// Rounds the number to the given number of decimal places.
+    pub fn decimals(self, decimals: usize) -> Decimal {
+        Decimal {
+            value: self.0,
+            decimals,
+        }
+    }
+}
+
+impl Localize for Number<f64> {
+    fn write_localized(&self, locale: &dyn Locale, w: &mut String) {
+        let mut buffer = String::new();
+        let _ = write!(buffer, "{}", self.0);
+        write_number(&buffer, locale, w);
+    }
+}
+
+impl Localize for Decimal {
+    fn write_localized(&self, locale: &dyn Locale, w: &mut String) {
+        write_fixed(self.value, self.decimals, locale, w);
+    }
+}
+
+impl<'a> Localize for Currency<'a> {
+    fn write_localized(&self, locale: &dyn Locale, w: &mut String) {
+        let Currency(amount, code) = *self;
+        let mut number = String::new();
+        write_fixed(
+            amount.abs(),
+            locale::currency_decimals(code),
+            locale,
+            &mut number,
+        );
+        if amount.is_sign_negative() && number.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+            w.push('-');
+        }
+        locale.write_currency(&number, code, w);
+    }
+}
+
+impl Percent {
+    /// Rounds the percentage to the given number of decimal places.
+    pub fn decimals(self, decimals: usize) -> PercentDecimal {
+        PercentDecimal {
+            value: self.0,
+            decimals,
+        }
+    }
+}
+
+/// A percentage with a fixed number of decimal places, created by
+/// [`Percent::decimals`].
+#[derive(Debug, Clone, Copy)]
+pub struct PercentDecimal {
+    value: f64,
+    decimals: usize,
+}
+
+impl Localize for Percent {
+    fn write_localized(&self, locale: &dyn Locale, w: &mut String) {
+        PercentDecimal {
+            value: self.0,
+            decimals: 0,
+        }
+        .write_localized(locale, w);
+    }
+}
+
+impl Localize for PercentDecimal {
+    fn write_localized(&self, locale: &dyn Locale, w: &mut String) {
+        let mut number = String::new();
+        write_fixed(self.value * 100.0, self.decimals, locale, &mut number);
+        locale.write_percent(&number, w);
+    }
+}
+
+impl_render_with_localize! {
+    Number<f64> Decimal Currency<'_> Percent PercentDecimal
+}
+
+fn write_fixed(value: f64, decimals: usize, locale: &dyn Locale, w: &mut String) {
+    let mut buffer = String::new();
+    let _ = write!(buffer, "{:.*}", decimals, value);
+    // Don't show "-0"
+    if buffer.starts_with('-') && !buffer.bytes().any(|b| (b'1'..=b'9').contains(&b)) {
+        buffer.remove(0);
+    }
+    write_number(&buffer, locale, w);
+}
+
+/// Adds separators to a number formatted with `.` as the decimal point.
+fn write_number(number: &str, locale: &dyn Locale, w: &mut String) {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number),
+    };
+    // Leave things like `inf` and `NaN` alone
+    if !unsigned.starts_with(|c: char| c.is_ascii_digit()) {
+        w.push_str(number);
+        return;
+    }
+    let (integer, fraction) = match unsigned.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (unsigned, None),
+    };
+    w.push_str(sign);
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            w.push(locale.group_separator());
+        }
+        w.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        w.push(locale.decimal_separator());
+        w.push_str(fraction);
+    }
+}
@@ -0,0 +1,335 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use crate::{attr::push_attr, escape, Render};
+
+/// One page of a paginated list.
+///
+/// Page numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page<T> {
+    /// The items on this page.
+    pub items: Vec<T>,
+    /// The number of this page.
+    pub number: usize,
+    /// The maximum number of items on each page.
+    pub per_page: usize,
+    /// The number of items across all pages.
+    pub total_items: usize,
+}
+
+impl<T> Page<T> {
+    /// Creates a page from its items and position in the list.
+    pub fn new(items: Vec<T>, number: usize, per_page: usize, total_items: usize) -> Self {
+        Page {
+            items,
+            number,
+            per_page,
+            total_items,
+        }
+    }
+
+    /// Returns the number of pages in the list.
+    pub fn total_pages(&self) -> usize {
+        if self.per_page == 0 {
+            return 0;
+        }
+        self.total_items.div_ceil(self.per_page)
+    }
+
+    /// Whether there is a page before this one.
+    pub fn has_prev(&self) -> bool {
+        self.number > 1
+    }
+
+    /// Whether there is a page after this one.
+    pub fn has_next(&self) -> bool {
+        self.number < self.total_pages()
+    }
+
+    /// Returns links to the other pages of the list, given a function from a
+    /// page number to its URL.
+    pub fn pagination<'a, F: Fn(usize) -> String>(&self, href: F) -> Pagination<'a, F> {
+        Pagination::new(self.number, self.total_pages(), href)
+    }
+}
+
+/// Links to the pages of a paginated list.
+///
+/// Renders a `nav` with links to the previous and next pages, the first and
+/// last pages, and the pages around the current one. Gaps between these are
+/// shown as an ellipsis. Nothing is rendered if there is only one page.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Page};
+///
+/// let page = Page::new(vec!["..."; 10], 5, 10, 200);
+/// let nav = page.pagination(|n| format!("/posts?page={}", n)).window(1);
+/// let html = html! { (nav) }.into_string();
+/// assert!(html.contains(r#"<a href="/posts?page=5" aria-label="Page 5" aria-current="page">5</a>"#));
+/// assert!(html.contains(r#"<span aria-hidden="true">…</span>"#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Pagination<'a, F> {
+    current: usize,
+    total: usize,
+    href: F,
+    window: usize,
+    label: Cow<'a, str>,
+    prev_label: Cow<'a, str>,
+    next_label: Cow<'a, str>,
+}
+
+impl<'a, F: Fn(usize) -> String> Pagination<'a, F> {
+    /// Creates links for `total` pages, with `current` selected.
+    pub fn new(current: usize, total: usize, href: F) -> Self {
+        Pagination {
+            current,
+            total,
+            href,
+            window: 2,
+            label: "Pagination".into(),
+            prev_label: "Previous".into(),
+            next_label: "Next".into(),
+        }
+    }
+
+    /// Sets how many pages to show on each side of the current one. The
+    /// default is 2.
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+
+    /// Sets the `aria-label` of the `nav` element.
+    pub fn label(mut self, label: impl Into<Cow<'a, str>>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Sets the text of the link to the previous page.
+    pub fn prev_label(mut self, prev_label: impl Into<Cow<'a, str>>) -> Self {
+        self.prev_label = prev_label.into();
+        self
+    }
+
+    /// Sets the text of the link to the next page.
+    pub fn next_label(mut self, next_label: impl Into<Cow<'a, str>>) -> Self {
+        self.next_label = next_label.into();
+        self
+    }
+
+    /// Returns the page numbers to link to, with `None` for each gap.
+    fn pages(&self) -> Vec<Option<usize>> {
+        let start = self.current.saturating_sub(self.window).max(1);
+        let end = self.current.saturating_add(self.window).min(self.total);
+        let mut pages = Vec::new();
+        if start > 1 {
+            pages.push(Some(1));
+        }
+        // Don't hide a single page behind an ellipsis
+        match start {
+            0..=2 => {}
+            3 => pages.push(Some(2)),
+            _ => pages.push(None),
+        }
+        pages.extend((start..=end).map(Some));
+        match self.total - end {
+            0 | 1 => {}
+            2 => pages.push(Some(self.total - 1)),
+            _ => pages.push(None),
+        }
+        if end < self.total {
+            pages.push(Some(self.total));
+        }
+        pages
+    }
+
+    fn push_link(&self, page: usize, rel: &str, text: &str, w: &mut String) {
+        w.push_str("<li><a");
+        push_attr("href", &(self.href)(page), w);
+        w.push_str(" rel=\"");
+        w.push_str(rel);
+        w.push_str("\">");
+        escape::escape_to_string(text, w);
+        w.push_str("</a></li>");
+    }
+
+    fn push_disabled(&self, text: &str, w: &mut String) {
+        w.push_str("<li><span aria-disabled=\"true\">");
+        escape::escape_to_string(text, w);
+        w.push_str("</span></li>");
+    }
+}
+
+impl<'a, F: Fn(usize) -> String> Render for Pagination<'a, F> {
+    fn render_to(&self, w: &mut String) {
+        if self.total <= 1 {
+            return;
+        }
+        w.push_str("<nav class=\"pagination\"");
+        push_attr("aria-label", &self.label, w);
+        w.push_str("><ul>");
+        if self.current > 1 {
+            self.push_link(self.current - 1, "prev", &self.prev_label, w);
+        } else {
+            self.push_disabled(&self.prev_label, w);
+        }
+        let mut buffer = itoa::Buffer::new();
+        for page in self.pages() {
+            match page {
+                Some(page) => {
+                    let number = buffer.format(page);
+                    w.push_str("<li><a");
+                    push_attr("href", &(self.href)(page), w);
+                    w.push_str(" aria-label=\"Page ");
+                    w.push_str(number);
+                    w.push('"');
+                    if page == self.current {
+                        w.push_str(" aria-current=\"page\"");
+                    }
+                    w.push('>');
+                    w.push_str(number);
+                    w.push_str("</a></li>");
+                }
+                None => w.push_str("<li><span aria-hidden=\"true\">…</span></li>"),
+            }
+        }
+        if self.current < self.total {
+            self.push_link(self.current + 1, "next", &self.next_label, w);
+        } else {
+            self.push_disabled(&self.next_label, w);
+        }
+        w.push_str("</ul></nav>");
+    }
+}
+
+/// A list that loads older items a page at a time by cursor, and takes new
+/// items at the top as they arrive, like a timeline or activity log.
+///
+/// Items are kept newest first. An item is known by the key that `key`
+/// gives it, so one that shows up in both a page and a live update is only
+/// listed once.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Feed};
+///
+/// struct Post { id: u32, text: &'static str }
+///
+/// let mut feed = Feed::new(|post: &Post| post.id);
+/// feed.load_older(
+///     vec![Post { id: 3, text: "Third" }, Post { id: 2, text: "Second" }],
+///     Some("2".to_string()),
+/// );
+/// // A new post arrives, along with an edit to one already shown
+/// feed.push_newer(vec![Post { id: 4, text: "Fourth" }, Post { id: 3, text: "Third!" }]);
+///
+/// let html = html! {
+///     @for post in feed.items() {
+///         p { (post.text) }
+///     }
+///     @if let Some(href) = feed.next_href(|cursor| format!("/posts?before={}", cursor)) {
+///         a href=(href) rel="next" { "Older posts" }
+///     }
+/// };
+/// assert_eq!(
+///     html.into_string(),
+///     r#"<p>Fourth</p><p>Third!</p><p>Second</p><a href="/posts?before=2" rel="next">Older posts</a>"#,
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Feed<T, F> {
+    items: Vec<T>,
+    key: F,
+    cursor: Option<String>,
+    loaded: bool,
+}
+
+impl<T, K: PartialEq, F: Fn(&T) -> K> Feed<T, F> {
+    /// Creates an empty feed, where `key` tells items apart.
+    pub fn new(key: F) -> Self {
+        Feed {
+            items: Vec::new(),
+            key,
+            cursor: None,
+            loaded: false,
+        }
+    }
+
+    /// Adds a page of older items at the bottom, newest first, leaving out
+    /// any already listed. `next_cursor` is where the page after this one
+    /// starts, or `None` if this was the last.
+    pub fn load_older(&mut self, items: impl IntoIterator<Item = T>, next_cursor: Option<String>) {
+        for item in items {
+            if self.position(&item).is_none() {
+                self.items.push(item);
+            }
+        }
+        self.cursor = next_cursor;
+        self.loaded = true;
+    }
+
+    /// Adds new items at the top, newest first. An item that's already
+    /// listed is replaced where it is instead, and one that's given more
+    /// than once is only taken the first time.
+    pub fn push_newer(&mut self, items: impl IntoIterator<Item = T>) {
+        let mut seen = Vec::new();
+        let mut newer = Vec::new();
+        for item in items {
+            let key = (self.key)(&item);
+            if seen.contains(&key) {
+                continue;
+            }
+            seen.push(key);
+            match self.position(&item) {
+                Some(i) => self.items[i] = item,
+                None => newer.push(item),
+            }
+        }
+        self.items.splice(0..0, newer);
+    }
+
+    /// Takes an item out of the feed, returning it if it was there.
+    pub fn remove(&mut self, key: &K) -> Option<T> {
+        let i = self
+            .items
+            .iter()
+            .position(|item| (self.key)(item) == *key)?;
+        Some(self.items.remove(i))
+    }
+
+    /// The items, newest first.
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Returns the items, newest first.
+    pub fn into_items(self) -> Vec<T> {
+        self.items
+    }
+
+    /// The cursor of the next page of older items, if there is one.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// Whether there are older items still to load. This is true before the
+    /// first page is loaded.
+    pub fn has_more(&self) -> bool {
+        !self.loaded || self.cursor.is_some()
+    }
+
+    /// Returns the URL of the next page of older items, given a function
+    /// from a cursor to its URL.
+    pub fn next_href(&self, href: impl Fn(&str) -> String) -> Option<String> {
+        self.cursor().map(href)
+    }
+
+    fn position(&self, item: &T) -> Option<usize> {
+        let key = (self.key)(item);
+        self.items.iter().position(|other| (self.key)(other) == key)
+    }
+}
@@ -0,0 +1,32 @@
+extern crate std;
+
+/// Says where a splice was if it panics, for the `panic-context` feature.
+///
+/// The panic itself is left as is; this only prints a note to stderr as
+/// the guard is dropped while unwinding, like
+/// `note: panicked while rendering div.user-card at src/views/user.rs:42`.
+/// When templates are nested, there's one note for each, innermost first.
+/// With `panic = "abort"` there's no unwinding, so there's no note.
+#[doc(hidden)]
+pub struct __PanicContext {
+    element: &'static str,
+    location: &'static str,
+}
+
+impl __PanicContext {
+    pub fn new(element: &'static str, location: &'static str) -> __PanicContext {
+        __PanicContext { element, location }
+    }
+}
+
+impl Drop for __PanicContext {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            std::eprintln!(
+                "note: panicked while rendering {} at {}",
+                self.element,
+                self.location
+            );
+        }
+    }
+}
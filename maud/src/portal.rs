@@ -0,0 +1,136 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{escape, Markup, PreEscaped};
+
+const OPEN: &str = "<template data-maud-portal=\"";
+const CLOSE: &str = "</template>";
+
+/// Moves the contents of each `@portal` into its target element.
+///
+/// A `@portal(target = "#modals") { ... }` renders its body in a
+/// `<template>`, so that it isn't shown where it's written. Call this on
+/// the finished page to move each body to the end of the element with that
+/// ID. Portals whose target isn't an ID on the page are left for
+/// [`PORTAL_SCRIPT`] to mount in the browser, which also handles other
+/// selectors.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Markup};
+///
+/// fn confirm(message: &str) -> Markup {
+///     html! {
+///         button { "Delete" }
+///         @portal(target = "#modals") {
+///             dialog { (message) }
+///         }
+///     }
+/// }
+///
+/// let page = maud::mount_portals(html! {
+///     main { (confirm("Are you sure?")) }
+///     #modals {}
+/// });
+/// assert_eq!(
+///     page.into_string(),
+///     r#"<main><button>Delete</button></main><div id="modals"><dialog>Are you sure?</dialog></div>"#,
+/// );
+/// ```
+pub fn mount_portals(markup: Markup) -> Markup {
+    let page = markup.into_string();
+    let mut result = String::with_capacity(page.len());
+    let mut portals = Vec::new();
+    let mut rest = page.as_str();
+    while let Some(start) = rest.find(OPEN) {
+        let content_start = start + OPEN.len();
+        let end = match find_close(&rest[content_start..], "template") {
+            Some((close, len)) => content_start + close + len,
+            None => break,
+        };
+        result.push_str(&rest[..start]);
+        portals.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    let mut unmounted = String::new();
+    for portal in portals {
+        let target = portal[OPEN.len()..].split('"').next().unwrap_or_default();
+        let content_start = portal.find('>').map_or(portal.len(), |i| i + 1);
+        let content = &portal[content_start..portal.len() - CLOSE.len()];
+        match target
+            .strip_prefix('#')
+            .and_then(|id| end_of_element(&result, id))
+        {
+            Some(index) => result.insert_str(index, content),
+            None => unmounted.push_str(portal),
+        }
+    }
+    let body_end = result.rfind("</body>").unwrap_or(result.len());
+    result.insert_str(body_end, &unmounted);
+    PreEscaped(result)
+}
+
+/// A script that mounts any portals left on the page, for pages that
+/// aren't passed through [`mount_portals`].
+///
+/// Include it once per page, after the portals and their targets.
+pub const PORTAL_SCRIPT: PreEscaped<&str> = PreEscaped(concat!(
+    "<script>document.querySelectorAll(\"template[data-maud-portal]\").forEach(function(t){",
+    "var e=document.querySelector(t.getAttribute(\"data-maud-portal\"));",
+    "if(e){e.appendChild(t.content);t.remove()}})</script>",
+));
+
+/// Finds where the end tag of the element with this ID starts.
+fn end_of_element(page: &str, id: &str) -> Option<usize> {
+    let mut attr = String::from(" id=\"");
+    escape::escape_to_string(id, &mut attr);
+    attr.push('"');
+    let attr_at = page.find(&attr)?;
+    let tag_start = page[..attr_at].rfind('<')?;
+    let name_len = page[tag_start + 1..]
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .unwrap_or(0);
+    let name = &page[tag_start + 1..tag_start + 1 + name_len];
+    let content_start = attr_at + page[attr_at..].find('>')? + 1;
+    let (close, _) = find_close(&page[content_start..], name)?;
+    Some(content_start + close)
+}
+
+/// Finds the end tag that closes an element, given the markup after its
+/// start tag, and returns where it starts and how long it is. Elements of
+/// the same name nested inside are skipped over.
+fn find_close(s: &str, name: &str) -> Option<(usize, usize)> {
+    let open = format!("<{}", name);
+    let close = format!("</{}>", name);
+    let mut depth = 0usize;
+    let mut pos = 0;
+    loop {
+        let next_close = pos + s[pos..].find(&close)?;
+        match find_open(&s[pos..], &open).map(|i| pos + i) {
+            Some(next_open) if next_open < next_close => {
+                depth += 1;
+                pos = next_open + open.len();
+            }
+            _ if depth > 0 => {
+                depth -= 1;
+                pos = next_close + close.len();
+            }
+            _ => return Some((next_close, close.len())),
+        }
+    }
+}
+
+/// Finds the next start tag that begins with `open`, like `<div`, and not
+/// one that only starts the same way, like `<divider`.
+fn find_open(s: &str, open: &str) -> Option<usize> {
+    let mut pos = 0;
+    loop {
+        let at = pos + s[pos..].find(open)?;
+        let after = s[at + open.len()..].chars().next();
+        if matches!(after, Some(c) if c.is_whitespace() || c == '>' || c == '/') {
+            return Some(at);
+        }
+        pos = at + open.len();
+    }
+}
@@ -0,0 +1,83 @@
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+use crate::{escape, url::percent_encode_to_string, Render, UrlBuilder};
+
+/// A path built by a function declared with [`routes!`](crate::routes).
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// maud::routes! {
+///     user_post(user: &str, id: u32) => "/users/{user}/posts/{id}";
+/// }
+///
+/// let route = user_post("Twilight Sparkle", 7);
+/// assert_eq!(route.as_str(), "/users/Twilight%20Sparkle/posts/7");
+/// assert_eq!(
+///     html! { a href=(route) { "Read more" } }.into_string(),
+///     r#"<a href="/users/Twilight%20Sparkle/posts/7">Read more</a>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Route(String);
+
+impl Route {
+    #[doc(hidden)]
+    pub fn __new() -> Route {
+        Route(String::new())
+    }
+
+    #[doc(hidden)]
+    pub fn __push_str(&mut self, s: &str) {
+        self.0.push_str(s);
+    }
+
+    #[doc(hidden)]
+    pub fn __push_param(&mut self, value: &impl Display) {
+        let mut buffer = String::new();
+        let _ = write!(buffer, "{}", value);
+        percent_encode_to_string(&buffer, &mut self.0);
+    }
+
+    /// Returns the path as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts the path to a string.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
+    /// Adds a query parameter, returning a [`UrlBuilder`].
+    pub fn query(self, key: &'static str, value: impl Display) -> UrlBuilder<'static> {
+        UrlBuilder::from(self).query(key, value)
+    }
+}
+
+impl Display for Route {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Render for Route {
+    fn render_to(&self, w: &mut String) {
+        escape::escape_to_string(&self.0, w);
+    }
+}
+
+impl From<Route> for String {
+    fn from(route: Route) -> String {
+        route.0
+    }
+}
+
+impl From<Route> for UrlBuilder<'static> {
+    fn from(route: Route) -> UrlBuilder<'static> {
+        UrlBuilder::new(route.0)
+    }
+}
@@ -0,0 +1,77 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{Markup, PreEscaped};
+
+/// Moves the stylesheets generated by `@style` blocks into the page's
+/// `<head>`, keeping only one copy of each.
+///
+/// A component with an `@style` block renders its stylesheet alongside its
+/// markup, so a component used many times on a page will repeat it. Call
+/// this on the finished page to emit each stylesheet once. If the page has
+/// no `</head>`, the stylesheets are placed at the start instead, after the
+/// doctype if there is one.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Markup};
+///
+/// fn badge(text: &str) -> Markup {
+///     html! {
+///         @style { "span { font-weight: bold }" }
+///         span { (text) }
+///     }
+/// }
+///
+/// let page = maud::hoist_styles(html! {
+///     html {
+///         head { title { "Badges" } }
+///         body { (badge("new")) (badge("hot")) }
+///     }
+/// });
+/// let page = page.into_string();
+/// assert_eq!(page.matches("<style").count(), 1);
+/// assert!(page.contains("<title>Badges</title><style"));
+/// ```
+pub fn hoist_styles(markup: Markup) -> Markup {
+    const OPEN: &str = "<style data-maud-scope=\"";
+    const CLOSE: &str = "</style>";
+
+    let page = markup.into_string();
+    let mut result = String::with_capacity(page.len());
+    let mut styles = String::new();
+    let mut seen = Vec::new();
+    let mut rest = page.as_str();
+    while let Some(start) = rest.find(OPEN) {
+        let end = match rest[start..].find(CLOSE) {
+            Some(len) => start + len + CLOSE.len(),
+            None => break,
+        };
+        result.push_str(&rest[..start]);
+        let style = &rest[start..end];
+        let scope = style[OPEN.len()..].split('"').next().unwrap_or_default();
+        if !seen.contains(&scope) {
+            seen.push(scope);
+            styles.push_str(style);
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    let at = result
+        .find("</head>")
+        .unwrap_or_else(|| doctype_end(&result));
+    result.insert_str(at, &styles);
+    PreEscaped(result)
+}
+
+/// Where the content after a leading `<!DOCTYPE ...>` starts, or 0 if there
+/// isn't one.
+fn doctype_end(page: &str) -> usize {
+    let start = page.len() - page.trim_start().len();
+    match page.get(start..start + 9) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("<!doctype") => page[start..]
+            .find('>')
+            .map_or(page.len(), |end| start + end + 1),
+        _ => 0,
+    }
+}
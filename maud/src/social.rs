@@ -0,0 +1,225 @@
+use alloc::{borrow::Cow, string::String};
+use core::fmt;
+
+use crate::{attr::push_attr, Render};
+
+/// Open Graph and Twitter card metadata for a page.
+///
+/// Renders the full set of `og:` and `twitter:` meta tags, so they don't
+/// have to be kept in sync by hand. Call [`validate`](Self::validate) (for
+/// example in a test, or when building the value) to check that the
+/// metadata will be accepted by the major link preview crawlers.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, SocialImage, SocialMeta};
+///
+/// let meta = SocialMeta::new("Maud", "https://maud.lambda.xyz/")
+///     .description("Compile-time HTML templates")
+///     .image(SocialImage::new("https://maud.lambda.xyz/maud.png", 1200, 630).alt("The Maud logo"))
+///     .twitter_site("@rustlang");
+/// assert!(meta.validate().is_ok());
+///
+/// let markup = html! {
+///     head { (meta) }
+/// };
+/// assert!(markup.into_string().contains(
+///     r#"<meta property="og:title" content="Maud"><meta name="twitter:title" content="Maud">"#
+/// ));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SocialMeta<'a> {
+    title: Cow<'a, str>,
+    url: Cow<'a, str>,
+    kind: Cow<'a, str>,
+    description: Option<Cow<'a, str>>,
+    site_name: Option<Cow<'a, str>>,
+    image: Option<SocialImage<'a>>,
+    twitter_site: Option<Cow<'a, str>>,
+}
+
+/// An image to show in link previews.
+#[derive(Debug, Clone)]
+pub struct SocialImage<'a> {
+    url: Cow<'a, str>,
+    width: u32,
+    height: u32,
+    alt: Option<Cow<'a, str>>,
+}
+
+/// A problem found by [`SocialMeta::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SocialMetaError {
+    /// The title is empty.
+    MissingTitle,
+    /// The page or image URL is not absolute. Crawlers don't resolve
+    /// relative URLs.
+    RelativeUrl(&'static str),
+    /// The image is smaller than the 200×200 minimum.
+    ImageTooSmall,
+    /// The image is larger than the 4096×4096 maximum.
+    ImageTooLarge,
+    /// The image has no alt text.
+    MissingImageAlt,
+}
+
+impl fmt::Display for SocialMetaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SocialMetaError::MissingTitle => f.write_str("title must not be empty"),
+            SocialMetaError::RelativeUrl(field) => write!(f, "{} must be an absolute URL", field),
+            SocialMetaError::ImageTooSmall => f.write_str("image must be at least 200×200"),
+            SocialMetaError::ImageTooLarge => f.write_str("image must be at most 4096×4096"),
+            SocialMetaError::MissingImageAlt => f.write_str("image must have alt text"),
+        }
+    }
+}
+
+impl<'a> SocialMeta<'a> {
+    /// Creates metadata for the page with the given title and canonical URL.
+    pub fn new(title: impl Into<Cow<'a, str>>, url: impl Into<Cow<'a, str>>) -> Self {
+        SocialMeta {
+            title: title.into(),
+            url: url.into(),
+            kind: "website".into(),
+            description: None,
+            site_name: None,
+            image: None,
+            twitter_site: None,
+        }
+    }
+
+    /// Sets the `og:type`, which defaults to `website`.
+    pub fn kind(mut self, kind: impl Into<Cow<'a, str>>) -> Self {
+        self.kind = kind.into();
+        self
+    }
+
+    /// Sets a one or two sentence description of the page.
+    pub fn description(mut self, description: impl Into<Cow<'a, str>>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Sets the name of the overall site.
+    pub fn site_name(mut self, site_name: impl Into<Cow<'a, str>>) -> Self {
+        self.site_name = Some(site_name.into());
+        self
+    }
+
+    /// Sets the preview image.
+    pub fn image(mut self, image: SocialImage<'a>) -> Self {
+        self.image = Some(image);
+        self
+    }
+
+    /// Sets the Twitter handle of the site, e.g. `@rustlang`.
+    pub fn twitter_site(mut self, handle: impl Into<Cow<'a, str>>) -> Self {
+        self.twitter_site = Some(handle.into());
+        self
+    }
+
+    /// Checks that the metadata is complete and well-formed.
+    pub fn validate(&self) -> Result<(), SocialMetaError> {
+        if self.title.trim().is_empty() {
+            return Err(SocialMetaError::MissingTitle);
+        }
+        if !is_absolute(&self.url) {
+            return Err(SocialMetaError::RelativeUrl("url"));
+        }
+        if let Some(image) = &self.image {
+            if !is_absolute(&image.url) {
+                return Err(SocialMetaError::RelativeUrl("image url"));
+            }
+            if image.width < 200 || image.height < 200 {
+                return Err(SocialMetaError::ImageTooSmall);
+            }
+            if image.width > 4096 || image.height > 4096 {
+                return Err(SocialMetaError::ImageTooLarge);
+            }
+            if image.alt.as_deref().unwrap_or("").trim().is_empty() {
+                return Err(SocialMetaError::MissingImageAlt);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> SocialImage<'a> {
+    /// Creates an image with the given absolute URL and dimensions.
+    pub fn new(url: impl Into<Cow<'a, str>>, width: u32, height: u32) -> Self {
+        SocialImage {
+            url: url.into(),
+            width,
+            height,
+            alt: None,
+        }
+    }
+
+    /// Sets the alt text of the image.
+    pub fn alt(mut self, alt: impl Into<Cow<'a, str>>) -> Self {
+        self.alt = Some(alt.into());
+        self
+    }
+
+    /// Whether the image is big enough for a large Twitter card.
+    fn is_large(&self) -> bool {
+        self.width >= 300 && self.height >= 157 && self.width > self.height
+    }
+}
+
+impl<'a> Render for SocialMeta<'a> {
+    fn render_to(&self, w: &mut String) {
+        both("title", &self.title, w);
+        og("type", &self.kind, w);
+        og("url", &self.url, w);
+        if let Some(description) = &self.description {
+            both("description", description, w);
+        }
+        if let Some(site_name) = &self.site_name {
+            og("site_name", site_name, w);
+        }
+        let card = match &self.image {
+            Some(image) if image.is_large() => "summary_large_image",
+            _ => "summary",
+        };
+        twitter("card", card, w);
+        if let Some(twitter_site) = &self.twitter_site {
+            twitter("site", twitter_site, w);
+        }
+        if let Some(image) = &self.image {
+            both("image", &image.url, w);
+            og("image:width", itoa::Buffer::new().format(image.width), w);
+            og("image:height", itoa::Buffer::new().format(image.height), w);
+            if let Some(alt) = &image.alt {
+                both("image:alt", alt, w);
+            }
+        }
+    }
+}
+
+fn is_absolute(url: &str) -> bool {
+    url.starts_with("https://") || url.starts_with("http://")
+}
+
+fn og(property: &str, content: &str, w: &mut String) {
+    w.push_str("<meta property=\"og:");
+    w.push_str(property);
+    w.push('"');
+    push_attr("content", content, w);
+    w.push('>');
+}
+
+fn twitter(name: &str, content: &str, w: &mut String) {
+    w.push_str("<meta name=\"twitter:");
+    w.push_str(name);
+    w.push('"');
+    push_attr("content", content, w);
+    w.push('>');
+}
+
+fn both(name: &str, content: &str, w: &mut String) {
+    og(name, content, w);
+    twitter(name, content, w);
+}
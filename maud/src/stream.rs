@@ -0,0 +1,211 @@
+extern crate std;
+
+use alloc::{boxed::Box, collections::VecDeque, format, string::String, sync::Arc, vec::Vec};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_core::Stream;
+use std::sync::Mutex;
+
+use crate::{attr::push_attr, Nonce, Render};
+
+/// A `@suspend` that hasn't resolved yet, rendering the chunk that fills
+/// its slot.
+type Suspended = Pin<Box<dyn Future<Output = String> + Send>>;
+
+/// Where `html_stream!` sends each chunk of a page.
+#[derive(Clone, Default)]
+pub struct Sink {
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Default)]
+struct Shared {
+    chunks: VecDeque<String>,
+    suspended: Vec<Suspended>,
+    slots: usize,
+}
+
+impl Sink {
+    /// Queues a chunk to be sent. Empty chunks are skipped.
+    pub fn send(&self, chunk: String) {
+        if !chunk.is_empty() {
+            self.lock().chunks.push_back(chunk);
+        }
+    }
+
+    /// Reserves a slot for a `@suspend`, and returns its number.
+    ///
+    /// The number goes in the slot's markers: a
+    /// `<template id="maud-slot-N">` before the placeholder and a
+    /// `<!--/maud-slot-N-->` after it.
+    pub fn slot(&self) -> usize {
+        let mut shared = self.lock();
+        let slot = shared.slots;
+        shared.slots += 1;
+        slot
+    }
+
+    /// Renders `future` once it resolves, and sends it to fill `slot`.
+    ///
+    /// This is what `@suspend` calls, once its placeholder has been sent.
+    /// The future isn't polled before then, so its content can't arrive
+    /// ahead of the slot it replaces, even if the placeholder awaits.
+    ///
+    /// The swap script gets the [`Nonce`] provided around the `@suspend`,
+    /// since the future resolves outside of it.
+    pub fn suspend<F>(&self, slot: usize, future: F)
+    where
+        F: Future + Send + 'static,
+        F::Output: Render,
+    {
+        let nonce = Nonce::current();
+        self.lock().suspended.push(Box::pin(async move {
+            let mut html = String::new();
+            future.await.render_to(&mut html);
+            fill(slot, &html, nonce.as_ref())
+        }));
+    }
+
+    fn next(&self) -> Option<String> {
+        self.lock().chunks.pop_front()
+    }
+
+    fn take_suspended(&self) -> Vec<Suspended> {
+        core::mem::take(&mut self.lock().suspended)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Shared> {
+        // A panic while pushing or popping can't leave the queue half done
+        self.shared
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+}
+
+impl core::fmt::Debug for Sink {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let shared = self.lock();
+        f.debug_struct("Sink")
+            .field("chunks", &shared.chunks)
+            .field("suspended", &shared.suspended.len())
+            .finish()
+    }
+}
+
+/// The chunk that swaps the content of a resolved `@suspend` into its slot.
+///
+/// The content goes in a `<template>`, so it isn't shown where it lands,
+/// and a script moves it over the placeholder. If the slot has gone, say
+/// because it was inside another placeholder, the script does nothing.
+fn fill(slot: usize, html: &str, nonce: Option<&Nonce>) -> String {
+    let mut script = String::from("<script");
+    if let Some(nonce) = nonce {
+        push_attr("nonce", nonce.as_str(), &mut script);
+    }
+    script.push('>');
+    format!(
+        concat!(
+            "<template id=\"maud-fill-{slot}\">{html}</template>",
+            "{script}(function(){{",
+            "var s=document.getElementById(\"maud-slot-{slot}\"),",
+            "f=document.getElementById(\"maud-fill-{slot}\"),n;",
+            "document.currentScript.remove();",
+            "if(!s){{f.remove();return}}",
+            "while((n=s.nextSibling)&&!(n.nodeType===8&&n.data===\"/maud-slot-{slot}\"))n.remove();",
+            "if(n)n.remove();",
+            "s.replaceWith(f.content);f.remove()",
+            "}})()</script>",
+        ),
+        slot = slot,
+        html = html,
+        script = script,
+    )
+}
+
+/// Renders a page in chunks, as a [`Stream`] that yields each one as soon
+/// as it's flushed.
+///
+/// `render` is given the [`Sink`] to pass to [`html_stream!`]. Everything
+/// before a `@flush` reaches the client while the rest of the page, and
+/// anything it awaits, is still being worked on.
+///
+/// A `@suspend(future) { placeholder }` sends its placeholder straight
+/// away and carries on with the rest of the page. The future runs
+/// alongside it, and its output is sent once it resolves, with a small
+/// script that swaps it in. So slow parts of a page don't hold up the
+/// parts after them.
+///
+/// ```rust
+/// # async fn load_dashboard() -> &'static str { "" }
+/// # async fn load_feed() -> &'static str { "" }
+/// let page = maud::stream(|sink| async move {
+///     maud::html_stream!(sink,
+///         head { title { "Dashboard" } }
+///         @flush;
+///         body {
+///             @suspend(load_feed()) { p { "Loading…" } }
+///             (load_dashboard().await)
+///         }
+///     )
+/// });
+/// # let _ = page;
+/// ```
+///
+/// [`html_stream!`]: crate::html_stream
+pub fn stream<F, Fut>(render: F) -> Streaming<Fut>
+where
+    F: FnOnce(Sink) -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let sink = Sink::default();
+    Streaming {
+        sink: sink.clone(),
+        render: Some(Box::pin(render(sink))),
+        suspended: Vec::new(),
+    }
+}
+
+/// A page being rendered in chunks. See [`stream`].
+pub struct Streaming<Fut> {
+    sink: Sink,
+    render: Option<Pin<Box<Fut>>>,
+    suspended: Vec<Suspended>,
+}
+
+impl<Fut: Future<Output = ()>> Stream for Streaming<Fut> {
+    type Item = String;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<String>> {
+        let this = self.get_mut();
+        if let Some(chunk) = this.sink.next() {
+            return Poll::Ready(Some(chunk));
+        }
+        // Rendering stops at each `.await` that isn't ready, so whatever
+        // was flushed before it can go out now
+        if let Some(render) = &mut this.render {
+            if render.as_mut().poll(cx).is_ready() {
+                this.render = None;
+            }
+        }
+        // Each future is registered after its slot was sent, so a fill
+        // never arrives ahead of the placeholder it replaces
+        this.suspended.extend(this.sink.take_suspended());
+        let sink = &this.sink;
+        this.suspended
+            .retain_mut(|suspended| match suspended.as_mut().poll(cx) {
+                Poll::Ready(chunk) => {
+                    sink.send(chunk);
+                    false
+                }
+                Poll::Pending => true,
+            });
+        match this.sink.next() {
+            Some(chunk) => Poll::Ready(Some(chunk)),
+            None if this.render.is_none() && this.suspended.is_empty() => Poll::Ready(None),
+            None => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Typed inline styles.
+//!
+//! See [`Style`] for details.
+
+use alloc::string::String;
+use core::fmt::{self, Display, Write};
+
+use crate::{Escaper, Render};
+
+/// A builder for the contents of a `style` attribute.
+///
+/// Values are formatted with their [`Display`] impl. Since the result is
+/// spliced into an attribute, it is HTML-escaped as usual; in addition,
+/// any `;`, `{`, `}`, or `\` in a value is escaped with a backslash, so a
+/// value can never end its own declaration and start another. Property
+/// names are escaped the same way, along with `:`.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, style::{Px, Rem}, Style};
+///
+/// let markup = html! {
+///     p style=(Style::new().set("color", "rebeccapurple").set("margin", Rem(1.5)).set("padding", Px(4))) {
+///         "Purple prose"
+///     }
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<p style="color: rebeccapurple; margin: 1.5rem; padding: 4px">Purple prose</p>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Style {
+    declarations: String,
+}
+
+impl Style {
+    /// Creates an empty `Style`.
+    pub fn new() -> Style {
+        Style::default()
+    }
+
+    /// Adds a declaration for the given property.
+    pub fn set(mut self, property: &str, value: impl Display) -> Style {
+        if !self.declarations.is_empty() {
+            self.declarations.push_str("; ");
+        }
+        for c in property.chars() {
+            if matches!(c, ':' | ';' | '{' | '}' | '\\') {
+                self.declarations.push('\\');
+            }
+            self.declarations.push(c);
+        }
+        self.declarations.push_str(": ");
+        let _ = write!(CssEscaper(&mut self.declarations), "{}", value);
+        self
+    }
+
+    /// Adds a declaration for the given property, but only if `value` is
+    /// `Some`.
+    pub fn set_opt(self, property: &str, value: Option<impl Display>) -> Style {
+        match value {
+            Some(value) => self.set(property, value),
+            None => self,
+        }
+    }
+
+    /// Returns `true` if no declarations have been added.
+    pub fn is_empty(&self) -> bool {
+        self.declarations.is_empty()
+    }
+}
+
+impl Render for Style {
+    fn render_to(&self, w: &mut String) {
+        let _ = Escaper::new(w).write_str(&self.declarations);
+    }
+}
+
+struct CssEscaper<'a>(&'a mut String);
+
+impl<'a> Write for CssEscaper<'a> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            if matches!(c, ';' | '{' | '}' | '\\') {
+                self.0.push('\\');
+            }
+            self.0.push(c);
+        }
+        Ok(())
+    }
+}
+
+macro_rules! define_units {
+    ($($(#[$attr:meta])* $name:ident => $suffix:literal,)*) => {
+        $(
+            $(#[$attr])*
+            #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+            pub struct $name<T>(pub T);
+
+            impl<T: Display> Display for $name<T> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    write!(f, concat!("{}", $suffix), self.0)
+                }
+            }
+        )*
+    };
+}
+
+define_units! {
+    /// A length in pixels, e.g. `Px(4)` is `4px`.
+    Px => "px",
+    /// A length relative to the element's font size, e.g. `Em(1.5)` is `1.5em`.
+    Em => "em",
+    /// A length relative to the root font size, e.g. `Rem(2)` is `2rem`.
+    Rem => "rem",
+    /// A percentage, e.g. `Pct(50)` is `50%`.
+    Pct => "%",
+    /// A percentage of the viewport width, e.g. `Vw(100)` is `100vw`.
+    Vw => "vw",
+    /// A percentage of the viewport height, e.g. `Vh(100)` is `100vh`.
+    Vh => "vh",
+}
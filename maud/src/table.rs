@@ -0,0 +1,326 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+
+use crate::{attr::push_attr, escape, Markup, Render};
+
+/// A table of rows, with a column for each field to show.
+///
+/// Columns that have a sort key link to the same table sorted by that
+/// column. Sorting itself happens on the server: use the key and direction
+/// from the request to order the rows, then pass them to
+/// [`sorted_by`](Self::sorted_by) so the headers reflect it.
+///
+/// Columns that have a filter key get a search box in a row under the
+/// headers, named after the key. Filtering happens on the server too: put
+/// the table in a `<form method="get">`, or point the boxes at one with
+/// [`filter_form`](Self::filter_form), then read each key from the query
+/// string, keep the rows that match, and pass the values to
+/// [`filtered_by`](Self::filtered_by) so the boxes keep them.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Column, SortDirection, Table};
+///
+/// struct Pony { name: &'static str, age: u32 }
+/// let ponies = [Pony { name: "Applejack", age: 20 }, Pony { name: "Fluttershy", age: 19 }];
+///
+/// let table = Table::new(&ponies)
+///     .column(Column::new("Name", |pony: &Pony| html! { (pony.name) }).sort_key("name"))
+///     .column(Column::new("Age", |pony: &Pony| html! { (pony.age) }))
+///     .row_key(|pony| pony.name.into())
+///     .sorted_by("name", SortDirection::Ascending)
+///     .sort_href(|key, direction| format!("?sort={}&dir={}", key, direction.as_str()));
+///
+/// let html = html! { (table) }.into_string();
+/// assert!(html.contains(r#"<th scope="col" aria-sort="ascending"><a href="?sort=name&amp;dir=desc">Name</a></th>"#));
+/// assert!(html.contains(r#"<tr data-key="Fluttershy"><td>Fluttershy</td><td>19</td></tr>"#));
+/// ```
+///
+/// With a filter on the name:
+///
+/// ```rust
+/// use maud::{html, Column, Table};
+///
+/// struct Pony { name: &'static str }
+/// let ponies = [Pony { name: "Applejack" }, Pony { name: "Fluttershy" }];
+///
+/// let query = "fl";
+/// let rows = ponies
+///     .into_iter()
+///     .filter(|pony| pony.name.to_lowercase().contains(query))
+///     .collect::<Vec<_>>();
+/// let table = Table::new(&rows)
+///     .column(Column::new("Name", |pony: &Pony| html! { (pony.name) }).filter_key("name"))
+///     .filtered_by("name", query);
+///
+/// let html = html! { form method="get" { (table) } }.into_string();
+/// assert!(html.contains(r#"<td><input type="search" name="name" value="fl" aria-label="Filter Name"></td>"#));
+/// assert!(!html.contains("Applejack"));
+/// ```
+pub struct Table<'a, T> {
+    rows: &'a [T],
+    columns: Vec<Column<'a, T>>,
+    row_key: Option<RowKey<'a, T>>,
+    sort: Option<(Cow<'a, str>, SortDirection)>,
+    sort_href: Option<SortHref<'a>>,
+    filters: Vec<(Cow<'a, str>, Cow<'a, str>)>,
+    filter_form: Option<Cow<'a, str>>,
+    caption: Option<Cow<'a, str>>,
+    empty: Option<Markup>,
+}
+
+type RowKey<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+type SortHref<'a> = Box<dyn Fn(&str, SortDirection) -> String + 'a>;
+
+/// A column in a [`Table`].
+pub struct Column<'a, T> {
+    header: Cow<'a, str>,
+    cell: Box<dyn Fn(&T) -> Markup + 'a>,
+    sort_key: Option<Cow<'a, str>>,
+    filter_key: Option<Cow<'a, str>>,
+}
+
+/// The order that a [`Table`] is sorted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortDirection {
+    /// Smallest first.
+    Ascending,
+    /// Largest first.
+    Descending,
+}
+
+impl SortDirection {
+    /// Returns the opposite direction.
+    pub fn reversed(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+
+    /// Returns `"asc"` or `"desc"`, for use in query strings.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "asc",
+            SortDirection::Descending => "desc",
+        }
+    }
+
+    fn aria_sort(self) -> &'static str {
+        match self {
+            SortDirection::Ascending => "ascending",
+            SortDirection::Descending => "descending",
+        }
+    }
+}
+
+impl<'a, T> Column<'a, T> {
+    /// Creates a column with the given header, which renders each cell with
+    /// the given function.
+    pub fn new(header: impl Into<Cow<'a, str>>, cell: impl Fn(&T) -> Markup + 'a) -> Self {
+        Column {
+            header: header.into(),
+            cell: Box::new(cell),
+            sort_key: None,
+            filter_key: None,
+        }
+    }
+
+    /// Makes the column sortable, under the given key.
+    pub fn sort_key(mut self, sort_key: impl Into<Cow<'a, str>>) -> Self {
+        self.sort_key = Some(sort_key.into());
+        self
+    }
+
+    /// Gives the column a search box in the filter row, named after the
+    /// given key.
+    pub fn filter_key(mut self, filter_key: impl Into<Cow<'a, str>>) -> Self {
+        self.filter_key = Some(filter_key.into());
+        self
+    }
+}
+
+impl<'a, T> Table<'a, T> {
+    /// Creates a table with the given rows and no columns.
+    pub fn new(rows: &'a [T]) -> Self {
+        Table {
+            rows,
+            columns: Vec::new(),
+            row_key: None,
+            sort: None,
+            sort_href: None,
+            filters: Vec::new(),
+            filter_form: None,
+            caption: None,
+            empty: None,
+        }
+    }
+
+    /// Adds a column.
+    pub fn column(mut self, column: Column<'a, T>) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    /// Sets a function that identifies each row, which is rendered as a
+    /// `data-key` attribute.
+    pub fn row_key(mut self, row_key: impl Fn(&T) -> String + 'a) -> Self {
+        self.row_key = Some(Box::new(row_key));
+        self
+    }
+
+    /// Marks the table as sorted by the column with the given key.
+    pub fn sorted_by(
+        mut self,
+        sort_key: impl Into<Cow<'a, str>>,
+        direction: SortDirection,
+    ) -> Self {
+        self.sort = Some((sort_key.into(), direction));
+        self
+    }
+
+    /// Sets a function from a sort key and direction to the URL of the
+    /// table sorted that way.
+    ///
+    /// Without this, sortable columns aren't linked.
+    pub fn sort_href(mut self, sort_href: impl Fn(&str, SortDirection) -> String + 'a) -> Self {
+        self.sort_href = Some(Box::new(sort_href));
+        self
+    }
+
+    /// Fills the search box of the column with the given filter key.
+    ///
+    /// Call this once for each filter in the request.
+    pub fn filtered_by(
+        mut self,
+        filter_key: impl Into<Cow<'a, str>>,
+        value: impl Into<Cow<'a, str>>,
+    ) -> Self {
+        self.filters.push((filter_key.into(), value.into()));
+        self
+    }
+
+    /// Sets the `id` of the form that the search boxes belong to, for when
+    /// the table isn't inside it.
+    pub fn filter_form(mut self, form_id: impl Into<Cow<'a, str>>) -> Self {
+        self.filter_form = Some(form_id.into());
+        self
+    }
+
+    /// Sets the caption of the table.
+    pub fn caption(mut self, caption: impl Into<Cow<'a, str>>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    /// Sets the markup to show when there are no rows.
+    pub fn empty(mut self, empty: Markup) -> Self {
+        self.empty = Some(empty);
+        self
+    }
+
+    fn push_header(&self, column: &Column<'a, T>, w: &mut String) {
+        w.push_str("<th scope=\"col\"");
+        let sort_key = match &column.sort_key {
+            Some(sort_key) => sort_key,
+            None => {
+                w.push('>');
+                escape::escape_to_string(&column.header, w);
+                w.push_str("</th>");
+                return;
+            }
+        };
+        let next_direction = match &self.sort {
+            Some((key, direction)) if key == sort_key => {
+                push_attr("aria-sort", direction.aria_sort(), w);
+                direction.reversed()
+            }
+            _ => SortDirection::Ascending,
+        };
+        w.push('>');
+        match &self.sort_href {
+            Some(sort_href) => {
+                w.push_str("<a");
+                push_attr("href", &sort_href(sort_key, next_direction), w);
+                w.push('>');
+                escape::escape_to_string(&column.header, w);
+                w.push_str("</a>");
+            }
+            None => escape::escape_to_string(&column.header, w),
+        }
+        w.push_str("</th>");
+    }
+
+    fn push_filter(&self, column: &Column<'a, T>, w: &mut String) {
+        w.push_str("<td>");
+        if let Some(filter_key) = &column.filter_key {
+            w.push_str("<input type=\"search\"");
+            push_attr("name", filter_key, w);
+            if let Some((_, value)) = self.filters.iter().find(|(key, _)| key == filter_key) {
+                push_attr("value", value, w);
+            }
+            if let Some(form_id) = &self.filter_form {
+                push_attr("form", form_id, w);
+            }
+            w.push_str(" aria-label=\"Filter ");
+            escape::escape_to_string(&column.header, w);
+            w.push_str("\">");
+        }
+        w.push_str("</td>");
+    }
+}
+
+impl<'a, T> Render for Table<'a, T> {
+    fn render_to(&self, w: &mut String) {
+        w.push_str("<table>");
+        if let Some(caption) = &self.caption {
+            w.push_str("<caption>");
+            escape::escape_to_string(caption, w);
+            w.push_str("</caption>");
+        }
+        w.push_str("<thead><tr>");
+        for column in &self.columns {
+            self.push_header(column, w);
+        }
+        w.push_str("</tr>");
+        if self
+            .columns
+            .iter()
+            .any(|column| column.filter_key.is_some())
+        {
+            w.push_str("<tr>");
+            for column in &self.columns {
+                self.push_filter(column, w);
+            }
+            w.push_str("</tr>");
+        }
+        w.push_str("</thead><tbody>");
+        if self.rows.is_empty() {
+            if let Some(empty) = &self.empty {
+                w.push_str("<tr><td");
+                push_attr(
+                    "colspan",
+                    itoa::Buffer::new().format(self.columns.len().max(1)),
+                    w,
+                );
+                w.push('>');
+                empty.render_to(w);
+                w.push_str("</td></tr>");
+            }
+        }
+        for row in self.rows {
+            w.push_str("<tr");
+            if let Some(row_key) = &self.row_key {
+                push_attr("data-key", &row_key(row), w);
+            }
+            w.push('>');
+            for column in &self.columns {
+                w.push_str("<td>");
+                (column.cell)(row).render_to(w);
+                w.push_str("</td>");
+            }
+            w.push_str("</tr>");
+        }
+        w.push_str("</tbody></table>");
+    }
+}
@@ -0,0 +1,428 @@
+use alloc::{format, string::String, vec::Vec};
+
+use crate::{entities, Markup};
+
+/// Converts rendered markup to plain text, for the text part of an email.
+///
+/// The rules are simple, and meant for markup written with `html!`:
+///
+/// * Block elements like `p` and `div` start on a new line, and those that
+///   look like paragraphs, like `p`, `h1` and `ul`, get a blank line around
+///   them. `br` is a line break.
+/// * Each `li` starts with `- `.
+/// * `a` is written as `text (href)`, unless the text is the link itself.
+/// * `img` is written as its `alt` text.
+/// * `head`, `script`, `style` and `template` are left out.
+/// * Whitespace is collapsed, except in `pre`, and character references
+///   are decoded.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let email = html! {
+///     h1 { "Welcome, Applejack!" }
+///     p { "Your order has shipped." }
+///     ul {
+///         li { "Apples × 12" }
+///         li { "Cider" }
+///     }
+///     p { a href="https://example.com/orders/7" { "Track it" } }
+/// };
+/// assert_eq!(
+///     maud::plain_text(&email),
+///     "Welcome, Applejack!\n\n\
+///      Your order has shipped.\n\n\
+///      - Apples × 12\n\
+///      - Cider\n\n\
+///      Track it (https://example.com/orders/7)",
+/// );
+/// ```
+pub fn plain_text(markup: &Markup) -> String {
+    let mut text = Text::default();
+    let mut rest = markup.0.as_str();
+    while let Some(start) = rest.find(['<', '&']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with('&') {
+            let (c, len) = decode_reference(rest);
+            text.push_char(c);
+            rest = &rest[len..];
+            continue;
+        }
+        let (tag, len) = match parse_tag(rest) {
+            Some(tag) => tag,
+            None => {
+                // A stray `<`
+                text.push_char('<');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        rest = &rest[len..];
+        match tag {
+            Tag::Start { name, attrs } => {
+                if matches!(&*name, "head" | "script" | "style" | "template") {
+                    rest = skip_element(rest, &name);
+                    continue;
+                }
+                text.start(&name, attrs);
+            }
+            Tag::End { name } => text.end(&name),
+            Tag::Other => {}
+        }
+    }
+    text.push_str(rest);
+    text.out
+}
+
+/// The HTML and plain text parts of an email, from one template.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, EmailBody};
+///
+/// let body = EmailBody::new(html! { p { "Thanks for signing up!" } });
+/// assert_eq!(body.html, "<p>Thanks for signing up!</p>");
+/// assert_eq!(body.text, "Thanks for signing up!");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmailBody {
+    /// The `text/html` part.
+    pub html: String,
+    /// The `text/plain` part.
+    pub text: String,
+}
+
+impl EmailBody {
+    /// Renders the plain text part from the markup.
+    pub fn new(markup: Markup) -> EmailBody {
+        let text = plain_text(&markup);
+        EmailBody {
+            html: markup.into_string(),
+            text,
+        }
+    }
+}
+
+#[derive(Default)]
+struct Text {
+    out: String,
+    /// Whether whitespace was seen since the last word.
+    space: bool,
+    /// How many line breaks to write before the next word.
+    breaks: usize,
+    /// How many `pre` elements we're in.
+    pre: usize,
+    /// Where the text of each open `a` starts, and where it links to.
+    links: Vec<(usize, Option<String>)>,
+}
+
+impl Text {
+    fn push_str(&mut self, s: &str) {
+        for c in s.chars() {
+            self.push_char(c);
+        }
+    }
+
+    fn push_char(&mut self, c: char) {
+        if self.pre > 0 {
+            if c == '\n' {
+                self.out.push('\n');
+            } else {
+                self.word(c);
+            }
+        } else if c.is_whitespace() && c != '\u{a0}' {
+            self.space = true;
+        } else {
+            self.word(c);
+        }
+    }
+
+    fn word(&mut self, c: char) {
+        if self.breaks > 0 {
+            if !self.out.is_empty() {
+                for _ in 0..self.breaks {
+                    self.out.push('\n');
+                }
+            }
+            self.breaks = 0;
+        } else if self.space && !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push(' ');
+        }
+        self.space = false;
+        self.out.push(c);
+    }
+
+    /// Starts the next word on a new line, with `count - 1` blank lines
+    /// before it.
+    fn line_break(&mut self, count: usize) {
+        self.breaks = self.breaks.max(count);
+        self.space = false;
+    }
+
+    fn start(&mut self, name: &str, attrs: &str) {
+        match name {
+            "br" => {
+                // Two `br`s in a row give a blank line
+                if self.breaks > 0 || self.out.ends_with('\n') {
+                    self.breaks += 1;
+                } else {
+                    self.line_break(1);
+                }
+            }
+            "li" => {
+                self.line_break(1);
+                self.push_str("- ");
+            }
+            "a" => {
+                let href = attr(attrs, "href");
+                self.links.push((self.out.len(), href));
+            }
+            "img" => {
+                if let Some(alt) = attr(attrs, "alt") {
+                    self.space = true;
+                    self.push_str(&alt);
+                    self.space = true;
+                }
+            }
+            "td" | "th" => self.space = true,
+            "pre" => {
+                self.line_break(2);
+                self.pre += 1;
+            }
+            name if is_paragraph(name) => self.line_break(2),
+            name if is_block(name) => self.line_break(1),
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &str) {
+        match name {
+            "a" => {
+                if let Some((start, Some(href))) = self.links.pop() {
+                    let text = self.out.get(start..).unwrap_or_default().trim();
+                    let shown = href.strip_prefix("mailto:").unwrap_or(&href);
+                    if !href.is_empty() && !href.starts_with('#') && text != shown {
+                        self.space = !text.is_empty();
+                        self.push_char('(');
+                        for c in href.chars() {
+                            self.word(c);
+                        }
+                        self.word(')');
+                    }
+                }
+            }
+            "pre" => {
+                self.pre = self.pre.saturating_sub(1);
+                self.line_break(2);
+            }
+            name if is_paragraph(name) => self.line_break(2),
+            name if is_block(name) => self.line_break(1),
+            _ => {}
+        }
+    }
+}
+
+/// Block elements that are set apart by a blank line.
+fn is_paragraph(name: &str) -> bool {
+    matches!(
+        name,
+        "p" | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "ul"
+            | "ol"
+            | "dl"
+            | "table"
+            | "blockquote"
+            | "hr"
+            | "figure"
+    )
+}
+
+/// Block elements that start on a new line.
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "address"
+            | "article"
+            | "aside"
+            | "body"
+            | "dd"
+            | "details"
+            | "div"
+            | "dt"
+            | "fieldset"
+            | "figcaption"
+            | "footer"
+            | "form"
+            | "header"
+            | "html"
+            | "li"
+            | "main"
+            | "nav"
+            | "section"
+            | "summary"
+            | "tr"
+    )
+}
+
+enum Tag<'a> {
+    Start {
+        name: String,
+        attrs: &'a str,
+    },
+    End {
+        name: String,
+    },
+    /// A comment, doctype or processing instruction.
+    Other,
+}
+
+/// Parses the tag at the start of `s`, and returns it with its length.
+fn parse_tag(s: &str) -> Option<(Tag<'_>, usize)> {
+    if let Some(comment) = s.strip_prefix("<!--") {
+        let len = comment.find("-->").map_or(s.len(), |end| 4 + end + 3);
+        return Some((Tag::Other, len));
+    }
+    if s.starts_with("<!") || s.starts_with("<?") {
+        let len = s.find('>').map_or(s.len(), |end| end + 1);
+        return Some((Tag::Other, len));
+    }
+    let (is_end, body) = match s[1..].strip_prefix('/') {
+        Some(body) => (true, body),
+        None => (false, &s[1..]),
+    };
+    if !body.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let name_len = body
+        .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+        .unwrap_or(body.len());
+    let name = body[..name_len].to_ascii_lowercase();
+    // Find the `>`, skipping over any in quoted attribute values
+    let mut quote = None;
+    let mut end = None;
+    for (i, c) in body.char_indices().skip(name_len) {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '>') => {
+                end = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let end = end?;
+    let len = s.len() - body.len() + end + 1;
+    let tag = if is_end {
+        Tag::End { name }
+    } else {
+        Tag::Start {
+            name,
+            attrs: &body[name_len..end],
+        }
+    };
+    Some((tag, len))
+}
+
+/// Skips past the end tag of an element whose start tag has been read.
+fn skip_element<'a>(rest: &'a str, name: &str) -> &'a str {
+    let close = format!("</{}", name);
+    let lower = rest.to_ascii_lowercase();
+    match lower.find(&close) {
+        Some(start) => match rest[start..].find('>') {
+            Some(end) => &rest[start + end + 1..],
+            None => "",
+        },
+        None => "",
+    }
+}
+
+/// Finds the value of an attribute, with character references decoded.
+fn attr(attrs: &str, name: &str) -> Option<String> {
+    let mut rest = attrs;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == '/');
+        if rest.is_empty() {
+            return None;
+        }
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=' || c == '/')
+            .unwrap_or(rest.len());
+        let this = &rest[..name_len];
+        rest = rest[name_len..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(value) => {
+                let value = value.trim_start();
+                let (value, after) = match value.chars().next() {
+                    Some(q @ ('"' | '\'')) => {
+                        let inner = &value[1..];
+                        let end = inner.find(q).unwrap_or(inner.len());
+                        (&inner[..end], inner.get(end + 1..).unwrap_or_default())
+                    }
+                    _ => {
+                        let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                        (&value[..end], &value[end..])
+                    }
+                };
+                rest = after;
+                value
+            }
+            None => "",
+        };
+        if this.eq_ignore_ascii_case(name) {
+            return Some(decode(value));
+        }
+    }
+}
+
+fn decode(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(start) = rest.find('&') {
+        result.push_str(&rest[..start]);
+        let (c, len) = decode_reference(&rest[start..]);
+        result.push(c);
+        rest = &rest[start + len..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Decodes the character reference at the start of `s`, and returns it
+/// with its length. Anything that isn't one is read as a plain `&`.
+fn decode_reference(s: &str) -> (char, usize) {
+    let end = match s.find(';') {
+        Some(end) if end <= 32 => end,
+        _ => return ('&', 1),
+    };
+    let name = &s[1..end];
+    let c = if let Some(number) = name.strip_prefix('#') {
+        match number.strip_prefix(['x', 'X']) {
+            Some(hex) => u32::from_str_radix(hex, 16).ok(),
+            None => number.parse().ok(),
+        }
+        .and_then(char::from_u32)
+    } else {
+        match name {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            name => entities::char(name),
+        }
+    };
+    match c {
+        Some(c) => (c, end + 1),
+        None => ('&', 1),
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::string::String;
+use core::str::FromStr;
+
+use crate::{context, PreEscaped, Render};
+
+/// A light or dark theme, for use with [`@provide`](crate::context).
+///
+/// A page provides the reader's theme at the top, usually from the cookie
+/// that [`THEME_SCRIPT`] sets. Anything inside can then call
+/// [`Theme::current`] to pick classes for it. Rendering a `Theme` gives its
+/// class name, for the root element.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Markup, Theme};
+///
+/// fn card(title: &str) -> Markup {
+///     html! {
+///         div class=(Theme::current().pick("bg-white", "bg-slate-800")) { (title) }
+///     }
+/// }
+///
+/// // From the `theme` cookie
+/// let theme: Theme = "dark".parse().unwrap_or_default();
+/// let markup = html! {
+///     @provide(theme) {
+///         html class=(theme) {
+///             body { (card("Rainbow Dash")) }
+///         }
+///     }
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<html class="dark"><body><div class="bg-slate-800">Rainbow Dash</div></body></html>"#,
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Theme {
+    /// The default.
+    #[default]
+    Light,
+    Dark,
+}
+
+impl Theme {
+    /// Returns the theme provided around the current render, or
+    /// [`Theme::Light`] if there isn't one.
+    pub fn current() -> Theme {
+        context::get().unwrap_or_default()
+    }
+
+    /// Returns the class name of the theme, `light` or `dark`.
+    pub fn class(self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+        }
+    }
+
+    /// Returns `light` for the light theme, or `dark` for the dark one.
+    pub fn pick<T>(self, light: T, dark: T) -> T {
+        match self {
+            Theme::Light => light,
+            Theme::Dark => dark,
+        }
+    }
+
+    /// Returns the other theme.
+    pub fn toggle(self) -> Theme {
+        self.pick(Theme::Dark, Theme::Light)
+    }
+}
+
+/// Parses a class name, like the value of the cookie set by
+/// [`THEME_SCRIPT`].
+impl FromStr for Theme {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Theme, ()> {
+        match s.trim() {
+            "light" => Ok(Theme::Light),
+            "dark" => Ok(Theme::Dark),
+            _ => Err(()),
+        }
+    }
+}
+
+impl Render for Theme {
+    fn render_to(&self, w: &mut String) {
+        w.push_str(self.class());
+    }
+}
+
+/// A script that switches themes without reloading the page.
+///
+/// Clicking an element with a `data-theme-toggle` attribute swaps the
+/// `light` or `dark` class on the root element, or sets the theme named in
+/// the attribute's value, like `data-theme-toggle="dark"`. The choice is
+/// saved in a `theme` cookie, so that the server can render the same
+/// theme the next time.
+///
+/// Include it once per page.
+pub const THEME_SCRIPT: PreEscaped<&str> = PreEscaped(concat!(
+    "<script>(function(){var r=document.documentElement;",
+    "document.addEventListener(\"click\",function(e){",
+    "var b=e.target.closest&&e.target.closest(\"[data-theme-toggle]\");if(!b)return;",
+    "var t=b.getAttribute(\"data-theme-toggle\");",
+    "if(t!=\"light\"&&t!=\"dark\")t=r.classList.contains(\"dark\")?\"light\":\"dark\";",
+    "r.classList.remove(\"light\",\"dark\");r.classList.add(t);",
+    "document.cookie=\"theme=\"+t+\";path=/;max-age=31536000;samesite=lax\"})})()</script>",
+));
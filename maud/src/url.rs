@@ -0,0 +1,175 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::{escape, Render};
+
+/// The URL rendered in place of one that [strict mode](UrlBuilder::strict)
+/// rejects.
+pub const BLOCKED_URL: &str = "about:invalid#blocked";
+
+/// A URL with query parameters, for use in `href`, `src` and `action`
+/// attributes.
+///
+/// Query keys, values and the fragment are percent-encoded, so they can
+/// contain any character.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, UrlBuilder};
+///
+/// let search = UrlBuilder::new("/search")
+///     .query("q", "cats & dogs")
+///     .query("page", 2);
+/// assert_eq!(
+///     html! { a href=(search) { "Next page" } }.into_string(),
+///     r#"<a href="/search?q=cats%20%26%20dogs&amp;page=2">Next page</a>"#,
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct UrlBuilder<'a> {
+    base: Cow<'a, str>,
+    query: Vec<(Cow<'a, str>, String)>,
+    fragment: Option<Cow<'a, str>>,
+    strict: bool,
+}
+
+impl<'a> UrlBuilder<'a> {
+    /// Creates a builder for the given base URL.
+    ///
+    /// The base is used as is, so it should already be encoded. If it has a
+    /// query string, new parameters are added after the existing ones.
+    pub fn new(base: impl Into<Cow<'a, str>>) -> Self {
+        UrlBuilder {
+            base: base.into(),
+            query: Vec::new(),
+            fragment: None,
+            strict: false,
+        }
+    }
+
+    /// Adds a query parameter.
+    pub fn query(mut self, key: impl Into<Cow<'a, str>>, value: impl Display) -> Self {
+        let mut buffer = String::new();
+        let _ = write!(buffer, "{}", value);
+        self.query.push((key.into(), buffer));
+        self
+    }
+
+    /// Adds a query parameter if the value is `Some`.
+    pub fn query_opt(self, key: impl Into<Cow<'a, str>>, value: Option<impl Display>) -> Self {
+        match value {
+            Some(value) => self.query(key, value),
+            None => self,
+        }
+    }
+
+    /// Sets the fragment, without the leading `#`.
+    pub fn fragment(mut self, fragment: impl Into<Cow<'a, str>>) -> Self {
+        self.fragment = Some(fragment.into());
+        self
+    }
+
+    /// Renders [`BLOCKED_URL`] instead if the URL uses the `javascript:`
+    /// scheme.
+    ///
+    /// Use this for URLs that come from users.
+    ///
+    /// ```rust
+    /// use maud::{html, UrlBuilder};
+    ///
+    /// let link = UrlBuilder::new(" JavaScript:alert(1)").strict();
+    /// assert_eq!(
+    ///     html! { a href=(link) {} }.into_string(),
+    ///     r#"<a href="about:invalid#blocked"></a>"#,
+    /// );
+    /// ```
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Returns the URL as a string, without HTML escaping.
+    pub fn build(&self) -> String {
+        if self.strict && is_javascript_url(&self.base) {
+            return BLOCKED_URL.into();
+        }
+        let (base, base_fragment) = match self.base.split_once('#') {
+            Some((base, fragment)) => (base, Some(fragment)),
+            None => (&*self.base, None),
+        };
+        let mut url = String::with_capacity(base.len());
+        url.push_str(base);
+        for (key, value) in &self.query {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            percent_encode_to_string(key, &mut url);
+            url.push('=');
+            percent_encode_to_string(value, &mut url);
+        }
+        match (&self.fragment, base_fragment) {
+            (Some(fragment), _) => {
+                url.push('#');
+                percent_encode_to_string(fragment, &mut url);
+            }
+            (None, Some(fragment)) => {
+                url.push('#');
+                url.push_str(fragment);
+            }
+            (None, None) => {}
+        }
+        url
+    }
+}
+
+impl<'a> Render for UrlBuilder<'a> {
+    fn render_to(&self, w: &mut String) {
+        escape::escape_to_string(&self.build(), w);
+    }
+}
+
+/// Whether a URL would run script when followed.
+///
+/// Browsers ignore leading whitespace and control characters, tabs and
+/// newlines anywhere, and the case of the scheme, so this does too.
+fn is_javascript_url(url: &str) -> bool {
+    let mut scheme = url
+        .trim_start_matches(|c: char| c <= ' ')
+        .chars()
+        .filter(|&c| !matches!(c, '\t' | '\n' | '\r'));
+    "javascript:"
+        .chars()
+        .all(|expected| scheme.next().map(|c| c.to_ascii_lowercase()) == Some(expected))
+}
+
+/// Percent-encodes everything except unreserved characters.
+pub(crate) fn percent_encode_to_string(input: &str, w: &mut String) {
+    for b in input.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                w.push(b as char)
+            }
+            _ => {
+                let _ = write!(w, "%{:02X}", b);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "url")]
+mod url_support {
+    use crate::{escape, Render};
+    use alloc::string::String;
+    use url_dep::Url;
+
+    impl Render for Url {
+        fn render_to(&self, w: &mut String) {
+            escape::escape_to_string(self.as_str(), w);
+        }
+    }
+
+    impl<'a> From<&'a Url> for super::UrlBuilder<'a> {
+        fn from(url: &'a Url) -> Self {
+            super::UrlBuilder::new(url.as_str())
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use crate::{attr::push_attr, escape, Render};
+
+/// A form split over several steps, and the data gathered so far.
+///
+/// Step numbers start at 1. On each request, rebuild the wizard from the
+/// submitted `step` field and wherever the app keeps the data between
+/// steps, like a session or hidden fields. Then pass the button that was
+/// pressed, from the `wizard` field, to [`apply`](Wizard::apply), which
+/// checks the current step before moving on from it, and every step before
+/// finishing.
+///
+/// [`progress`](Wizard::progress) lists the steps, and
+/// [`controls`](Wizard::controls) renders the buttons and the `step` field.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Wizard, WizardStep};
+///
+/// #[derive(Default)]
+/// struct Signup {
+///     email: String,
+///     plan: Option<String>,
+/// }
+///
+/// let mut wizard = Wizard::new(["Account", "Plan", "Confirm"], Signup::default()).at(2);
+/// wizard.data.email = "twilight@example.com".to_string();
+/// let step = wizard.apply(Some("next"), |step, data: &Signup| match step {
+///     2 if data.plan.is_none() => Err("Pick a plan"),
+///     _ => Ok(()),
+/// });
+/// assert_eq!(step, Err("Pick a plan"));
+/// assert_eq!(wizard.step(), 2);
+///
+/// wizard.data.plan = Some("pro".to_string());
+/// assert_eq!(wizard.apply(Some("next"), |_, _| Ok::<_, ()>(())), Ok(WizardStep::Moved));
+/// assert_eq!(wizard.step(), 3);
+///
+/// let markup = html! {
+///     form method="post" {
+///         (wizard.progress())
+///         (wizard.controls())
+///     }
+/// };
+/// assert!(markup.into_string().contains(r#"<li aria-current="step">Confirm</li>"#));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Wizard<'a, T> {
+    steps: Vec<Cow<'a, str>>,
+    current: usize,
+    /// The data gathered by the steps so far.
+    pub data: T,
+    back_label: Cow<'a, str>,
+    next_label: Cow<'a, str>,
+    finish_label: Cow<'a, str>,
+}
+
+/// What [`Wizard::apply`] did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WizardStep {
+    /// It moved to another step, or stayed on this one.
+    Moved,
+    /// The last step was submitted, so the data is ready to use.
+    Finished,
+}
+
+impl<'a, T> Wizard<'a, T> {
+    /// Creates a wizard with the given step names, on the first step.
+    pub fn new<S: Into<Cow<'a, str>>>(steps: impl IntoIterator<Item = S>, data: T) -> Self {
+        Wizard {
+            steps: steps.into_iter().map(Into::into).collect(),
+            current: 1,
+            data,
+            back_label: "Back".into(),
+            next_label: "Next".into(),
+            finish_label: "Finish".into(),
+        }
+    }
+
+    /// Moves to the given step, as submitted by the form. Numbers out of
+    /// range go to the nearest step.
+    pub fn at(mut self, step: usize) -> Self {
+        self.current = step.clamp(1, self.steps.len().max(1));
+        self
+    }
+
+    /// Sets the text of the button that goes back a step.
+    pub fn back_label(mut self, back_label: impl Into<Cow<'a, str>>) -> Self {
+        self.back_label = back_label.into();
+        self
+    }
+
+    /// Sets the text of the button that goes on to the next step.
+    pub fn next_label(mut self, next_label: impl Into<Cow<'a, str>>) -> Self {
+        self.next_label = next_label.into();
+        self
+    }
+
+    /// Sets the text of the button on the last step.
+    pub fn finish_label(mut self, finish_label: impl Into<Cow<'a, str>>) -> Self {
+        self.finish_label = finish_label.into();
+        self
+    }
+
+    /// Returns the number of the current step.
+    pub fn step(&self) -> usize {
+        self.current
+    }
+
+    /// Returns the name of the current step.
+    pub fn step_name(&self) -> &str {
+        self.steps.get(self.current - 1).map_or("", |name| name)
+    }
+
+    /// Whether this is the first step.
+    pub fn is_first(&self) -> bool {
+        self.current == 1
+    }
+
+    /// Whether this is the last step.
+    pub fn is_last(&self) -> bool {
+        self.current >= self.steps.len()
+    }
+
+    /// Goes back a step, without checking the current one.
+    pub fn back(&mut self) {
+        self.current = self.current.saturating_sub(1).max(1);
+    }
+
+    /// Checks the current step with `check`, given its number and the data,
+    /// and moves on to the next step if it passes.
+    ///
+    /// On the last step, every step is checked, as the step number comes
+    /// from the form and earlier steps could have been skipped. The wizard
+    /// goes back to the first one that fails.
+    pub fn advance<E>(
+        &mut self,
+        mut check: impl FnMut(usize, &T) -> Result<(), E>,
+    ) -> Result<WizardStep, E> {
+        if !self.is_last() {
+            check(self.current, &self.data)?;
+            self.current += 1;
+            return Ok(WizardStep::Moved);
+        }
+        for step in 1..=self.current {
+            if let Err(error) = check(step, &self.data) {
+                self.current = step;
+                return Err(error);
+            }
+        }
+        Ok(WizardStep::Finished)
+    }
+
+    /// Applies the button that was pressed: `back` goes back a step, and
+    /// anything else, including pressing Enter in a field, is [`advance`].
+    /// Without a button, as when the form is first shown, nothing happens.
+    ///
+    /// [`advance`]: Wizard::advance
+    pub fn apply<E>(
+        &mut self,
+        action: Option<&str>,
+        check: impl FnMut(usize, &T) -> Result<(), E>,
+    ) -> Result<WizardStep, E> {
+        match action {
+            None => Ok(WizardStep::Moved),
+            Some("back") => {
+                self.back();
+                Ok(WizardStep::Moved)
+            }
+            Some(_) => self.advance(check),
+        }
+    }
+
+    /// Returns the data, once the wizard is finished.
+    pub fn into_data(self) -> T {
+        self.data
+    }
+
+    /// Renders the list of steps, with the current one marked.
+    pub fn progress(&self) -> impl Render + '_ {
+        struct Progress<'w, 'a, T>(&'w Wizard<'a, T>);
+
+        impl<T> Render for Progress<'_, '_, T> {
+            fn render_to(&self, w: &mut String) {
+                w.push_str("<ol class=\"wizard-steps\">");
+                for (i, name) in self.0.steps.iter().enumerate() {
+                    w.push_str("<li");
+                    match (i + 1).cmp(&self.0.current) {
+                        core::cmp::Ordering::Less => w.push_str(" class=\"done\""),
+                        core::cmp::Ordering::Equal => w.push_str(" aria-current=\"step\""),
+                        core::cmp::Ordering::Greater => {}
+                    }
+                    w.push('>');
+                    escape::escape_to_string(name, w);
+                    w.push_str("</li>");
+                }
+                w.push_str("</ol>");
+            }
+        }
+
+        Progress(self)
+    }
+
+    /// Renders the `step` field and the buttons, named `wizard`.
+    ///
+    /// The next button comes first, so that it's the one pressing Enter in
+    /// a field submits. The back button skips the browser's validation.
+    pub fn controls(&self) -> impl Render + '_ {
+        struct Controls<'w, 'a, T>(&'w Wizard<'a, T>);
+
+        impl<T> Render for Controls<'_, '_, T> {
+            fn render_to(&self, w: &mut String) {
+                let wizard = self.0;
+                let mut buffer = itoa::Buffer::new();
+                w.push_str("<input type=\"hidden\" name=\"step\"");
+                push_attr("value", buffer.format(wizard.current), w);
+                w.push_str("><div class=\"wizard-controls\">");
+                w.push_str("<button type=\"submit\" name=\"wizard\" value=\"next\">");
+                let label = if wizard.is_last() {
+                    &wizard.finish_label
+                } else {
+                    &wizard.next_label
+                };
+                escape::escape_to_string(label, w);
+                w.push_str("</button>");
+                if !wizard.is_first() {
+                    w.push_str(
+                        "<button type=\"submit\" name=\"wizard\" value=\"back\" formnovalidate>",
+                    );
+                    escape::escape_to_string(&wizard.back_label, w);
+                    w.push_str("</button>");
+                }
+                w.push_str("</div>");
+            }
+        }
+
+        Controls(self)
+    }
+}
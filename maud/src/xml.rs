@@ -0,0 +1,31 @@
+use alloc::string::String;
+
+use crate::Render;
+
+/// Renders text as an XML CDATA section, so it's written without escaping.
+///
+/// This is handy for HTML in feeds, like the `description` of an RSS item.
+/// A `]]>` in the text would end the section early, so it's split across
+/// two sections instead.
+///
+/// ```rust
+/// use maud::{xml, Cdata};
+///
+/// let item = xml! {
+///     description { (Cdata("<p>Hello & welcome</p>")) }
+/// };
+/// assert_eq!(
+///     item.into_string(),
+///     "<description><![CDATA[<p>Hello & welcome</p>]]></description>"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Cdata<T>(pub T);
+
+impl<T: AsRef<str>> Render for Cdata<T> {
+    fn render_to(&self, w: &mut String) {
+        w.push_str("<![CDATA[");
+        w.push_str(&self.0.as_ref().replace("]]>", "]]]]><![CDATA[>"));
+        w.push_str("]]>");
+    }
+}
@@ -0,0 +1,52 @@
+// `.cargo/config.toml` points `MAUD_ASSET_DIR` at `tests/static`, and
+// `MAUD_ASSET_URL` at `/assets/`
+
+use maud::{asset, html};
+
+/// The 32-bit FNV-1a hash that `asset!` puts in the query string.
+fn fnv1a(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0x811c_9dc5, |hash, &byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    })
+}
+
+#[test]
+fn hash() {
+    assert_eq!(
+        asset!("app.css"),
+        format!(
+            "/assets/app.css?v={:08x}",
+            fnv1a(include_bytes!("static/app.css"))
+        ),
+    );
+}
+
+#[test]
+fn nested_path() {
+    assert_eq!(
+        asset!("css/site.css"),
+        format!(
+            "/assets/css/site.css?v={:08x}",
+            fnv1a(include_bytes!("static/css/site.css"))
+        ),
+    );
+}
+
+#[test]
+fn different_contents_different_hash() {
+    let (app, site) = (asset!("app.css"), asset!("css/site.css"));
+    assert_ne!(
+        app.split_once("?v=").unwrap().1,
+        site.split_once("?v=").unwrap().1
+    );
+}
+
+#[test]
+fn in_template() {
+    let result = html! {
+        link rel="stylesheet" href=(asset!("app.css"));
+    };
+    assert!(result
+        .into_string()
+        .starts_with(r#"<link rel="stylesheet" href="/assets/app.css?v="#));
+}
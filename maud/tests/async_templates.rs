@@ -0,0 +1,123 @@
+use maud::html;
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = pin!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+struct User {
+    name: &'static str,
+}
+
+async fn fetch_user(_id: u32) -> User {
+    User { name: "Twilight" }
+}
+
+#[test]
+fn await_in_splice() {
+    let result = block_on(async {
+        html! {
+            p { "Hello, " (fetch_user(1).await.name) "!" }
+        }
+    });
+    assert_eq!(result.into_string(), "<p>Hello, Twilight!</p>");
+}
+
+#[test]
+fn await_in_let() {
+    let result = block_on(async {
+        html! {
+            @let user = fetch_user(1).await;
+            @if user.name.starts_with('T') {
+                (user.name)
+            }
+        }
+    });
+    assert_eq!(result.into_string(), "Twilight");
+}
+
+#[cfg(feature = "futures")]
+mod streams {
+    use super::block_on;
+    use futures_core::Stream;
+    use maud::html;
+    use std::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    /// Yields `Pending` before each item, like a real stream might.
+    struct Countdown {
+        remaining: u32,
+        ready: bool,
+    }
+
+    impl Stream for Countdown {
+        type Item = u32;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<u32>> {
+            if !self.ready {
+                self.ready = true;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            self.ready = false;
+            if self.remaining == 0 {
+                return Poll::Ready(None);
+            }
+            self.remaining -= 1;
+            Poll::Ready(Some(self.remaining + 1))
+        }
+    }
+
+    fn countdown(from: u32) -> Countdown {
+        Countdown {
+            remaining: from,
+            ready: false,
+        }
+    }
+
+    #[test]
+    fn for_await() {
+        let result = block_on(async {
+            html! {
+                ol {
+                    @for await n in countdown(3) {
+                        li { (n) }
+                    }
+                }
+            }
+        });
+        assert_eq!(
+            result.into_string(),
+            "<ol><li>3</li><li>2</li><li>1</li></ol>"
+        );
+    }
+
+    #[test]
+    fn for_await_with_label() {
+        let result = block_on(async {
+            html! {
+                @'outer: for await n in countdown(5) {
+                    @for i in 0..n {
+                        @if i == 2 { @continue 'outer }
+                        @if n == 1 { @break 'outer }
+                        (i)
+                    }
+                    ";"
+                }
+            }
+        });
+        assert_eq!(result.into_string(), "01010101;");
+    }
+}
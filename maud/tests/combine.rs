@@ -0,0 +1,59 @@
+use maud::{html, Markup};
+
+#[test]
+fn concat() {
+    let result = Markup::concat(vec![html! { p { "Applejack" } }, html! { p { "Rarity" } }]);
+    assert_eq!(result.into_string(), "<p>Applejack</p><p>Rarity</p>");
+}
+
+#[test]
+fn concat_escapes_text() {
+    let result = Markup::concat(["<b>", "&"]);
+    assert_eq!(result.into_string(), "&lt;b&gt;&amp;");
+}
+
+#[test]
+fn join() {
+    let result = Markup::join([1, 2, 3], html! { br; });
+    assert_eq!(result.into_string(), "1<br>2<br>3");
+}
+
+#[test]
+fn join_empty() {
+    let result = Markup::join(Vec::<Markup>::new(), ", ");
+    assert_eq!(result.into_string(), "");
+}
+
+#[test]
+fn repeat() {
+    let result = html! { hr; }.repeat(2);
+    assert_eq!(result.into_string(), "<hr><hr>");
+}
+
+#[test]
+fn wrap_in() {
+    let items = ["Apples", "Cider"]
+        .iter()
+        .map(|item| html! { (item) }.wrap_in("li"));
+    let result = Markup::concat(items).wrap_in("ul");
+    assert_eq!(
+        result.into_string(),
+        "<ul><li>Apples</li><li>Cider</li></ul>"
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid tag name")]
+fn wrap_in_invalid_tag() {
+    let _ = html! { "Oops" }.wrap_in("p onclick=alert(1)");
+}
+
+#[test]
+fn collect_and_extend() {
+    let mut result: Markup = (1..=3).map(|n| html! { span { (n) } }).collect();
+    result.extend(["!"]);
+    assert_eq!(
+        result.into_string(),
+        "<span>1</span><span>2</span><span>3</span>!"
+    );
+}
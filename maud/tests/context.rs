@@ -0,0 +1,81 @@
+#![cfg(feature = "context")]
+
+use maud::{context, html, Markup};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Theme {
+    Light,
+    Dark,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct User(&'static str);
+
+fn greeting() -> Markup {
+    html! {
+        @consume(theme: Theme);
+        @consume(user: User);
+        p.greeting.dark[theme == Some(Theme::Dark)] {
+            @match user {
+                Some(User(name)) => { "Hi, " (name) "!" }
+                None => "Hi!",
+            }
+        }
+    }
+}
+
+#[test]
+fn nested_components() {
+    let result = html! {
+        @provide(Theme::Dark) {
+            @provide(User("Fluttershy")) {
+                div { (greeting()) }
+            }
+            (greeting())
+        }
+        (greeting())
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<div><p class="greeting dark">Hi, Fluttershy!</p></div>"#,
+            r#"<p class="greeting dark">Hi!</p>"#,
+            r#"<p class="greeting">Hi!</p>"#,
+        )
+    );
+}
+
+#[test]
+fn innermost_wins() {
+    let result = html! {
+        @provide(Theme::Dark) {
+            @provide(Theme::Light) { (format!("{:?}", context::get::<Theme>())) }
+            " " (format!("{:?}", context::get::<Theme>()))
+        }
+    };
+    assert_eq!(result.into_string(), "Some(Light) Some(Dark)");
+}
+
+#[test]
+fn removed_on_break() {
+    let result = html! {
+        @for theme in [Theme::Dark, Theme::Light] {
+            @provide(theme) {
+                @if theme == Theme::Dark { @continue; }
+                (format!("{:?}", theme))
+            }
+        }
+    };
+    assert_eq!(result.into_string(), "Light");
+    assert_eq!(context::get::<Theme>(), None);
+}
+
+#[test]
+fn provide_function() {
+    let result = context::provide(User("Rarity"), greeting);
+    assert_eq!(
+        result.into_string(),
+        r#"<p class="greeting">Hi, Rarity!</p>"#
+    );
+    assert_eq!(context::get::<User>(), None);
+}
@@ -148,6 +148,7 @@ fn match_no_trailing_comma() {
 }
 
 #[test]
+#[allow(clippy::redundant_guards)]
 fn match_expr_with_guards() {
     for &(input, output) in &[(Some(1), "one"), (None, "none"), (Some(2), "2")] {
         let result = html! {
@@ -161,6 +162,51 @@ fn match_expr_with_guards() {
     }
 }
 
+#[test]
+fn match_expr_with_or_patterns() {
+    for &(input, output) in &[(1, "odd"), (8, "even"), (5, "big"), (9, "wat")] {
+        let result = html! {
+            @match input {
+                | 1 | 3 => "odd",
+                2 | 8 => "even",
+                n @ 4..=6 if n % 2 == 1 => "big",
+                _ => "wat",
+            }
+        };
+        assert_eq!(result.into_string(), output);
+    }
+}
+
+#[test]
+fn match_expr_with_closure_in_guard() {
+    let ponies = ["Applejack", "Rarity"];
+    for &(input, output) in &[(Some(6), "pony 6"), (Some(5), "other"), (None, "none")] {
+        let result = html! {
+            @match input {
+                Some(n) if ponies.iter().any(|pony| pony.len() == n) => { "pony " (n) }
+                Some(_) => "other",
+                None => "none",
+            }
+        };
+        assert_eq!(result.into_string(), output);
+    }
+}
+
+#[test]
+fn match_expr_with_complex_head() {
+    let ponies = ["Applejack", "Rarity"];
+    for &(flag, output) in &[(true, "first"), (false, "second")] {
+        let result = html! {
+            @match if flag { ponies.iter().map(|pony| pony.len()).max() } else { Some(6) } {
+                Some(9) => "first",
+                Some(_) => "second",
+                None => "none",
+            }
+        };
+        assert_eq!(result.into_string(), output);
+    }
+}
+
 #[test]
 fn match_in_attribute() {
     for &(input, output) in &[
@@ -212,3 +258,166 @@ fn let_type_ascription() {
     };
     assert_eq!(result.into_string(), "I have 42 cupcakes!");
 }
+
+#[test]
+fn if_let_chain() {
+    for &(a, b, output) in &[
+        (Some(1), Some(2), "1 2"),
+        (Some(1), None, "no b"),
+        (None, Some(2), "no a"),
+        (Some(5), Some(2), "big"),
+    ] {
+        let result = html! {
+            @if let Some(x) = a && x < 5 && let Some(y) = b {
+                (x) " " (y)
+            } @else if let Some(x) = a && x >= 5 {
+                "big"
+            } @else if a.is_some() {
+                "no b"
+            } @else {
+                "no a"
+            }
+        };
+        assert_eq!(result.into_string(), output);
+    }
+}
+
+#[test]
+fn if_let_chain_with_references_in_pattern() {
+    let pairs = [(1, "one"), (2, "two")];
+    let result = html! {
+        @for i in 0..3 {
+            @if let Some(&(n, name)) = pairs.get(i) && n % 2 == 0 {
+                (name)
+            }
+        }
+    };
+    assert_eq!(result.into_string(), "two");
+}
+
+#[test]
+fn if_let_struct_pattern() {
+    #[derive(Clone, Copy)]
+    struct Point {
+        x: i32,
+        y: i32,
+    }
+    for &(point, output) in &[
+        (Point { x: 1, y: 2 }, "1,2"),
+        (Point { x: 0, y: 5 }, "origin"),
+    ] {
+        let result = html! {
+            @if let Point { x: 1.., y } = point {
+                (point.x) "," (y)
+            } @else if let Point { x: 0, .. } = point {
+                "origin"
+            }
+        };
+        assert_eq!(result.into_string(), output);
+    }
+}
+
+#[test]
+fn while_let_struct_pattern() {
+    struct Node {
+        value: u32,
+    }
+    let mut nodes = vec![Node { value: 1 }, Node { value: 2 }];
+    let result = html! {
+        @while let Some(Node { value }) = nodes.pop() {
+            (value)
+        }
+    };
+    assert_eq!(result.into_string(), "21");
+}
+
+#[test]
+fn break_and_continue() {
+    let result = html! {
+        @for i in 0..10 {
+            @if i % 2 == 0 {
+                @continue
+            }
+            @if i > 6 {
+                @break;
+            }
+            (i)
+        }
+    };
+    assert_eq!(result.into_string(), "135");
+}
+
+#[test]
+fn break_in_while() {
+    let mut stack = vec![5, 4, 3, 2, 1, 0];
+    let result = html! {
+        @while let Some(i) = stack.pop() {
+            @if i == 3 { @break }
+            li { (i) }
+        }
+    };
+    assert_eq!(result.into_string(), "<li>0</li><li>1</li><li>2</li>");
+}
+
+#[test]
+fn labeled_loops() {
+    let rows = [[1, 2, 3], [4, 5, 6], [7, 8, 9]];
+    let result = html! {
+        @'rows: for row in &rows {
+            @for &cell in row {
+                @if cell == 5 { @continue 'rows }
+                @if cell == 8 { @break 'rows; }
+                (cell)
+            }
+            ";"
+        }
+    };
+    assert_eq!(result.into_string(), "123;47");
+}
+
+#[test]
+fn break_in_let_chain() {
+    let values = [Some(1), Some(2), None, Some(4)];
+    let result = html! {
+        @for value in values {
+            @if let Some(n) = value && n > 1 {
+                @break
+            } @else if let Some(n) = value {
+                (n)
+            }
+        }
+    };
+    assert_eq!(result.into_string(), "1");
+}
+
+#[test]
+fn early_return() {
+    let render = |items: &[&str]| {
+        html! {
+            h1 { "Items" }
+            @if items.is_empty() {
+                p { "Nothing here" }
+                @return;
+            }
+            ul {
+                @for item in items {
+                    li { (item) }
+                }
+            }
+        }
+        .into_string()
+    };
+    assert_eq!(render(&[]), "<h1>Items</h1><p>Nothing here</p>");
+    assert_eq!(render(&["a"]), "<h1>Items</h1><ul><li>a</li></ul>");
+}
+
+#[test]
+fn early_return_in_loop() {
+    let result = html! {
+        @for i in 0.. {
+            @if i == 3 { @return }
+            (i)
+        }
+    };
+    assert_eq!(result.into_string(), "012");
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "serde")]
+
+use maud::html;
+use serde_json::json;
+
+#[test]
+fn fields() {
+    let result = html! {
+        li data..=(json!({ "id": 7, "title": "Applejack", "done": false })) { "Apples" }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<li data-done="false" data-id="7" data-title="Applejack">Apples</li>"#
+    );
+}
+
+#[test]
+fn kebab_case_keys() {
+    let result = html! {
+        div data..=(json!({ "user_id": 1, "sortOrder": "asc" })) {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<div data-sort-order="asc" data-user-id="1"></div>"#
+    );
+}
+
+#[test]
+fn null_is_omitted() {
+    let result = html! {
+        div data..=(json!({ "parent": null })) {}
+    };
+    assert_eq!(result.into_string(), "<div></div>");
+}
+
+#[test]
+fn nested_values_are_json() {
+    let result = html! {
+        div data..=(json!({ "tags": ["a", "b"] })) {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<div data-tags="[&quot;a&quot;,&quot;b&quot;]"></div>"#
+    );
+}
+
+#[test]
+fn values_are_escaped() {
+    let result = html! {
+        div data..=(json!({ "name": "<Pinkie & Pie>" })) {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<div data-name="&lt;Pinkie &amp; Pie&gt;"></div>"#
+    );
+}
+
+#[test]
+fn mixed_with_other_attributes() {
+    let result = html! {
+        button.primary data..=(json!({ "action": "save" })) type="submit" { "Save" }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<button class="primary" data-action="save" type="submit">Save</button>"#
+    );
+}
+
+#[test]
+fn single_value() {
+    let fields = json!({ "id": 3 });
+    let result = html! {
+        div data..=fields {}
+    };
+    assert_eq!(result.into_string(), r#"<div data-id="3"></div>"#);
+}
+
+#[test]
+fn plain_data_attribute() {
+    let data = "payload";
+    let result = html! {
+        div data=(data) {}
+    };
+    assert_eq!(result.into_string(), r#"<div data="payload"></div>"#);
+}
@@ -0,0 +1,94 @@
+use maud::RELATIVE_TIME_SCRIPT;
+
+#[test]
+fn relative_script() {
+    let script = RELATIVE_TIME_SCRIPT.0;
+    assert!(script.starts_with("<script>"));
+    assert!(script.ends_with("</script>"));
+    assert!(script.contains("time[data-relative]"));
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use chrono_dep::{FixedOffset, NaiveDate, TimeZone, Utc};
+    use maud::{html, Time};
+
+    #[test]
+    fn date_time() {
+        let value = Utc.with_ymd_and_hms(2022, 8, 12, 9, 5, 0).unwrap();
+        assert_eq!(
+            html! { (Time::new(value)) }.into_string(),
+            r#"<time datetime="2022-08-12T09:05:00Z">August 12, 2022, 09:05 UTC</time>"#
+        );
+    }
+
+    #[test]
+    fn offset_and_format() {
+        let value = FixedOffset::west_opt(5 * 3600)
+            .unwrap()
+            .with_ymd_and_hms(2022, 1, 2, 23, 0, 0)
+            .unwrap();
+        assert_eq!(
+            html! { (Time::new(&value).format("%a <%H>")) }.into_string(),
+            r#"<time datetime="2022-01-02T23:00:00-05:00">Sun &lt;23&gt;</time>"#
+        );
+    }
+
+    #[test]
+    fn invalid_format_falls_back() {
+        let value = NaiveDate::from_ymd_opt(2022, 3, 4).unwrap();
+        assert_eq!(
+            html! { (Time::new(value).format("%Q")) }.into_string(),
+            r#"<time datetime="2022-03-04">March 4, 2022</time>"#
+        );
+    }
+
+    #[test]
+    fn relative() {
+        let value = NaiveDate::from_ymd_opt(2022, 3, 4)
+            .unwrap()
+            .and_hms_opt(1, 2, 3)
+            .unwrap();
+        assert_eq!(
+            html! { (Time::new(value).relative()) }.into_string(),
+            r#"<time datetime="2022-03-04T01:02:03" data-relative>March 4, 2022, 01:02</time>"#
+        );
+    }
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use maud::{html, Time};
+    use time_dep::{Date, Month, PrimitiveDateTime, Time as TimeOfDay, UtcOffset};
+
+    fn date() -> Date {
+        Date::from_calendar_date(2022, Month::August, 12).unwrap()
+    }
+
+    #[test]
+    fn offset_date_time() {
+        let value = PrimitiveDateTime::new(date(), TimeOfDay::from_hms(9, 5, 0).unwrap())
+            .assume_offset(UtcOffset::from_hms(9, 0, 0).unwrap());
+        assert_eq!(
+            html! { (Time::new(value)) }.into_string(),
+            r#"<time datetime="2022-08-12T09:05:00+09:00">August 12, 2022, 09:05 +09:00</time>"#
+        );
+    }
+
+    #[test]
+    fn primitive_date_time() {
+        let value = PrimitiveDateTime::new(date(), TimeOfDay::from_hms(9, 5, 0).unwrap());
+        assert_eq!(
+            html! { (Time::new(value).format("[hour]h")) }.into_string(),
+            r#"<time datetime="2022-08-12T09:05:00">09h</time>"#
+        );
+    }
+
+    #[test]
+    fn date_only() {
+        assert_eq!(
+            html! { (Time::new(date()).format("[unknown")) }.into_string(),
+            r#"<time datetime="2022-08-12">August 12, 2022</time>"#
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Checks escaping against a real HTML parser: whatever string goes into a
+//! splice should be what a browser reads back out.
+
+use html5ever::{local_name, ns, parse_fragment, tendril::TendrilSink, ParseOpts, QualName};
+use markup5ever_rcdom::{Handle, NodeData, RcDom};
+use maud::{html, Escaper, Markup};
+use proptest::prelude::*;
+use std::fmt::Write;
+
+/// Parses markup as the contents of a `<body>`.
+fn parse(markup: Markup) -> RcDom {
+    parse_fragment(
+        RcDom::default(),
+        ParseOpts::default(),
+        QualName::new(None, ns!(html), local_name!("body")),
+        Vec::new(),
+        false,
+    )
+    .one(markup.into_string())
+}
+
+/// The first element in a parsed fragment.
+///
+/// The `RcDom` must outlive the result, as dropping it clears out the
+/// children of every node.
+fn first_element(dom: &RcDom) -> Handle {
+    // The fragment is wrapped in an `<html>` element
+    let root = dom.document.children.borrow()[0].clone();
+    let first = root.children.borrow()[0].clone();
+    first
+}
+
+fn text_content(node: &Handle) -> String {
+    let mut text = String::new();
+    for child in node.children.borrow().iter() {
+        if let NodeData::Text { contents } = &child.data {
+            text.push_str(&contents.borrow());
+        }
+    }
+    text
+}
+
+fn attribute(node: &Handle, name: &str) -> Option<String> {
+    match &node.data {
+        NodeData::Element { attrs, .. } => attrs
+            .borrow()
+            .iter()
+            .find(|attr| &*attr.name.local == name)
+            .map(|attr| attr.value.to_string()),
+        _ => None,
+    }
+}
+
+/// What a parser should read back from `input`.
+///
+/// Before parsing, HTML turns every `\r\n` and lone `\r` into `\n`. A NUL is
+/// dropped from text, and replaced with U+FFFD in an attribute. No escaping
+/// can get around either of these.
+fn expected(input: &str, nul: &str) -> String {
+    input
+        .replace("\r\n", "\n")
+        .replace('\r', "\n")
+        .replace('\0', nul)
+}
+
+/// A plain, obviously correct escaper to compare against.
+fn reference_escape(input: &str) -> String {
+    let mut output = String::new();
+    for c in input.chars() {
+        match c {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            c => output.push(c),
+        }
+    }
+    output
+}
+
+/// Any string, or one made mostly of characters that mean something in
+/// HTML.
+fn input() -> impl Strategy<Value = String> {
+    prop_oneof![
+        any::<String>(),
+        proptest::string::string_regex(
+            r#"([<>&"'=/!?;# a-z0-9\-\t\n\r\x00]|&amp;|&#x3c;|<!--|-->|</p>|<script>|&lt)*"#
+        )
+        .unwrap(),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn text(input in input()) {
+        let dom = parse(html! { p { (input) } });
+        let p = first_element(&dom);
+        prop_assert_eq!(text_content(&p), expected(&input, ""));
+    }
+
+    #[test]
+    fn attribute_value(input in input()) {
+        let dom = parse(html! { p title=(input) {} });
+        let p = first_element(&dom);
+        prop_assert_eq!(attribute(&p, "title"), Some(expected(&input, "\u{fffd}")));
+    }
+
+    #[test]
+    fn attribute_value_in_text(input in input()) {
+        // Text after an attribute, to catch a quote that ends it early
+        let dom = parse(html! { p title=(input) { "after" } });
+        let p = first_element(&dom);
+        prop_assert_eq!(text_content(&p), "after");
+    }
+
+    #[test]
+    fn matches_reference(input in input()) {
+        prop_assert_eq!(html! { (input) }.into_string(), reference_escape(&input));
+        let mut escaped = String::new();
+        write!(Escaper::new(&mut escaped), "{}", input).unwrap();
+        prop_assert_eq!(escaped, reference_escape(&input));
+    }
+}
+
+#[test]
+fn literals_match_splices() {
+    // Literals are escaped at compile time, by a copy of the runtime escaper
+    macro_rules! check {
+        ($($literal:tt)*) => {
+            $(
+                assert_eq!(
+                    html! { p title=$literal { $literal } }.into_string(),
+                    html! { p title=($literal) { ($literal) } }.into_string(),
+                );
+            )*
+        };
+    }
+    check! {
+        "" "plain" "<script>alert(1)</script>" "a & b" "&amp;" "\"quoted\""
+        "'single'" "</p><p>" "<!-- comment -->" "wide 🦀 chars" "\r\n\t\0"
+    }
+}
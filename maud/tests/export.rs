@@ -0,0 +1,81 @@
+#![cfg(feature = "export")]
+
+use maud::{export::export, html, Markup, PreEscaped};
+use std::{fs, path::PathBuf};
+
+fn out_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("maud-export-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+fn site(path: &str) -> Option<Markup> {
+    match path {
+        "/" => Some(html! {
+            a href="/docs/" { "Docs" }
+            a href="/about?ref=home#team" { "About" }
+            a href="https://example.com/" { "Elsewhere" }
+            a href="//cdn.example.com/app.js" { "CDN" }
+            a href="#top" { "Top" }
+        }),
+        "/docs/" => Some(html! { a href="/docs/intro" { "Intro" } a href="/" { "Home" } }),
+        "/docs/intro" => Some(html! { a href="/feed.xml" { "Feed" } }),
+        "/about" => Some(html! { a href="/../secret" { "Secret" } }),
+        "/feed.xml" => Some(html! { "<feed/>" }),
+        _ => None,
+    }
+}
+
+#[test]
+fn follows_internal_links() {
+    let dir = out_dir("links");
+    let exported = export(&dir, &["/"], site).unwrap();
+    assert_eq!(
+        exported.written,
+        ["/", "/docs/", "/about", "/docs/intro", "/feed.xml"]
+    );
+    assert!(exported.missing.is_empty());
+    for file in [
+        "index.html",
+        "docs/index.html",
+        "about/index.html",
+        "docs/intro/index.html",
+        "feed.xml",
+    ] {
+        assert!(dir.join(file).is_file(), "{} is missing", file);
+    }
+    assert!(!dir.join("../secret").exists());
+    assert_eq!(
+        fs::read_to_string(dir.join("docs/intro/index.html")).unwrap(),
+        r#"<a href="/feed.xml">Feed</a>"#
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reports_missing_pages() {
+    let dir = out_dir("missing");
+    let exported = export(&dir, &["/nowhere", "/feed.xml", "/feed.xml"], site).unwrap();
+    assert_eq!(exported.written, ["/feed.xml"]);
+    assert_eq!(exported.missing, ["/nowhere"]);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn finds_links_however_theyre_quoted() {
+    let dir = out_dir("quotes");
+    let exported = export(&dir, &["/"], |path| match path {
+        "/" => Some(PreEscaped(String::from(
+            "<a href=/one>1</a><a class=x href='/two?a=1&amp;b=2'>2</a>\
+             <link rel=stylesheet HREF = \"/three\">\
+             <a data-href=\"/nope\">-</a><!-- <a href=\"/nope\"> -->\
+             <script>let a = '<a href=\"/nope\">';</script><a href=/four/>4</a>",
+        ))),
+        "/one" | "/two" | "/three" | "/four/" => Some(html! {}),
+        _ => None,
+    })
+    .unwrap();
+    assert_eq!(exported.written, ["/", "/one", "/two", "/three", "/four/"]);
+    assert!(exported.missing.is_empty());
+    fs::remove_dir_all(&dir).unwrap();
+}
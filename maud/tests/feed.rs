@@ -0,0 +1,69 @@
+use maud::Feed;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Post {
+    id: u32,
+    text: &'static str,
+}
+
+fn post(id: u32, text: &'static str) -> Post {
+    Post { id, text }
+}
+
+fn ids<F: Fn(&Post) -> u32>(feed: &Feed<Post, F>) -> Vec<u32> {
+    feed.items().iter().map(|post| post.id).collect()
+}
+
+#[test]
+fn pages() {
+    let mut feed = Feed::new(|post: &Post| post.id);
+    assert!(feed.has_more());
+    feed.load_older(vec![post(5, "e"), post(4, "d")], Some("4".into()));
+    assert_eq!(feed.cursor(), Some("4"));
+    feed.load_older(vec![post(3, "c")], None);
+    assert_eq!(ids(&feed), [5, 4, 3]);
+    assert!(!feed.has_more());
+    assert_eq!(feed.next_href(|cursor| format!("?before={}", cursor)), None);
+}
+
+#[test]
+fn pages_overlapping_live_items() {
+    let mut feed = Feed::new(|post: &Post| post.id);
+    feed.load_older(vec![post(5, "e"), post(4, "d")], Some("4".into()));
+    // Post 6 arrives, and pushes post 4 onto the next page too
+    feed.push_newer(vec![post(6, "f")]);
+    feed.load_older(vec![post(4, "d"), post(3, "c")], Some("3".into()));
+    assert_eq!(ids(&feed), [6, 5, 4, 3]);
+    assert_eq!(
+        feed.next_href(|cursor| format!("?before={}", cursor)),
+        Some("?before=3".to_string())
+    );
+}
+
+#[test]
+fn updates() {
+    let mut feed = Feed::new(|post: &Post| post.id);
+    feed.load_older(vec![post(2, "b"), post(1, "a")], None);
+    feed.push_newer(vec![post(4, "d"), post(3, "c"), post(1, "A")]);
+    assert_eq!(ids(&feed), [4, 3, 2, 1]);
+    assert_eq!(feed.items()[3], post(1, "A"));
+    assert_eq!(feed.remove(&2), Some(post(2, "b")));
+    assert_eq!(feed.remove(&2), None);
+    assert_eq!(ids(&feed), [4, 3, 1]);
+}
+
+#[test]
+fn duplicates_in_one_update() {
+    let mut feed = Feed::new(|post: &Post| post.id);
+    feed.load_older(vec![post(1, "a")], None);
+    feed.push_newer(vec![
+        post(3, "C"),
+        post(2, "b"),
+        post(3, "c"),
+        post(1, "A"),
+        post(1, "a"),
+    ]);
+    assert_eq!(ids(&feed), [3, 2, 1]);
+    assert_eq!(feed.items()[0], post(3, "C"));
+    assert_eq!(feed.items()[2], post(1, "A"));
+}
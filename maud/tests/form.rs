@@ -0,0 +1,117 @@
+use maud::{html, CheckboxGroup, Choice, RadioGroup, Select};
+use std::borrow::Cow;
+
+#[derive(Debug, PartialEq)]
+enum Pony {
+    Applejack,
+    Rarity,
+    Fluttershy,
+}
+
+impl Choice for Pony {
+    fn value(&self) -> Cow<'_, str> {
+        match self {
+            Pony::Applejack => "aj".into(),
+            Pony::Rarity => "rarity".into(),
+            Pony::Fluttershy => "flutters".into(),
+        }
+    }
+
+    fn label(&self) -> Cow<'_, str> {
+        format!("{:?}", self).into()
+    }
+}
+
+const PONIES: [Pony; 3] = [Pony::Applejack, Pony::Rarity, Pony::Fluttershy];
+
+#[test]
+fn select_enum() {
+    let result = html! {
+        (Select::new("pony", &PONIES).id("best-pony").selected(&Pony::Rarity).required())
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<select name="pony" id="best-pony" required>"#,
+            r#"<option value="aj">Applejack</option>"#,
+            r#"<option value="rarity" selected>Rarity</option>"#,
+            r#"<option value="flutters">Fluttershy</option>"#,
+            r#"</select>"#,
+        )
+    );
+}
+
+#[test]
+fn select_placeholder() {
+    let result = html! {
+        (Select::new("pony", &PONIES[..1]).placeholder("Choose a pony").selected_opt(None))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<select name="pony" id="pony">"#,
+            r#"<option value="" selected>Choose a pony</option>"#,
+            r#"<option value="aj">Applejack</option>"#,
+            r#"</select>"#,
+        )
+    );
+}
+
+#[test]
+fn select_error() {
+    let result = html! {
+        (Select::new("pony", &PONIES[..1]).error("Pick a <real> pony"))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<select name="pony" id="pony" aria-invalid="true" aria-describedby="pony-error">"#,
+            r#"<option value="aj">Applejack</option>"#,
+            r#"</select>"#,
+            r#"<p class="field-error" id="pony-error">Pick a &lt;real&gt; pony</p>"#,
+        )
+    );
+}
+
+#[test]
+fn checkbox_group() {
+    let chosen = vec![Pony::Applejack, Pony::Fluttershy];
+    let result = html! {
+        (CheckboxGroup::new("ponies", "Favorite ponies", &PONIES).checked(&chosen))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<fieldset><legend>Favorite ponies</legend>"#,
+            r#"<label><input type="checkbox" name="ponies" value="aj" checked>Applejack</label>"#,
+            r#"<label><input type="checkbox" name="ponies" value="rarity">Rarity</label>"#,
+            r#"<label><input type="checkbox" name="ponies" value="flutters" checked>Fluttershy</label>"#,
+            r#"</fieldset>"#,
+        )
+    );
+}
+
+#[test]
+fn radio_group() {
+    let result = html! {
+        (RadioGroup::new("pony", "Best pony", &PONIES[..2]).checked_opt(Some(&Pony::Applejack)))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<fieldset><legend>Best pony</legend>"#,
+            r#"<label><input type="radio" name="pony" value="aj" checked>Applejack</label>"#,
+            r#"<label><input type="radio" name="pony" value="rarity">Rarity</label>"#,
+            r#"</fieldset>"#,
+        )
+    );
+}
+
+#[test]
+fn strings() {
+    let options = vec![String::from("a&b"), String::from("c")];
+    let result = html! { (Select::new("s", &options).selected(&options[0])) };
+    assert!(result
+        .into_string()
+        .contains(r#"<option value="a&amp;b" selected>a&amp;b</option>"#));
+}
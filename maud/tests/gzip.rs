@@ -0,0 +1,55 @@
+#![cfg(feature = "gzip")]
+
+use flate2::read::GzDecoder;
+use maud::{html, html_gzip, Gzipped};
+use std::io::Read;
+
+fn decompress(page: Gzipped) -> String {
+    let mut text = String::new();
+    GzDecoder::new(page.as_bytes())
+        .read_to_string(&mut text)
+        .unwrap();
+    text
+}
+
+#[test]
+fn static_only() {
+    let page = html_gzip! {
+        p { "Rocks, these are my rocks. Sediments make me sedimental. Smooth and round." }
+    };
+    assert_eq!(
+        decompress(page),
+        "<p>Rocks, these are my rocks. Sediments make me sedimental. Smooth and round.</p>"
+    );
+}
+
+#[test]
+fn empty() {
+    assert_eq!(decompress(html_gzip! {}), "");
+}
+
+#[test]
+fn matches_html() {
+    let items = ["Asleep in the ground", "Shades of brown", "And gray"];
+    let name = "Pinkie <Pie>";
+    macro_rules! page {
+        ($html:ident) => {
+            $html! {
+                head {
+                    title { "Rocks" }
+                    meta name="description" content="A long description of rocks, to make a long static segment";
+                }
+                body {
+                    h1 { "Hello, " (name) "!" }
+                    ul {
+                        @for item in &items {
+                            li class="item item-with-a-long-class-name-so-that-this-is-compressed" { (item) }
+                        }
+                    }
+                    footer { "This is the end of the page, which is long enough to compress." }
+                }
+            }
+        };
+    }
+    assert_eq!(decompress(page!(html_gzip)), page!(html).into_string());
+}
@@ -0,0 +1,48 @@
+use maud::{html, ResponsiveImage};
+
+#[test]
+fn single_format() {
+    let image = ResponsiveImage::new("/photos/{width}/pony.jpg", "A pony")
+        .widths([800, 400])
+        .size(800, 600);
+    assert_eq!(
+        html! { (image) }.into_string(),
+        r#"<img src="/photos/800/pony.jpg" srcset="/photos/400/pony.jpg 400w, /photos/800/pony.jpg 800w" width="800" height="600" alt="A pony" loading="lazy" decoding="async">"#
+    );
+}
+
+#[test]
+fn without_widths() {
+    let image = ResponsiveImage::new("/logo.svg", "").eager();
+    assert_eq!(
+        html! { (image) }.into_string(),
+        r#"<img src="/logo.svg" alt="" decoding="async">"#
+    );
+}
+
+#[test]
+fn multiple_formats() {
+    let image = ResponsiveImage::new("/i/{width}.{format}", "Art")
+        .widths([100])
+        .formats(["avif", "webp", "png"]);
+    assert_eq!(
+        html! { (image) }.into_string(),
+        concat!(
+            r#"<picture>"#,
+            r#"<source type="image/avif" srcset="/i/100.avif 100w">"#,
+            r#"<source type="image/webp" srcset="/i/100.webp 100w">"#,
+            r#"<img src="/i/100.png" srcset="/i/100.png 100w" alt="Art" loading="lazy" decoding="async">"#,
+            r#"</picture>"#,
+        )
+    );
+}
+
+#[test]
+fn escaping() {
+    let image =
+        ResponsiveImage::new("/a b,c/{width}.jpg?x=1&y=2", r#""Quoted" <alt>"#).widths([10]);
+    assert_eq!(
+        html! { (image) }.into_string(),
+        r#"<img src="/a%20b%2Cc/10.jpg?x=1&amp;y=2" srcset="/a%20b%2Cc/10.jpg?x=1&amp;y=2 10w" alt="&quot;Quoted&quot; &lt;alt&gt;" loading="lazy" decoding="async">"#
+    );
+}
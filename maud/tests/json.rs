@@ -0,0 +1,44 @@
+#![cfg(feature = "serde")]
+
+use maud::{html, Json, JsonAttr};
+use serde_json::{json, Value};
+
+#[test]
+fn content() {
+    let result = html! {
+        script type="application/json" { (Json(json!({ "a": [1, 2], "b": null }))) }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<script type="application/json">{"a":[1,2],"b":null}</script>"#
+    );
+}
+
+#[test]
+fn content_escapes_html() {
+    let result = html! {
+        (Json("</script><!-- & \u{2028}"))
+    };
+    assert_eq!(
+        result.into_string(),
+        r#""\u003c/script\u003e\u003c!-- \u0026 \u2028""#
+    );
+}
+
+#[test]
+fn content_round_trips() {
+    let value = json!({ "html": "<p class=\"x\">Tom & Jerry</p>", "sep": "\u{2029}" });
+    let result = html! { (Json(&value)) }.into_string();
+    assert_eq!(serde_json::from_str::<Value>(&result).unwrap(), value);
+}
+
+#[test]
+fn attribute() {
+    let result = html! {
+        div data-state=(JsonAttr(json!({ "q": "\"<'&'>\"" }))) {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<div data-state="{&quot;q&quot;:&quot;\&quot;&lt;'&amp;'&gt;\&quot;&quot;}"></div>"#
+    );
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "serde")]
+
+use maud::html;
+use serde_json::json;
+
+#[test]
+fn renders_script() {
+    let result = html! {
+        head {
+            @json_ld(json!({ "@type": "Person", "name": "Rarity" }))
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<head><script type="application/ld+json">{"@type":"Person","name":"Rarity"}</script></head>"#
+    );
+}
+
+#[test]
+fn escapes_script_end_and_comments() {
+    let result = html! {
+        @json_ld(json!({ "a": "</script>", "b": "<!--", "c": "&amp;" }))
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<script type="application/ld+json">{"a":"\u003c/script\u003e","b":"\u003c!--","c":"\u0026amp;"}</script>"#
+    );
+}
+
+#[test]
+fn escapes_line_separators() {
+    let result = html! {
+        @json_ld(json!({ "text": "a\u{2028}b\u{2029}c" }))
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<script type="application/ld+json">{"text":"a\u2028b\u2029c"}</script>"#
+    );
+}
+
+#[test]
+fn escaped_json_round_trips() {
+    let value = json!({ "headline": "<b>Tom & Jerry</b>" });
+    let result = html! { @json_ld(&value) }.into_string();
+    let json = result
+        .strip_prefix(r#"<script type="application/ld+json">"#)
+        .and_then(|rest| rest.strip_suffix("</script>"))
+        .unwrap();
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(json).unwrap(),
+        value
+    );
+}
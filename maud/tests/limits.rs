@@ -0,0 +1,121 @@
+#![cfg(feature = "limits")]
+
+use maud::{html, try_html, LimitExceeded, Limits, Markup};
+
+fn nested(depth: usize) -> Markup {
+    html! {
+        div {
+            @if depth > 0 {
+                (nested(depth - 1))
+            }
+        }
+    }
+}
+
+#[test]
+fn bytes() {
+    let limits = Limits::new().max_bytes(100);
+    let result = limits.render(|| {
+        html! {
+            @for _ in 0.. {
+                "Pinkie Pie "
+            }
+        }
+    });
+    assert_eq!(result.unwrap_err(), LimitExceeded::Bytes(100));
+}
+
+#[test]
+fn bytes_across_templates() {
+    let limits = Limits::new().max_bytes(100);
+    let result = limits.render(|| {
+        html! {
+            ("x".repeat(95))
+            (html! { "Applejack " (1) })
+        }
+    });
+    assert_eq!(result.unwrap_err(), LimitExceeded::Bytes(100));
+}
+
+#[test]
+fn depth() {
+    let limits = Limits::new().max_depth(10);
+    assert_eq!(
+        limits.render(|| nested(5)).unwrap().into_string(),
+        "<div>".repeat(6) + &"</div>".repeat(6)
+    );
+    assert_eq!(
+        limits.render(|| nested(20)).unwrap_err(),
+        LimitExceeded::Depth(10)
+    );
+}
+
+#[test]
+fn try_html() {
+    let limits = Limits::new().max_bytes(10);
+    let result = limits.render(|| -> Result<Markup, std::fmt::Error> {
+        try_html! { p { "Rarity, Fluttershy and Twilight Sparkle" } (1) }
+    });
+    assert_eq!(result.unwrap_err(), LimitExceeded::Bytes(10));
+}
+
+#[test]
+fn other_panics() {
+    let result = std::panic::catch_unwind(|| {
+        Limits::new()
+            .max_bytes(10)
+            .render(|| -> Markup { panic!("oops") })
+    });
+    assert!(result.is_err());
+    // The limits are gone once `render` returns
+    let result = html! { @for _ in 0..20 { "Spike" } };
+    assert_eq!(result.into_string(), "Spike".repeat(20));
+}
+
+#[test]
+fn later_templates_stop() {
+    let limits = Limits::new().max_bytes(10);
+    let mut rendered = Vec::new();
+    let result = limits.render(|| {
+        for name in ["Applejack", "Rainbow Dash", "Rarity"] {
+            rendered.push(html! { p { (name) } }.into_string());
+        }
+    });
+    assert_eq!(result.unwrap_err(), LimitExceeded::Bytes(10));
+    assert_eq!(rendered, ["<p>Applejack</p>", "<p>", "<p>"]);
+}
+
+#[test]
+fn outside_limits() {
+    let result = html! { @for _ in 0..3 { p { "Spike" } } };
+    assert_eq!(result.into_string(), "<p>Spike</p>".repeat(3));
+}
+
+#[cfg(feature = "futures")]
+#[test]
+fn bytes_across_flushes() {
+    use futures_core::Stream;
+    use std::{
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+
+    let limits = Limits::new().max_bytes(100);
+    let result = limits.render(|| {
+        let mut page = pin!(maud::stream(|sink| async move {
+            maud::html_stream!(sink,
+                @for _ in 0.. {
+                    "Pinkie Pie "
+                    @flush;
+                }
+            )
+        }));
+        let mut chunks = Vec::new();
+        let mut cx = Context::from_waker(Waker::noop());
+        while let Poll::Ready(Some(chunk)) = page.as_mut().poll_next(&mut cx) {
+            chunks.push(chunk);
+        }
+        chunks
+    });
+    assert_eq!(result.unwrap_err(), LimitExceeded::Bytes(100));
+}
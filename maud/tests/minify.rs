@@ -0,0 +1,91 @@
+use maud::html;
+
+#[test]
+fn whitespace() {
+    let result = html! {
+        @minify;
+        p {
+            "
+                Rocks, these are
+                my rocks.
+            "
+        }
+    };
+    assert_eq!(result.into_string(), "<p> Rocks, these are my rocks. </p>");
+}
+
+#[test]
+fn whitespace_kept() {
+    let result = html! {
+        @minify;
+        pre { "a\n  b" }
+        textarea { "a\n  b" }
+        p title="a\n  b" {}
+    };
+    assert_eq!(
+        result.into_string(),
+        "<pre>a\n  b</pre><textarea>a\n  b</textarea><p title=\"a\n  b\"></p>"
+    );
+}
+
+#[test]
+fn optional_end_tags() {
+    let result = html! {
+        @minify;
+        ul {
+            li { "one" }
+            li { "two" }
+        }
+        div {
+            p { "intro" }
+            p { "more" }
+            span { "aside" }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        "<ul><li>one<li>two</ul><div><p>intro<p>more</p><span>aside</span></div>"
+    );
+}
+
+#[test]
+fn end_tags_kept_before_splices() {
+    let items = ["one", "two"];
+    let result = html! {
+        @minify;
+        ul {
+            li { "first" }
+            @for item in &items {
+                li { (item) }
+            }
+        }
+    };
+    // The end tag after a loop body can't be known at compile time
+    assert_eq!(
+        result.into_string(),
+        "<ul><li>first</li><li>one</li><li>two</li></ul>"
+    );
+}
+
+#[test]
+fn only_when_asked() {
+    let result = html! {
+        ul {
+            li { "one  two" }
+            li { "three" }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        "<ul><li>one  two</li><li>three</li></ul>"
+    );
+}
+
+#[test]
+fn custom_element_parent() {
+    let result = html! {
+        @minify;
+        my-card { p { "Hello" } }
+    };
+    assert_eq!(result.into_string(), "<my-card><p>Hello</p></my-card>");
+}
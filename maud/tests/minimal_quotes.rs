@@ -0,0 +1,43 @@
+use maud::html;
+
+#[test]
+fn unquoted() {
+    let result = html! {
+        @quotes minimal;
+        a.lower-middle href="/ponies?sort&page" { "Ponies" }
+    };
+    assert_eq!(
+        result.into_string(),
+        "<a class=lower-middle href=/ponies?sort&amp;page>Ponies</a>"
+    );
+}
+
+#[test]
+fn quoted_where_needed() {
+    let result = html! {
+        @quotes minimal;
+        p title="two words" data-say=r#"say "hi""# data-empty="" {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<p title="two words" data-say='say "hi"' data-empty=""></p>"#
+    );
+}
+
+#[test]
+fn splices_stay_quoted() {
+    let id = "main";
+    let result = html! {
+        @quotes minimal;
+        div id=(id) {}
+    };
+    assert_eq!(result.into_string(), r#"<div id="main"></div>"#);
+}
+
+#[test]
+fn only_when_asked() {
+    let result = html! {
+        a href="/" { "Home" }
+    };
+    assert_eq!(result.into_string(), r#"<a href="/">Home</a>"#);
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "escape-non-ascii")]
+
+use maud::{html, Markup, NonAscii, PreEscaped};
+
+fn card(name: &str) -> Markup {
+    html! { p title="Café" { "Crème " (name) } }
+}
+
+#[test]
+fn as_is_by_default() {
+    assert_eq!(
+        card("brûlée").into_string(),
+        r#"<p title="Café">Crème brûlée</p>"#
+    );
+}
+
+#[test]
+fn named() {
+    let result = NonAscii::new().named().render(|| card("🦀"));
+    assert_eq!(
+        result.into_string(),
+        r#"<p title="Caf&eacute;">Cr&egrave;me 🦀</p>"#
+    );
+}
+
+#[test]
+fn numeric() {
+    let result = NonAscii::new().numeric().render(|| card("🦀"));
+    assert_eq!(
+        result.into_string(),
+        r#"<p title="Caf&#xE9;">Cr&#xE8;me &#x1F980;</p>"#
+    );
+}
+
+#[test]
+fn named_and_numeric() {
+    let result = NonAscii::new().named().numeric().render(|| card("🦀"));
+    assert_eq!(
+        result.into_string(),
+        r#"<p title="Caf&eacute;">Cr&egrave;me &#x1F980;</p>"#
+    );
+}
+
+#[test]
+fn pre_escaped_is_left_alone() {
+    let result = NonAscii::new()
+        .numeric()
+        .render(|| html! { script { (PreEscaped("let s = 'é';")) } });
+    assert_eq!(result.into_string(), "<script>let s = 'é';</script>");
+}
+
+#[test]
+fn only_inside_render() {
+    let (inner, outer) = NonAscii::new().numeric().render(|| {
+        let inner = NonAscii::new().render(|| card("é"));
+        (inner, card("é"))
+    });
+    assert_eq!(inner.into_string(), r#"<p title="Café">Crème é</p>"#);
+    assert_eq!(
+        outer.into_string(),
+        r#"<p title="Caf&#xE9;">Cr&#xE8;me &#xE9;</p>"#
+    );
+    assert_eq!(card("é").into_string(), r#"<p title="Café">Crème é</p>"#);
+}
+
+#[test]
+fn xml_uses_numeric_references() {
+    let title = "Crème brûlée";
+    let result = NonAscii::new().named().render(|| {
+        maud::xml! {
+            item title="Café" { (title) (html! { p { "é" } }) }
+        }
+    });
+    assert_eq!(
+        result.into_string(),
+        "<item title=\"Caf&#xE9;\">Cr&#xE8;me br&#xFB;l&#xE9;e<p>&#xE9;</p></item>"
+    );
+}
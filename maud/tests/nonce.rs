@@ -0,0 +1,38 @@
+use maud::{html, Nonce, PreEscaped};
+
+#[test]
+fn nothing_without_a_nonce() {
+    assert_eq!(Nonce::current(), None);
+    assert_eq!(
+        Nonce::attach(PreEscaped("<script>go()</script>")).into_string(),
+        "<script>go()</script>"
+    );
+    let result = html! { @style { "p {}" } };
+    assert!(result
+        .into_string()
+        .starts_with("<style data-maud-scope=\""));
+}
+
+#[cfg(feature = "context")]
+#[test]
+fn provided() {
+    let style = || html! { @style { "p {}" } };
+    let result = html! {
+        @provide(Nonce::new("abc\"")) {
+            (style())
+            script nonce=[Nonce::current()] {}
+            (Nonce::attach(PreEscaped("<script>go()</script>")))
+            (Nonce::attach(PreEscaped("<style media=\"print\">p {}</style>")))
+        }
+        script nonce=[Nonce::current()] {}
+    };
+    let result = result.into_string();
+    assert!(result.starts_with("<style data-maud-scope=\""));
+    assert!(result.contains(r#"" nonce="abc&quot;">p"#));
+    assert!(result.ends_with(concat!(
+        r#"<script nonce="abc&quot;"></script>"#,
+        r#"<script nonce="abc&quot;">go()</script>"#,
+        r#"<style nonce="abc&quot;" media="print">p {}</style>"#,
+        "<script></script>",
+    )));
+}
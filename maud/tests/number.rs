@@ -0,0 +1,98 @@
+use maud::{
+    html,
+    locale::{De, En, Fr, Locale},
+    Currency, Localize, Number, Percent,
+};
+
+#[test]
+fn integers() {
+    assert_eq!(html! { (Number(0)) }.into_string(), "0");
+    assert_eq!(html! { (Number(999)) }.into_string(), "999");
+    assert_eq!(html! { (Number(1000)) }.into_string(), "1,000");
+    assert_eq!(html! { (Number(-1234567i64)) }.into_string(), "-1,234,567");
+    assert_eq!(
+        html! { (Number(u64::MAX)) }.into_string(),
+        "18,446,744,073,709,551,615"
+    );
+}
+
+#[test]
+fn floats() {
+    assert_eq!(html! { (Number(1234.5)) }.into_string(), "1,234.5");
+    assert_eq!(
+        html! { (Number(1234.5).decimals(2)) }.into_string(),
+        "1,234.50"
+    );
+    assert_eq!(html! { (Number(-0.001).decimals(1)) }.into_string(), "0.0");
+    assert_eq!(html! { (Number(f64::NAN)) }.into_string(), "NaN");
+    assert_eq!(html! { (Number(f64::NEG_INFINITY)) }.into_string(), "-inf");
+}
+
+#[test]
+fn locales() {
+    let n = Number(1234567.25).decimals(2);
+    assert_eq!(html! { (n.in_locale(&En)) }.into_string(), "1,234,567.25");
+    assert_eq!(html! { (n.in_locale(&De)) }.into_string(), "1.234.567,25");
+    assert_eq!(
+        html! { (n.in_locale(&Fr)) }.into_string(),
+        "1\u{202f}234\u{202f}567,25"
+    );
+}
+
+#[test]
+fn currency() {
+    assert_eq!(
+        html! { (Currency(1234.5, "USD")) }.into_string(),
+        "$1,234.50"
+    );
+    assert_eq!(html! { (Currency(1234.5, "JPY")) }.into_string(), "¥1,234");
+    assert_eq!(html! { (Currency(-3.0, "EUR")) }.into_string(), "-€3.00");
+    assert_eq!(html! { (Currency(-0.001, "EUR")) }.into_string(), "€0.00");
+    assert_eq!(
+        html! { (Currency(10.0, "CHF")) }.into_string(),
+        "CHF\u{a0}10.00"
+    );
+    assert_eq!(
+        html! { (Currency(1234.5, "EUR").in_locale(&De)) }.into_string(),
+        "1.234,50\u{a0}€"
+    );
+}
+
+#[test]
+fn percent() {
+    assert_eq!(html! { (Percent(0.256)) }.into_string(), "26%");
+    assert_eq!(
+        html! { (Percent(0.256).decimals(1)) }.into_string(),
+        "25.6%"
+    );
+    assert_eq!(
+        html! { (Percent(0.256).decimals(1).in_locale(&De)) }.into_string(),
+        "25,6\u{a0}%"
+    );
+}
+
+#[test]
+fn custom_locale() {
+    struct Apostrophe;
+
+    impl Locale for Apostrophe {
+        fn decimal_separator(&self) -> char {
+            '.'
+        }
+
+        fn group_separator(&self) -> char {
+            '\''
+        }
+
+        fn write_currency(&self, number: &str, code: &str, w: &mut String) {
+            w.push_str(code);
+            w.push(' ');
+            w.push_str(number);
+        }
+    }
+
+    assert_eq!(
+        html! { (Currency(1234567.0, "CHF").in_locale(&Apostrophe)) }.into_string(),
+        "CHF 1'234'567.00"
+    );
+}
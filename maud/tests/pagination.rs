@@ -0,0 +1,94 @@
+use maud::{html, Page, Pagination};
+
+fn href(page: usize) -> String {
+    format!("?page={}", page)
+}
+
+/// Summarizes the rendered links, e.g. `< 1 … 4 [5] 6 … 20 >`.
+fn summary(pagination: Pagination<fn(usize) -> String>) -> String {
+    let html = html! { (pagination) }.into_string();
+    let mut result = Vec::new();
+    for item in html.split("<li>").skip(1) {
+        let text = item
+            .split('>')
+            .nth(1)
+            .unwrap()
+            .split('<')
+            .next()
+            .unwrap()
+            .to_string();
+        result.push(if item.contains("rel=\"prev\"") {
+            "<".to_string()
+        } else if item.contains("rel=\"next\"") {
+            ">".to_string()
+        } else if item.contains("aria-disabled") {
+            "-".to_string()
+        } else if item.contains("aria-current") {
+            format!("[{}]", text)
+        } else {
+            text
+        });
+    }
+    result.join(" ")
+}
+
+fn pagination(current: usize, total: usize) -> Pagination<'static, fn(usize) -> String> {
+    Pagination::new(current, total, href as fn(usize) -> String)
+}
+
+#[test]
+fn window() {
+    assert_eq!(summary(pagination(10, 20)), "< 1 … 8 9 [10] 11 12 … 20 >");
+    assert_eq!(summary(pagination(10, 20).window(0)), "< 1 … [10] … 20 >");
+    assert_eq!(summary(pagination(2, 3).window(usize::MAX)), "< 1 [2] 3 >");
+}
+
+#[test]
+fn start_and_end() {
+    assert_eq!(summary(pagination(1, 20)), "- [1] 2 3 … 20 >");
+    assert_eq!(summary(pagination(20, 20)), "< 1 … 18 19 [20] -");
+}
+
+#[test]
+fn single_page_gaps_are_filled_in() {
+    assert_eq!(summary(pagination(4, 7)), "< 1 2 3 [4] 5 6 7 >");
+    assert_eq!(summary(pagination(5, 9)), "< 1 2 3 4 [5] 6 7 8 9 >");
+}
+
+#[test]
+fn single_page_renders_nothing() {
+    assert_eq!(html! { (pagination(1, 1)) }.into_string(), "");
+    assert_eq!(html! { (pagination(1, 0)) }.into_string(), "");
+}
+
+#[test]
+fn markup() {
+    let result = html! {
+        (pagination(2, 3).label("Posts").prev_label("Newer").next_label("Older"))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<nav class="pagination" aria-label="Posts"><ul>"#,
+            r#"<li><a href="?page=1" rel="prev">Newer</a></li>"#,
+            r#"<li><a href="?page=1" aria-label="Page 1">1</a></li>"#,
+            r#"<li><a href="?page=2" aria-label="Page 2" aria-current="page">2</a></li>"#,
+            r#"<li><a href="?page=3" aria-label="Page 3">3</a></li>"#,
+            r#"<li><a href="?page=3" rel="next">Older</a></li>"#,
+            r#"</ul></nav>"#,
+        )
+    );
+}
+
+#[test]
+fn page() {
+    let page = Page::new(vec![1, 2, 3], 3, 3, 9);
+    assert_eq!(page.total_pages(), 3);
+    assert!(page.has_prev());
+    assert!(!page.has_next());
+    assert_eq!(Page::new(Vec::<()>::new(), 1, 10, 0).total_pages(), 0);
+    assert_eq!(Page::new(vec![(); 1], 1, 10, 11).total_pages(), 2);
+    assert!(html! { (page.pagination(href)) }
+        .into_string()
+        .contains(r#"<a href="?page=2" rel="prev">"#));
+}
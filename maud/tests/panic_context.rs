@@ -0,0 +1,54 @@
+#![cfg(feature = "panic-context")]
+
+use maud::{html, Markup};
+use std::{env, process::Command};
+
+fn user_card(name: Option<&str>) -> Markup {
+    html! {
+        div.user-card {
+            "Name: " (name.unwrap())
+        }
+    }
+}
+
+fn page() -> Markup {
+    html! {
+        main {
+            (user_card(None))
+        }
+    }
+}
+
+/// The notes are printed to standard error, so the template is rendered
+/// in a copy of this test binary and its output read back.
+#[test]
+fn notes() {
+    if env::var_os("MAUD_PANIC_CONTEXT_CHILD").is_some() {
+        page();
+        return;
+    }
+    let output = Command::new(env::current_exe().unwrap())
+        .args(["notes", "--exact", "--nocapture", "--test-threads=1"])
+        .env("MAUD_PANIC_CONTEXT_CHILD", "1")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let notes = stderr
+        .lines()
+        .filter(|line| line.starts_with("note: panicked while rendering"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        notes,
+        [
+            "note: panicked while rendering div.user-card at maud/tests/panic_context.rs:9",
+            "note: panicked while rendering main at maud/tests/panic_context.rs:17",
+        ]
+    );
+}
+
+#[test]
+fn no_panic() {
+    let result = html! { p { (1 + 1) } };
+    assert_eq!(result.into_string(), "<p>2</p>");
+}
@@ -0,0 +1,85 @@
+use maud::{html, plain_text, EmailBody};
+
+#[test]
+fn blocks_and_breaks() {
+    let markup = html! {
+        div { "Dear " b { "Rarity" } "," }
+        p { "Your gems" br; "have arrived." }
+        p { "Thanks," br; br; "The team" }
+    };
+    assert_eq!(
+        plain_text(&markup),
+        "Dear Rarity,\n\nYour gems\nhave arrived.\n\nThanks,\n\nThe team"
+    );
+}
+
+#[test]
+fn whitespace_is_collapsed_except_in_pre() {
+    let markup = html! {
+        p { "  lots   of\n  space  " }
+        pre { "keep\n  this" }
+    };
+    assert_eq!(plain_text(&markup), "lots of space\n\nkeep\n  this");
+}
+
+#[test]
+fn links() {
+    let markup = html! {
+        p { a href="https://example.com/a?x=1&y=2" { "Read more" } }
+        p { a href="https://example.com" { "https://example.com" } }
+        p { a href="mailto:help@example.com" { "help@example.com" } }
+        p { a href="#top" { "Back to top" } }
+    };
+    assert_eq!(
+        plain_text(&markup),
+        "Read more (https://example.com/a?x=1&y=2)\n\n\
+         https://example.com\n\n\
+         help@example.com\n\n\
+         Back to top"
+    );
+}
+
+#[test]
+fn hidden_elements_are_left_out() {
+    let markup = html! {
+        html {
+            head { title { "Receipt" } style { "p { color: red }" } }
+            body {
+                script { "if (a < b) { alert('</p>'); }" }
+                template { p { "Later" } }
+                p { "Total: " img src="coin.png" alt="$"; "5" }
+            }
+        }
+    };
+    assert_eq!(plain_text(&markup), "Total: $ 5");
+}
+
+#[test]
+fn references_are_decoded() {
+    let markup = html! {
+        p { "Fish & chips <3" }
+        (maud::PreEscaped("<p>caf&eacute; &#x1F980; &copy; &bogus; &</p>"))
+    };
+    assert_eq!(
+        plain_text(&markup),
+        "Fish & chips <3\n\ncafé 🦀 © &bogus; &"
+    );
+}
+
+#[test]
+fn tables() {
+    let markup = html! {
+        table {
+            tr { th { "Item" } th { "Price" } }
+            tr { td { "Hat" } td { "$20" } }
+        }
+    };
+    assert_eq!(plain_text(&markup), "Item Price\nHat $20");
+}
+
+#[test]
+fn email_body() {
+    let body = EmailBody::new(html! { h1 { "Hi" } p { "There" } });
+    assert_eq!(body.html, "<h1>Hi</h1><p>There</p>");
+    assert_eq!(body.text, "Hi\n\nThere");
+}
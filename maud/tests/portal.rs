@@ -0,0 +1,76 @@
+use maud::{html, mount_portals, PORTAL_SCRIPT};
+
+#[test]
+fn renders_a_template() {
+    let result = html! {
+        p { "Hi" }
+        @portal(target = "#toasts") { .toast { "Saved" } }
+    };
+    assert_eq!(
+        result.into_string(),
+        r##"<p>Hi</p><template data-maud-portal="#toasts"><div class="toast">Saved</div></template>"##
+    );
+}
+
+#[test]
+fn dynamic_target() {
+    let target = "#a\"b";
+    let result = html! { @portal(target = (target)) { "x" } };
+    assert_eq!(
+        result.into_string(),
+        r##"<template data-maud-portal="#a&quot;b">x</template>"##
+    );
+}
+
+#[test]
+fn mounted_at_the_end_of_the_target() {
+    let page = mount_portals(html! {
+        body {
+            div #modals { p { "Already here" } }
+            main {
+                div { div { "Nested" } }
+                @portal(target = "#modals") { dialog open { "One" } }
+                @portal(target = "#modals") { dialog { "Two" } }
+            }
+        }
+    });
+    assert_eq!(
+        page.into_string(),
+        concat!(
+            r#"<body><div id="modals"><p>Already here</p><dialog open>One</dialog><dialog>Two</dialog></div>"#,
+            "<main><div><div>Nested</div></div></main></body>",
+        )
+    );
+}
+
+#[test]
+fn nested_elements_of_the_same_name() {
+    let page = mount_portals(html! {
+        div #root { div { "a" } divider {} div { "b" } }
+        @portal(target = "#root") { "end" }
+    });
+    assert_eq!(
+        page.into_string(),
+        r#"<div id="root"><div>a</div><divider></divider><div>b</div>end</div>"#
+    );
+}
+
+#[test]
+fn missing_targets_are_left_for_the_script() {
+    let page = mount_portals(html! {
+        body {
+            @portal(target = ".sidebar") { "Menu" }
+            @portal(target = "#nowhere") { "Lost" }
+            p { "Content" }
+        }
+    });
+    assert_eq!(
+        page.into_string(),
+        concat!(
+            "<body><p>Content</p>",
+            r#"<template data-maud-portal=".sidebar">Menu</template>"#,
+            r##"<template data-maud-portal="#nowhere">Lost</template></body>"##,
+        )
+    );
+    assert!(PORTAL_SCRIPT.0.contains("template[data-maud-portal]"));
+}
@@ -0,0 +1,58 @@
+use maud::{html, Route};
+
+mod routes {
+    maud::routes! {
+        pub home => "/";
+        pub user_profile(id: u32) => "/users/{id}";
+        pub(crate) post(user: &str, slug: &str) => "/users/{user}/posts/{slug}.html";
+        pub show(route: u32) => "/r/{route}";
+    }
+}
+
+#[test]
+fn no_params() {
+    assert_eq!(routes::home().as_str(), "/");
+}
+
+#[test]
+fn params() {
+    assert_eq!(routes::user_profile(42).as_str(), "/users/42");
+    assert_eq!(
+        routes::post("rarity", "gems").into_string(),
+        "/users/rarity/posts/gems.html"
+    );
+}
+
+#[test]
+fn param_named_route() {
+    assert_eq!(routes::show(7).as_str(), "/r/7");
+}
+
+#[test]
+fn params_are_encoded() {
+    assert_eq!(
+        routes::post("../admin", "a?b#c").as_str(),
+        "/users/..%2Fadmin/posts/a%3Fb%23c.html"
+    );
+}
+
+#[test]
+fn href() {
+    let result = html! {
+        a href=(routes::user_profile(1)) { "Profile" }
+    };
+    assert_eq!(result.into_string(), r#"<a href="/users/1">Profile</a>"#);
+}
+
+#[test]
+fn query() {
+    let url = routes::user_profile(1).query("tab", "posts & replies");
+    assert_eq!(url.build(), "/users/1?tab=posts%20%26%20replies");
+}
+
+#[test]
+fn route_is_a_value() {
+    let route: Route = routes::home();
+    assert_eq!(route.to_string(), "/");
+    assert_eq!(String::from(route), "/");
+}
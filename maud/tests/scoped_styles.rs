@@ -0,0 +1,126 @@
+use maud::{html, Markup};
+
+#[test]
+fn scope_is_applied() {
+    let result = html! {
+        @style { ".title { color: red }" }
+        div.card {
+            h1.title { "Hello" }
+            p class="lead" { "World" }
+        }
+    }
+    .into_string();
+    let scope = result
+        .split("data-maud-scope=\"")
+        .nth(1)
+        .unwrap()
+        .split('"')
+        .next()
+        .unwrap()
+        .to_string();
+    assert!(scope.starts_with("maud-"));
+    assert_eq!(
+        result,
+        format!(
+            r#"<style data-maud-scope="{0}">.title.{0} {{ color: red }}
+</style><div class="card {0}"><h1 class="title {0}">Hello</h1><p class="lead {0}">World</p></div>"#,
+            scope
+        )
+    );
+}
+
+#[test]
+fn scope_is_stable() {
+    fn render() -> Markup {
+        html! {
+            @style { "p { margin: 0 }" }
+            p {}
+        }
+    }
+    let other = html! {
+        @style { "p { margin: 1px }" }
+        p {}
+    };
+    assert_eq!(render().into_string(), render().into_string());
+    assert_ne!(render().into_string(), other.into_string());
+}
+
+#[test]
+fn elements_in_control_structures() {
+    let items = ["a", "b"];
+    let result = html! {
+        @style { "li { list-style: none }" }
+        ul {
+            @for item in &items {
+                li { (item) }
+            }
+        }
+    }
+    .into_string();
+    assert_eq!(result.matches("class=\"maud-").count(), 3);
+}
+
+#[test]
+fn hoisting() {
+    fn item(name: &str) -> Markup {
+        html! {
+            @style { "li { color: blue }" }
+            li { (name) }
+        }
+    }
+    let page = maud::hoist_styles(html! {
+        html {
+            head { title { "List" } }
+            body { ul { (item("one")) (item("two")) } }
+        }
+    })
+    .into_string();
+    assert_eq!(page.matches("<style").count(), 1);
+    assert!(page.starts_with("<html><head><title>List</title><style"));
+    assert!(page.contains("</style></head><body><ul><li"));
+}
+
+#[test]
+fn hoisting_without_head() {
+    let page = maud::hoist_styles(html! {
+        p { "Intro" }
+        div {
+            @style { "p { color: green }" }
+        }
+    })
+    .into_string();
+    assert!(page.starts_with("<style"));
+    assert!(page.contains("</style><p class=\"maud-"));
+}
+
+#[test]
+fn hoisting_after_doctype() {
+    let page = maud::hoist_styles(html! {
+        (maud::DOCTYPE)
+        div {
+            @style { "p { color: green }" }
+            p { "Body" }
+        }
+    })
+    .into_string();
+    assert!(page.starts_with("<!DOCTYPE html><style"));
+}
+
+#[test]
+fn toggled_and_optional_classes() {
+    let on = true;
+    let result = html! {
+        @style { "p { margin: 0 }" }
+        p.lead[on] {}
+        p.lead[!on] {}
+        p class=[Some("note")] {}
+        p class=[None::<&str>] {}
+    }
+    .into_string();
+    let scope = result.split("data-maud-scope=\"").nth(1).unwrap();
+    let scope = scope.split('"').next().unwrap();
+    assert!(result.ends_with(&format!(
+        r#"<p class="{0} lead"></p><p class="{0}"></p><p class="{0} note"></p><p class="{0}"></p>"#,
+        scope
+    )));
+}
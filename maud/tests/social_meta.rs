@@ -0,0 +1,95 @@
+use maud::{html, SocialImage, SocialMeta, SocialMetaError};
+
+#[test]
+fn minimal() {
+    let meta = SocialMeta::new("Hello", "https://example.com/hello");
+    assert_eq!(meta.validate(), Ok(()));
+    assert_eq!(
+        html! { (meta) }.into_string(),
+        concat!(
+            r#"<meta property="og:title" content="Hello">"#,
+            r#"<meta name="twitter:title" content="Hello">"#,
+            r#"<meta property="og:type" content="website">"#,
+            r#"<meta property="og:url" content="https://example.com/hello">"#,
+            r#"<meta name="twitter:card" content="summary">"#,
+        )
+    );
+}
+
+#[test]
+fn full() {
+    let meta = SocialMeta::new("Post", "https://example.com/post")
+        .kind("article")
+        .description("A <great> post")
+        .site_name("Example")
+        .twitter_site("@example")
+        .image(SocialImage::new("https://example.com/post.png", 1200, 630).alt("Cover"));
+    assert_eq!(meta.validate(), Ok(()));
+    assert_eq!(
+        html! { (meta) }.into_string(),
+        concat!(
+            r#"<meta property="og:title" content="Post">"#,
+            r#"<meta name="twitter:title" content="Post">"#,
+            r#"<meta property="og:type" content="article">"#,
+            r#"<meta property="og:url" content="https://example.com/post">"#,
+            r#"<meta property="og:description" content="A &lt;great&gt; post">"#,
+            r#"<meta name="twitter:description" content="A &lt;great&gt; post">"#,
+            r#"<meta property="og:site_name" content="Example">"#,
+            r#"<meta name="twitter:card" content="summary_large_image">"#,
+            r#"<meta name="twitter:site" content="@example">"#,
+            r#"<meta property="og:image" content="https://example.com/post.png">"#,
+            r#"<meta name="twitter:image" content="https://example.com/post.png">"#,
+            r#"<meta property="og:image:width" content="1200">"#,
+            r#"<meta property="og:image:height" content="630">"#,
+            r#"<meta property="og:image:alt" content="Cover">"#,
+            r#"<meta name="twitter:image:alt" content="Cover">"#,
+        )
+    );
+}
+
+#[test]
+fn square_image_uses_summary_card() {
+    let meta = SocialMeta::new("Hi", "https://example.com/")
+        .image(SocialImage::new("https://example.com/a.png", 400, 400).alt("A"));
+    assert!(html! { (meta) }
+        .into_string()
+        .contains(r#"<meta name="twitter:card" content="summary">"#));
+}
+
+#[test]
+fn validation() {
+    let image =
+        |width, height| SocialImage::new("https://example.com/a.png", width, height).alt("A");
+    assert_eq!(
+        SocialMeta::new(" ", "https://example.com/").validate(),
+        Err(SocialMetaError::MissingTitle)
+    );
+    assert_eq!(
+        SocialMeta::new("Hi", "/relative").validate(),
+        Err(SocialMetaError::RelativeUrl("url"))
+    );
+    assert_eq!(
+        SocialMeta::new("Hi", "https://example.com/")
+            .image(SocialImage::new("/a.png", 1200, 630).alt("A"))
+            .validate(),
+        Err(SocialMetaError::RelativeUrl("image url"))
+    );
+    assert_eq!(
+        SocialMeta::new("Hi", "https://example.com/")
+            .image(image(100, 100))
+            .validate(),
+        Err(SocialMetaError::ImageTooSmall)
+    );
+    assert_eq!(
+        SocialMeta::new("Hi", "https://example.com/")
+            .image(image(5000, 1000))
+            .validate(),
+        Err(SocialMetaError::ImageTooLarge)
+    );
+    assert_eq!(
+        SocialMeta::new("Hi", "https://example.com/")
+            .image(SocialImage::new("https://example.com/a.png", 1200, 630))
+            .validate(),
+        Err(SocialMetaError::MissingImageAlt)
+    );
+}
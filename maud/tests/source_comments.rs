@@ -0,0 +1,42 @@
+#![cfg(all(feature = "source-comments", debug_assertions))]
+
+use maud::{html, Markup};
+
+fn greeting(name: &str) -> Markup {
+    html! { p { "Hello, " (name) "!" } }
+}
+
+#[test]
+fn partials() {
+    let result = html! {
+        h1 { "Welcome" }
+        (greeting("Rarity"))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<!-- maud/tests/source_comments.rs:11 -->",
+            "<h1>Welcome</h1>",
+            "<!-- maud/tests/source_comments.rs:6 -->",
+            "<p>Hello, Rarity!</p>",
+            "<!-- /maud/tests/source_comments.rs:6 -->",
+            "<!-- /maud/tests/source_comments.rs:11 -->",
+        )
+    );
+}
+
+#[test]
+fn early_return() {
+    let result = html! {
+        "Before"
+        @return;
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<!-- maud/tests/source_comments.rs:30 -->",
+            "Before",
+            "<!-- /maud/tests/source_comments.rs:30 -->",
+        )
+    );
+}
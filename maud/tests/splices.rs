@@ -37,6 +37,13 @@ fn attributes() {
     );
 }
 
+#[test]
+fn data_attribute() {
+    let data = "ponies.json";
+    let result = html! { div data=(data) {} };
+    assert_eq!(result.into_string(), r#"<div data="ponies.json"></div>"#);
+}
+
 #[test]
 fn class_shorthand() {
     let pinkie_class = "pinkie";
@@ -124,3 +131,24 @@ fn nested_macro_invocation() {
     let result = html! { (format!("{best_pony} is best pony")) };
     assert_eq!(result.into_string(), "Pinkie Pie is best pony");
 }
+
+#[test]
+fn binary_operators() {
+    let (x, y) = (2, 3);
+    let result = html! { (x + y) " " (x * y - 1) };
+    assert_eq!(result.into_string(), "5 5");
+}
+
+#[test]
+fn question_mark() {
+    fn greet(age: &str) -> Result<maud::Markup, std::num::ParseIntError> {
+        Ok(html! {
+            p { "Next year you'll be " (age.parse::<u32>()? + 1) }
+        })
+    }
+    assert_eq!(
+        greet("41").unwrap().into_string(),
+        "<p>Next year you'll be 42</p>"
+    );
+    assert!(greet("forty-one").is_err());
+}
@@ -0,0 +1,85 @@
+use maud::{html, Attributes, Markup};
+
+fn card(title: &str, rest: &Attributes) -> Markup {
+    html! {
+        section.card ..rest { h2 { (title) } }
+    }
+}
+
+#[test]
+fn forwarded() {
+    let rest = Attributes::new()
+        .set("id", "ponies")
+        .set("data-count", 6)
+        .flag("hidden");
+    assert_eq!(
+        card("Ponies", &rest).into_string(),
+        r#"<section class="card" id="ponies" data-count="6" hidden><h2>Ponies</h2></section>"#
+    );
+}
+
+#[test]
+fn classes_are_merged() {
+    let rest = Attributes::new().class("wide").class("dark");
+    assert_eq!(
+        card("Ponies", &rest).into_string(),
+        r#"<section class="card wide dark"><h2>Ponies</h2></section>"#
+    );
+    let result = html! { p ..(Attributes::new().class("lead")) { "Hi" } };
+    assert_eq!(result.into_string(), r#"<p class="lead">Hi</p>"#);
+}
+
+#[test]
+fn own_attributes_win() {
+    let rest = Attributes::new().set("type", "submit").set("title", "Save");
+    let result = html! { button #save type="button" ..rest { "Save" } };
+    assert_eq!(
+        result.into_string(),
+        r#"<button id="save" type="button" title="Save">Save</button>"#
+    );
+}
+
+#[test]
+fn values_are_escaped() {
+    let rest = Attributes::new()
+        .set("title", r#"<"Rarity" & co>"#)
+        .class("a\"b");
+    let result = html! { span.x ..rest {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<span class="x a&quot;b" title="&lt;&quot;Rarity&quot; &amp; co&gt;"></span>"#
+    );
+}
+
+#[test]
+fn invalid_names_are_skipped() {
+    let rest = Attributes::new()
+        .set("onclick=\"alert(1)\"", "")
+        .set("a b", "c")
+        .set("aria-label", "Close");
+    let result = html! { button ..rest {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<button aria-label="Close"></button>"#
+    );
+}
+
+#[test]
+fn set_replaces_and_remove() {
+    let mut rest = Attributes::new().set("Title", "One").set("title", "Two");
+    assert_eq!(rest.get("title"), Some("Two"));
+    assert_eq!(rest.remove("title"), Some("Two".to_string()));
+    assert!(rest.is_empty());
+    let rest = Attributes::new()
+        .set_opt("href", None::<&str>)
+        .set_opt("rel", Some("next"));
+    assert_eq!(html! { a ..rest {} }.into_string(), r#"<a rel="next"></a>"#);
+}
+
+#[test]
+fn get_and_remove_ignore_case() {
+    let mut rest = Attributes::new().set("aria-label", "Close");
+    assert_eq!(rest.get("ARIA-Label"), Some("Close"));
+    assert_eq!(rest.remove("Aria-Label"), Some("Close".to_string()));
+    assert!(rest.is_empty());
+}
@@ -0,0 +1,186 @@
+#![cfg(feature = "futures")]
+
+use futures_core::Stream;
+use maud::html_stream;
+use std::{
+    future::Future,
+    pin::{pin, Pin},
+    task::{Context, Poll, Waker},
+};
+
+/// Resolves on the second poll, like a query that isn't ready yet.
+#[derive(Default)]
+struct Later {
+    polled: bool,
+}
+
+impl Future for Later {
+    type Output = &'static str;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<&'static str> {
+        if self.polled {
+            Poll::Ready("Twilight")
+        } else {
+            self.polled = true;
+            Poll::Pending
+        }
+    }
+}
+
+fn next<S: Stream>(stream: Pin<&mut S>) -> Poll<Option<S::Item>> {
+    stream.poll_next(&mut Context::from_waker(Waker::noop()))
+}
+
+#[test]
+fn flushes_before_await() {
+    let mut page = pin!(maud::stream(|sink| async move {
+        html_stream!(sink,
+            head { title { "Dashboard" } }
+            @flush;
+            body { (Later::default().await) }
+        )
+    }));
+    assert_eq!(
+        next(page.as_mut()),
+        Poll::Ready(Some("<head><title>Dashboard</title></head>".to_string()))
+    );
+    assert_eq!(
+        next(page.as_mut()),
+        Poll::Ready(Some("<body>Twilight</body>".to_string()))
+    );
+    assert_eq!(next(page.as_mut()), Poll::Ready(None));
+}
+
+#[test]
+fn flush_in_loop() {
+    let mut page = pin!(maud::stream(|sink| async move {
+        html_stream!(sink,
+            @for i in 1..=3 {
+                p { (i) }
+                @flush;
+            }
+        )
+    }));
+    for i in 1..=3 {
+        assert_eq!(
+            next(page.as_mut()),
+            Poll::Ready(Some(format!("<p>{i}</p>")))
+        );
+    }
+    assert_eq!(next(page.as_mut()), Poll::Ready(None));
+}
+
+#[test]
+fn empty_chunks_are_skipped() {
+    let mut page = pin!(maud::stream(|sink| async move {
+        html_stream!(sink,
+            @flush;
+            @flush;
+        )
+    }));
+    assert_eq!(next(page.as_mut()), Poll::Ready(None));
+}
+
+#[test]
+fn suspend_sends_placeholder_then_fill() {
+    let mut page = pin!(maud::stream(|sink| async move {
+        html_stream!(sink,
+            ul {
+                @suspend(Later::default()) { li { "Loading" } }
+                li { "After" }
+            }
+        )
+    }));
+    assert_eq!(
+        next(page.as_mut()),
+        Poll::Ready(Some(
+            "<ul><template id=\"maud-slot-0\"></template><li>Loading</li><!--/maud-slot-0-->"
+                .to_string()
+        ))
+    );
+    assert_eq!(
+        next(page.as_mut()),
+        Poll::Ready(Some("<li>After</li></ul>".to_string()))
+    );
+    let Poll::Ready(Some(fill)) = next(page.as_mut()) else {
+        panic!("expected the fill once the future resolves");
+    };
+    assert!(fill.starts_with("<template id=\"maud-fill-0\">Twilight</template><script>"));
+    assert!(fill.contains("maud-slot-0"));
+    assert_eq!(next(page.as_mut()), Poll::Ready(None));
+}
+
+#[test]
+fn nested_suspends_get_their_own_slots() {
+    let mut page = pin!(maud::stream(|sink| async move {
+        html_stream!(sink,
+            @suspend(async { "outer" }) {
+                @suspend(async { "inner" }) { "..." }
+            }
+        )
+    }));
+    assert_eq!(
+        next(page.as_mut()),
+        Poll::Ready(Some(
+            "<template id=\"maud-slot-0\"></template>\
+             <template id=\"maud-slot-1\"></template>...<!--/maud-slot-1-->"
+                .to_string()
+        ))
+    );
+    assert_eq!(
+        next(page.as_mut()),
+        Poll::Ready(Some("<!--/maud-slot-0-->".to_string()))
+    );
+    // The inner slot is sent first, so its future is registered first
+    let fills = [next(page.as_mut()), next(page.as_mut())];
+    for (fill, slot, content) in [(&fills[0], 1, "inner"), (&fills[1], 0, "outer")] {
+        let Poll::Ready(Some(fill)) = fill else {
+            panic!("expected a fill");
+        };
+        assert!(fill.starts_with(&format!(
+            "<template id=\"maud-fill-{slot}\">{content}</template>"
+        )));
+    }
+    assert_eq!(next(page.as_mut()), Poll::Ready(None));
+}
+
+#[test]
+fn fill_waits_for_awaiting_placeholder() {
+    let mut page = pin!(maud::stream(|sink| async move {
+        html_stream!(sink,
+            @suspend(async { "Ready" }) {
+                p { (Later::default().await) }
+            }
+        )
+    }));
+    assert_eq!(next(page.as_mut()), Poll::Pending);
+    assert_eq!(
+        next(page.as_mut()),
+        Poll::Ready(Some(
+            "<template id=\"maud-slot-0\"></template><p>Twilight</p><!--/maud-slot-0-->"
+                .to_string()
+        ))
+    );
+    let Poll::Ready(Some(fill)) = next(page.as_mut()) else {
+        panic!("expected the fill after the slot");
+    };
+    assert!(fill.starts_with("<template id=\"maud-fill-0\">Ready</template>"));
+    assert_eq!(next(page.as_mut()), Poll::Ready(None));
+}
+
+#[cfg(feature = "context")]
+#[test]
+fn fill_script_gets_nonce() {
+    let mut page = pin!(maud::stream(|sink| async move {
+        html_stream!(sink,
+            @provide(maud::Nonce::new("r4nd0m")) {
+                @suspend(async { "Ready" }) { "..." }
+            }
+        )
+    }));
+    assert!(matches!(next(page.as_mut()), Poll::Ready(Some(_))));
+    let Poll::Ready(Some(fill)) = next(page.as_mut()) else {
+        panic!("expected the fill");
+    };
+    assert!(fill.contains("</template><script nonce=\"r4nd0m\">"));
+}
@@ -0,0 +1,61 @@
+use maud::{
+    html,
+    style::{Pct, Px},
+    Style,
+};
+
+#[test]
+fn declarations() {
+    let style = Style::new()
+        .set("display", "flex")
+        .set("gap", Px(8))
+        .set("width", Pct(50));
+    let result = html! { div style=(style) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<div style="display: flex; gap: 8px; width: 50%"></div>"#
+    );
+}
+
+#[test]
+fn optional_declarations() {
+    let color: Option<&str> = None;
+    let style = Style::new()
+        .set_opt("color", color)
+        .set_opt("margin", Some(Px(0)));
+    assert_eq!(html! { (style) }.into_string(), "margin: 0px");
+}
+
+#[test]
+fn empty() {
+    assert!(Style::new().is_empty());
+    assert!(!Style::new().set("color", "red").is_empty());
+}
+
+#[test]
+fn html_escaping() {
+    let style = Style::new().set("font-family", r#""Comic Sans", <serif>"#);
+    let result = html! { p style=(style) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<p style="font-family: &quot;Comic Sans&quot;, &lt;serif&gt;"></p>"#
+    );
+}
+
+#[test]
+fn declaration_injection() {
+    let style = Style::new().set("color", "red; background: url(evil) } body {");
+    assert_eq!(
+        html! { (style) }.into_string(),
+        r"color: red\; background: url(evil) \} body \{"
+    );
+}
+
+#[test]
+fn property_injection() {
+    let style = Style::new().set("color: red; background", "blue");
+    assert_eq!(
+        html! { (style) }.into_string(),
+        r"color\: red\; background: blue"
+    );
+}
@@ -0,0 +1,107 @@
+use maud::{html, Column, SortDirection, Table};
+
+struct Pony {
+    name: &'static str,
+    color: &'static str,
+}
+
+const PONIES: [Pony; 2] = [
+    Pony {
+        name: "Rarity",
+        color: "white",
+    },
+    Pony {
+        name: "Rainbow <Dash>",
+        color: "blue",
+    },
+];
+
+fn table(rows: &[Pony]) -> Table<'_, Pony> {
+    Table::new(rows)
+        .column(Column::new("Name", |pony: &Pony| html! { (pony.name) }).sort_key("name"))
+        .column(Column::new(
+            "Color",
+            |pony: &Pony| html! { em { (pony.color) } },
+        ))
+}
+
+#[test]
+fn rows() {
+    assert_eq!(
+        html! { (table(&PONIES)) }.into_string(),
+        concat!(
+            r#"<table><thead><tr><th scope="col">Name</th><th scope="col">Color</th></tr></thead><tbody>"#,
+            r#"<tr><td>Rarity</td><td><em>white</em></td></tr>"#,
+            r#"<tr><td>Rainbow &lt;Dash&gt;</td><td><em>blue</em></td></tr>"#,
+            r#"</tbody></table>"#,
+        )
+    );
+}
+
+#[test]
+fn row_keys_and_caption() {
+    let result = html! {
+        (table(&PONIES[..1]).row_key(|pony| pony.name.to_lowercase()).caption("Ponies & friends"))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<table><caption>Ponies &amp; friends</caption>"#,
+            r#"<thead><tr><th scope="col">Name</th><th scope="col">Color</th></tr></thead><tbody>"#,
+            r#"<tr data-key="rarity"><td>Rarity</td><td><em>white</em></td></tr>"#,
+            r#"</tbody></table>"#,
+        )
+    );
+}
+
+#[test]
+fn sort_links() {
+    let href =
+        |key: &str, direction: SortDirection| format!("?sort={}:{}", key, direction.as_str());
+    let unsorted = html! { (table(&PONIES).sort_href(href)) }.into_string();
+    assert!(unsorted.contains(r#"<th scope="col"><a href="?sort=name:asc">Name</a></th>"#));
+    assert!(unsorted.contains(r#"<th scope="col">Color</th>"#));
+
+    let sorted = html! {
+        (table(&PONIES).sort_href(href).sorted_by("name", SortDirection::Descending))
+    }
+    .into_string();
+    assert!(sorted.contains(
+        r#"<th scope="col" aria-sort="descending"><a href="?sort=name:asc">Name</a></th>"#
+    ));
+}
+
+#[test]
+fn empty() {
+    let result = html! {
+        (table(&[]).empty(html! { "No ponies yet" }))
+    };
+    assert!(result
+        .into_string()
+        .contains(r#"<tbody><tr><td colspan="2">No ponies yet</td></tr></tbody>"#));
+}
+
+#[test]
+fn filter_row() {
+    let result = html! {
+        (Table::new(&PONIES[..1])
+            .column(Column::new("Name", |pony: &Pony| html! { (pony.name) }).filter_key("name"))
+            .column(Column::new("Color", |pony: &Pony| html! { (pony.color) }))
+            .filtered_by("name", "Ra\"rity")
+            .filter_form("filters"))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<table><thead><tr><th scope="col">Name</th><th scope="col">Color</th></tr>"#,
+            r#"<tr><td><input type="search" name="name" value="Ra&quot;rity" form="filters" aria-label="Filter Name"></td><td></td></tr>"#,
+            r#"</thead><tbody><tr><td>Rarity</td><td>white</td></tr></tbody></table>"#,
+        )
+    );
+}
+
+#[test]
+fn no_filter_row_without_filter_keys() {
+    let result = html! { (table(&PONIES).filtered_by("name", "Rarity")) };
+    assert!(!result.into_string().contains("<input"));
+}
@@ -0,0 +1,42 @@
+#![cfg(all(feature = "test-ids", debug_assertions))]
+
+use maud::html;
+
+#[test]
+fn interactive_elements() {
+    let result = html! {
+        form {
+            p { "Name" }
+            input name="name";
+            button { "Save" }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<form data-testid="maud/tests/test_ids.rs:8:form">"#,
+            "<p>Name</p>",
+            r#"<input name="name" data-testid="maud/tests/test_ids.rs:10:form/input">"#,
+            r#"<button data-testid="maud/tests/test_ids.rs:11:form/button">Save</button>"#,
+            "</form>",
+        )
+    );
+}
+
+#[test]
+fn handlers() {
+    let result = html! { div onclick="go()" { "Go" } span { "Stay" } };
+    assert_eq!(
+        result.into_string(),
+        r#"<div onclick="go()" data-testid="maud/tests/test_ids.rs:28:div">Go</div><span>Stay</span>"#
+    );
+}
+
+#[test]
+fn own_test_id() {
+    let result = html! { a href="/" data-testid="home" { "Home" } };
+    assert_eq!(
+        result.into_string(),
+        r#"<a href="/" data-testid="home">Home</a>"#
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "context")]
+
+use maud::{html, Markup, Theme, THEME_SCRIPT};
+
+fn button(label: &str) -> Markup {
+    html! {
+        button class=(Theme::current().pick("btn-light", "btn-dark")) { (label) }
+    }
+}
+
+#[test]
+fn current_defaults_to_light() {
+    assert_eq!(Theme::current(), Theme::Light);
+    assert_eq!(
+        button("Go").into_string(),
+        r#"<button class="btn-light">Go</button>"#
+    );
+}
+
+#[test]
+fn provided() {
+    let result = html! {
+        @provide(Theme::Dark) {
+            main class=(Theme::Dark) { (button("Go")) }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<main class="dark"><button class="btn-dark">Go</button></main>"#
+    );
+}
+
+#[test]
+fn parse_and_toggle() {
+    assert_eq!("dark".parse(), Ok(Theme::Dark));
+    assert_eq!(" light ".parse(), Ok(Theme::Light));
+    assert_eq!("sepia".parse::<Theme>(), Err(()));
+    assert_eq!(Theme::Light.toggle(), Theme::Dark);
+    assert_eq!(Theme::Dark.toggle().class(), "light");
+}
+
+#[test]
+fn script() {
+    let script = THEME_SCRIPT.0;
+    assert!(script.starts_with("<script>") && script.ends_with("</script>"));
+    assert!(script.contains("[data-theme-toggle]"));
+    assert!(script.contains("document.cookie=\"theme=\""));
+}
@@ -0,0 +1,84 @@
+use maud::{html, try_html, Markup, Render, TryRender};
+use std::{num::ParseIntError, rc::Rc, sync::Arc};
+
+struct Badge(&'static str);
+
+impl Render for Badge {
+    fn render(&self) -> Markup {
+        html! { span.badge { (self.0) } }
+    }
+}
+
+#[test]
+fn boxed_splices() {
+    let parts: Vec<Box<dyn Render>> = vec![Box::new(Badge("new")), Box::new("<b>"), Box::new(42)];
+    let result = html! {
+        @for part in &parts {
+            (part)
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<span class="badge">new</span>&lt;b&gt;42"#
+    );
+}
+
+#[test]
+fn shared_splices() {
+    let rc: Rc<dyn Render> = Rc::new(Badge("rc"));
+    let arc: Arc<dyn Render + Send + Sync> = Arc::new(Badge("arc"));
+    let result = html! { (rc) (arc) };
+    assert_eq!(
+        result.into_string(),
+        r#"<span class="badge">rc</span><span class="badge">arc</span>"#
+    );
+}
+
+#[test]
+fn component_props() {
+    struct Card {
+        title: &'static str,
+        body: Box<dyn Render>,
+    }
+
+    impl Render for Card {
+        fn render(&self) -> Markup {
+            html! {
+                section {
+                    h2 { (self.title) }
+                    (self.body)
+                }
+            }
+        }
+    }
+
+    let card = Card {
+        title: "Ponies",
+        body: Box::new(html! { p { "Applejack" } }),
+    };
+    assert_eq!(
+        card.render().into_string(),
+        "<section><h2>Ponies</h2><p>Applejack</p></section>"
+    );
+}
+
+#[test]
+fn boxed_try_render() {
+    struct Quantity(&'static str);
+
+    impl TryRender for Quantity {
+        type Error = ParseIntError;
+
+        fn try_render_to(&self, buffer: &mut String) -> Result<(), ParseIntError> {
+            self.0.parse::<u32>()?.render_to(buffer);
+            Ok(())
+        }
+    }
+
+    let ok: Box<dyn TryRender<Error = ParseIntError>> = Box::new(Quantity("3"));
+    let bad: Rc<dyn TryRender<Error = ParseIntError>> = Rc::new(Quantity("three"));
+    let result: Result<Markup, ParseIntError> = try_html! { (ok) };
+    assert_eq!(result.unwrap().into_string(), "3");
+    let result: Result<Markup, ParseIntError> = try_html! { (bad) };
+    assert!(result.is_err());
+}
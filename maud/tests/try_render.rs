@@ -0,0 +1,81 @@
+use maud::{html, try_html, Markup, Render, TryRender};
+use std::num::ParseIntError;
+
+struct Quantity(&'static str);
+
+impl TryRender for Quantity {
+    type Error = ParseIntError;
+
+    fn try_render_to(&self, w: &mut String) -> Result<(), ParseIntError> {
+        let n: u32 = self.0.parse()?;
+        n.render_to(w);
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum CartError {
+    BadQuantity,
+}
+
+impl From<ParseIntError> for CartError {
+    fn from(_: ParseIntError) -> CartError {
+        CartError::BadQuantity
+    }
+}
+
+fn cart(quantities: &[&'static str]) -> Result<Markup, CartError> {
+    try_html! {
+        ul {
+            @for &quantity in quantities {
+                li data-quantity=(Quantity(quantity)) { (Quantity(quantity)) " × apple" }
+            }
+        }
+    }
+}
+
+#[test]
+fn ok() {
+    assert_eq!(
+        cart(&["1", "2"]).unwrap().into_string(),
+        concat!(
+            r#"<ul><li data-quantity="1">1 × apple</li>"#,
+            r#"<li data-quantity="2">2 × apple</li></ul>"#,
+        ),
+    );
+}
+
+#[test]
+fn error_is_converted() {
+    assert_eq!(cart(&["1", "lots"]).err(), Some(CartError::BadQuantity));
+}
+
+#[test]
+fn render_types() {
+    let name = "<Pinkie Pie>";
+    let result: Result<Markup, ParseIntError> = try_html! {
+        p { (name) " has " (Quantity("3")) " balloons" }
+        (html! { br; })
+    };
+    assert_eq!(
+        result.unwrap().into_string(),
+        "<p>&lt;Pinkie Pie&gt; has 3 balloons</p><br>"
+    );
+}
+
+#[test]
+fn try_render() {
+    assert_eq!(Quantity("42").try_render().unwrap().into_string(), "42");
+    assert!(Quantity("-1").try_render().is_err());
+}
+
+#[test]
+fn early_return() {
+    let done = true;
+    let result: Result<Markup, ParseIntError> = try_html! {
+        (Quantity("1"))
+        @if done { @return }
+        (Quantity("oops"))
+    };
+    assert_eq!(result.unwrap().into_string(), "1");
+}
@@ -0,0 +1,75 @@
+use maud::{html, UrlBuilder};
+
+#[test]
+fn encodes_query() {
+    let url = UrlBuilder::new("/search")
+        .query("q", "a b&c=d/é")
+        .query("tag", "rust");
+    assert_eq!(url.build(), "/search?q=a%20b%26c%3Dd%2F%C3%A9&tag=rust");
+}
+
+#[test]
+fn appends_to_existing_query_and_keeps_fragment() {
+    let url = UrlBuilder::new("/search?sort=new#results").query("page", 3);
+    assert_eq!(url.build(), "/search?sort=new&page=3#results");
+}
+
+#[test]
+fn fragment() {
+    let url = UrlBuilder::new("/docs")
+        .query_opt("v", None::<u32>)
+        .fragment("see also");
+    assert_eq!(url.build(), "/docs#see%20also");
+}
+
+#[test]
+fn escapes_in_attributes() {
+    let url = UrlBuilder::new("/a?x=\"1\"").query("y", 2);
+    let result = html! { a href=(url) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<a href="/a?x=&quot;1&quot;&amp;y=2"></a>"#
+    );
+}
+
+#[test]
+fn strict_rejects_javascript() {
+    for url in [
+        "javascript:alert(1)",
+        "JAVASCRIPT:alert(1)",
+        "\u{1}  java\tscript:alert(1)",
+    ] {
+        assert_eq!(UrlBuilder::new(url).strict().build(), maud::BLOCKED_URL);
+    }
+}
+
+#[test]
+fn strict_allows_other_urls() {
+    for url in ["https://example.com/", "/javascript:", "javascript.html"] {
+        assert_eq!(UrlBuilder::new(url).strict().build(), url);
+    }
+}
+
+#[test]
+fn lax_allows_javascript() {
+    assert_eq!(
+        UrlBuilder::new("javascript:void(0)").build(),
+        "javascript:void(0)"
+    );
+}
+
+#[cfg(feature = "url")]
+#[test]
+fn url_crate() {
+    let url = url_dep::Url::parse("https://example.com/a?b=1&c=\"2\"").unwrap();
+    let result = html! { a href=(url) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<a href="https://example.com/a?b=1&amp;c=%222%22"></a>"#
+    );
+    let builder = UrlBuilder::from(&url).query("d", "3 4");
+    assert_eq!(
+        builder.build(),
+        "https://example.com/a?b=1&c=%222%22&d=3%204"
+    );
+}
@@ -0,0 +1,3 @@
+fn main() {
+    let _ = maud::asset!("/etc/passwd");
+}
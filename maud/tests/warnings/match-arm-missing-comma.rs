@@ -0,0 +1,10 @@
+use maud::html;
+
+fn main() {
+    html! {
+        @match Some(1) {
+            Some(_) => "some"
+            None => "none",
+        }
+    };
+}
@@ -0,0 +1,12 @@
+use maud::html;
+
+fn main() {
+    html! {
+        @fro i in 0..3 {
+            (i)
+        }
+        @mathc 1 {
+            _ => {}
+        }
+    };
+}
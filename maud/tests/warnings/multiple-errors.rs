@@ -0,0 +1,12 @@
+use maud::html;
+
+fn main() {
+    html! {
+        @fro item in [1, 2] { li { (item) } }
+        if true { p { "Yes" } }
+        p class="a" class="b" { "Duplicate" }
+        br "text"
+        h1 'x'
+        p { "Still parsing" }
+    };
+}
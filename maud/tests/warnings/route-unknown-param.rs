@@ -0,0 +1,5 @@
+maud::routes! {
+    user_profile(id: u32) => "/users/{user_id}";
+}
+
+fn main() {}
@@ -0,0 +1,5 @@
+maud::routes! {
+    user_profile(id: u32, tab: &str) => "/users/{id}";
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use maud::html;
+
+fn main() {
+    html! {
+        @profile emial;
+        p { "Hello" }
+    };
+}
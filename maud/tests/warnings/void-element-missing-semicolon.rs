@@ -0,0 +1,8 @@
+use maud::html;
+
+fn main() {
+    html! {
+        p { "Some text" }
+        hr
+    };
+}
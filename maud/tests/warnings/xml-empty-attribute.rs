@@ -0,0 +1,7 @@
+use maud::xml;
+
+fn main() {
+    xml! {
+        option selected { "Feeds" }
+    };
+}
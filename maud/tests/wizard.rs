@@ -0,0 +1,122 @@
+use maud::{html, Wizard, WizardStep};
+
+fn wizard(step: usize) -> Wizard<'static, Vec<&'static str>> {
+    Wizard::new(["Name", "Address", "Payment"], Vec::new()).at(step)
+}
+
+#[test]
+fn steps() {
+    let mut wizard = wizard(1);
+    assert!(wizard.is_first());
+    assert_eq!(wizard.step_name(), "Name");
+    assert_eq!(
+        wizard.advance(|_, _| Ok::<_, ()>(())),
+        Ok(WizardStep::Moved)
+    );
+    assert_eq!(
+        wizard.advance(|_, _| Ok::<_, ()>(())),
+        Ok(WizardStep::Moved)
+    );
+    assert!(wizard.is_last());
+    assert_eq!(
+        wizard.advance(|_, _| Ok::<_, ()>(())),
+        Ok(WizardStep::Finished)
+    );
+    assert_eq!(wizard.step(), 3);
+    wizard.back();
+    wizard.back();
+    wizard.back();
+    assert_eq!(wizard.step(), 1);
+}
+
+#[test]
+fn out_of_range_steps_are_clamped() {
+    assert_eq!(wizard(0).step(), 1);
+    assert_eq!(wizard(9).step(), 3);
+}
+
+#[test]
+fn apply() {
+    let mut wizard = wizard(2);
+    let check = |step: usize, data: &Vec<&str>| {
+        if step == 2 && data.is_empty() {
+            Err("Enter an address")
+        } else {
+            Ok(())
+        }
+    };
+    assert_eq!(wizard.apply(None, check), Ok(WizardStep::Moved));
+    assert_eq!(wizard.step(), 2);
+    assert_eq!(wizard.apply(Some("next"), check), Err("Enter an address"));
+    assert_eq!(wizard.step(), 2);
+    wizard.data.push("1 Carousel Boutique");
+    assert_eq!(wizard.apply(Some(""), check), Ok(WizardStep::Moved));
+    assert_eq!(wizard.step(), 3);
+    // Going back doesn't check anything
+    assert_eq!(
+        wizard.apply(Some("back"), |_, _| Err("unreachable")),
+        Ok(WizardStep::Moved)
+    );
+    assert_eq!(wizard.step(), 2);
+    assert_eq!(wizard.into_data(), ["1 Carousel Boutique"]);
+}
+
+#[test]
+fn skipped_steps_are_checked() {
+    // A form posted straight to the last step
+    let mut wizard = wizard(3);
+    let check = |step: usize, data: &Vec<&str>| {
+        if step == 1 && data.is_empty() {
+            Err("Enter a name")
+        } else {
+            Ok(())
+        }
+    };
+    assert_eq!(wizard.apply(Some("next"), check), Err("Enter a name"));
+    assert_eq!(wizard.step(), 1);
+    wizard.data.push("Rarity");
+    let mut checked = Vec::new();
+    let result = wizard.at(3).advance(|step, _| {
+        checked.push(step);
+        Ok::<_, ()>(())
+    });
+    assert_eq!(result, Ok(WizardStep::Finished));
+    assert_eq!(checked, [1, 2, 3]);
+}
+
+#[test]
+fn progress() {
+    let result = html! { (wizard(2).progress()) };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<ol class="wizard-steps">"#,
+            r#"<li class="done">Name</li><li aria-current="step">Address</li><li>Payment</li>"#,
+            "</ol>",
+        )
+    );
+}
+
+#[test]
+fn controls() {
+    assert_eq!(
+        html! { (wizard(1).controls()) }.into_string(),
+        concat!(
+            r#"<input type="hidden" name="step" value="1">"#,
+            r#"<div class="wizard-controls">"#,
+            r#"<button type="submit" name="wizard" value="next">Next</button>"#,
+            "</div>",
+        )
+    );
+    let wizard = wizard(3).finish_label("Pay <now>").back_label("Zurück");
+    assert_eq!(
+        html! { (wizard.controls()) }.into_string(),
+        concat!(
+            r#"<input type="hidden" name="step" value="3">"#,
+            r#"<div class="wizard-controls">"#,
+            r#"<button type="submit" name="wizard" value="next">Pay &lt;now&gt;</button>"#,
+            r#"<button type="submit" name="wizard" value="back" formnovalidate>Zurück</button>"#,
+            "</div>",
+        )
+    );
+}
@@ -0,0 +1,47 @@
+use maud::{xml, Cdata};
+
+#[test]
+fn rss_feed() {
+    let title = "Tom & Jerry";
+    let result = xml! {
+        @pi xml version="1.0" encoding="UTF-8";
+        rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" {
+            channel {
+                title { (title) }
+                atom:link href="https://example.com/feed.xml" rel="self";
+                item {
+                    description { (Cdata("<p>It's <em>back</em></p>")) }
+                }
+            }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+            r#"<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom">"#,
+            "<channel>",
+            "<title>Tom &amp; Jerry</title>",
+            r#"<atom:link href="https://example.com/feed.xml" rel="self"/>"#,
+            "<item><description><![CDATA[<p>It's <em>back</em></p>]]></description></item>",
+            "</channel>",
+            "</rss>",
+        )
+    );
+}
+
+#[test]
+fn processing_instruction_with_splice() {
+    let href = "/feed.xsl";
+    let result = xml! { @pi xml-stylesheet type="text/xsl" href=(href); };
+    assert_eq!(
+        result.into_string(),
+        r#"<?xml-stylesheet type="text/xsl" href="/feed.xsl"?>"#
+    );
+}
+
+#[test]
+fn cdata_end_is_split() {
+    let result = xml! { (Cdata("a]]>b")) };
+    assert_eq!(result.into_string(), "<![CDATA[a]]]]><![CDATA[>b]]>");
+}
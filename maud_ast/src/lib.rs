@@ -0,0 +1,130 @@
+//! The syntax tree of Maud templates, for tools that read them.
+//!
+//! This is the parser that `html!` itself uses, compiled as a plain library
+//! so that formatters, linters, codemods and documentation generators don't
+//! have to write their own. [`parse`] turns the contents of a template into
+//! a tree of [`Markup`], [`Visit`] walks it, and [`print`] writes it back
+//! out as template source.
+//!
+//! Rust code inside a template, like splices and the conditions of `@if`,
+//! is kept as a [`TokenStream`], as the macro would paste it. Spans carry
+//! line and column numbers, from the source that the tokens were parsed
+//! from.
+//!
+//! With the `serde` feature, the tree implements `serde::Serialize`. Token
+//! streams are written as strings, and spans as their start and end lines
+//! and columns.
+//!
+//! # Stability
+//!
+//! The tree follows the template syntax, so it changes whenever the syntax
+//! does, in any release. Pin an exact version of this crate.
+//!
+//! # Example
+//!
+//! ```rust
+//! use maud_ast::{proc_macro2::TokenStream, Markup, Visit};
+//!
+//! /// Counts the elements in a template.
+//! #[derive(Default)]
+//! struct Elements(usize);
+//!
+//! impl<'ast> Visit<'ast> for Elements {
+//!     fn visit_markup(&mut self, markup: &'ast Markup) {
+//!         if let Markup::Element { .. } = markup {
+//!             self.0 += 1;
+//!         }
+//!         maud_ast::visit::walk_markup(self, markup);
+//!     }
+//! }
+//!
+//! let tokens: TokenStream = r#"ul { li { "One" } li { "Two" } }"#.parse().unwrap();
+//! let template = maud_ast::parse(tokens).unwrap();
+//! let mut elements = Elements::default();
+//! elements.visit_markups(&template.markups);
+//! assert_eq!(elements.0, 3);
+//! ```
+
+#![doc(html_root_url = "https://docs.rs/maud_ast/0.24.0")]
+
+use proc_macro2::TokenStream;
+
+pub use proc_macro2;
+pub use proc_macro_error::SpanRange;
+
+pub use crate::{
+    ast::{Attr, AttrType, Block, ElementBody, Markup, MatchArm, NamedAttr, Special, Toggler},
+    diagnostics::{Level, Message},
+    visit::Visit,
+};
+
+// The parser is compiled in from `maud_macros`, as a proc-macro crate can't
+// be linked into anything else. Only some of what it defines is used here.
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/ast.rs"]
+mod ast;
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/diagnostics.rs"]
+mod diagnostics;
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/parse.rs"]
+mod parse;
+#[path = "../../maud_macros/src/pretty.rs"]
+mod pretty;
+#[cfg(feature = "serde")]
+mod ser;
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/suggest.rs"]
+mod suggest;
+pub mod visit;
+
+/// A template that parsed without errors.
+#[derive(Debug)]
+pub struct Template {
+    /// The markup at the top level of the template.
+    pub markups: Vec<Markup>,
+    /// What the parser warned about, like misspelled attribute names.
+    pub warnings: Vec<Message>,
+}
+
+/// Parses the contents of an `html!` block, without the braces around it.
+///
+/// If there are any errors, returns every message instead, warnings
+/// included.
+pub fn parse(input: TokenStream) -> Result<Template, Vec<Message>> {
+    template(diagnostics::capture(|| parse::parse(input)))
+}
+
+/// Parses the contents of an `xml!` block, which doesn't check attribute
+/// names against HTML's.
+pub fn parse_xml(input: TokenStream) -> Result<Template, Vec<Message>> {
+    template(diagnostics::capture(|| parse::parse_xml(input)))
+}
+
+fn template(
+    (markups, messages): (Option<Vec<Markup>>, Vec<Message>),
+) -> Result<Template, Vec<Message>> {
+    match markups {
+        Some(markups) if messages.iter().all(|message| message.level != Level::Error) => {
+            Ok(Template {
+                markups,
+                warnings: messages,
+            })
+        }
+        _ => Err(messages),
+    }
+}
+
+/// Prints a tree back out as template source.
+///
+/// The output is all on one line, and isn't meant to look like the
+/// original, only to parse back to the same tree. Use `maudfmt` to lay it
+/// out.
+///
+/// # Panics
+///
+/// If the tree has a [`Markup::ParseError`] in it, which [`parse`] never
+/// returns.
+pub fn print(markups: &[Markup]) -> String {
+    pretty::print(markups)
+}
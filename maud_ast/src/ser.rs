@@ -0,0 +1,299 @@
+//! `Serialize` for the syntax tree, with the `serde` feature.
+//!
+//! Each enum is written the way `#[derive(Serialize)]` would write it, so
+//! in JSON a node looks like `{"Element": {"name": "p", ...}}`. Rust code is
+//! written as a string, and each span as the line and column it starts and
+//! ends at.
+
+use proc_macro2::{LineColumn, TokenStream};
+use proc_macro_error::SpanRange;
+use serde_dep::ser::{Serialize, SerializeStruct, SerializeStructVariant, Serializer};
+
+use crate::{Attr, AttrType, Block, ElementBody, Markup, MatchArm, NamedAttr, Special, Toggler};
+
+/// Writes a struct variant, with each field in the order given.
+macro_rules! variant {
+    ($serializer:expr, $enum:literal, $index:literal, $variant:literal {
+        $($field:literal: $value:expr),* $(,)?
+    }) => {{
+        let mut state = $serializer.serialize_struct_variant(
+            $enum,
+            $index,
+            $variant,
+            [$($field),*].len(),
+        )?;
+        $(state.serialize_field($field, $value)?;)*
+        state.end()
+    }};
+}
+
+/// Writes a struct, with each field in the order given.
+macro_rules! fields {
+    ($serializer:expr, $struct:literal { $($field:literal: $value:expr),* $(,)? }) => {{
+        let mut state = $serializer.serialize_struct($struct, [$($field),*].len())?;
+        $(state.serialize_field($field, $value)?;)*
+        state.end()
+    }};
+}
+
+struct Tokens<'a>(&'a TokenStream);
+
+impl Serialize for Tokens<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self.0)
+    }
+}
+
+struct Span(SpanRange);
+
+impl Serialize for Span {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields!(serializer, "Span" {
+            "start": &Position(self.0.first.start()),
+            "end": &Position(self.0.last.end()),
+        })
+    }
+}
+
+struct Position(LineColumn);
+
+impl Serialize for Position {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields!(serializer, "Position" {
+            "line": &self.0.line,
+            "column": &self.0.column,
+        })
+    }
+}
+
+impl Serialize for Markup {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Markup::ParseError { span } => variant!(serializer, "Markup", 0, "ParseError" {
+                "span": &Span(*span),
+            }),
+            Markup::Block(block) => {
+                serializer.serialize_newtype_variant("Markup", 1, "Block", block)
+            }
+            Markup::Literal { content, span } => variant!(serializer, "Markup", 2, "Literal" {
+                "content": content,
+                "span": &Span(*span),
+            }),
+            Markup::Symbol { symbol } => variant!(serializer, "Markup", 3, "Symbol" {
+                "symbol": &Tokens(symbol),
+            }),
+            Markup::Splice { expr, outer_span } => variant!(serializer, "Markup", 4, "Splice" {
+                "expr": &Tokens(expr),
+                "outer_span": &Span(*outer_span),
+            }),
+            Markup::Element { name, attrs, body } => variant!(serializer, "Markup", 5, "Element" {
+                "name": &Tokens(name),
+                "attrs": attrs,
+                "body": body,
+            }),
+            Markup::Let { at_span, tokens } => variant!(serializer, "Markup", 6, "Let" {
+                "at_span": &Span(*at_span),
+                "tokens": &Tokens(tokens),
+            }),
+            Markup::Control { at_span, tokens } => variant!(serializer, "Markup", 7, "Control" {
+                "at_span": &Span(*at_span),
+                "tokens": &Tokens(tokens),
+            }),
+            Markup::Return { at_span } => variant!(serializer, "Markup", 8, "Return" {
+                "at_span": &Span(*at_span),
+            }),
+            Markup::Flush { at_span } => variant!(serializer, "Markup", 9, "Flush" {
+                "at_span": &Span(*at_span),
+            }),
+            Markup::Suspend {
+                at_span,
+                future,
+                body,
+            } => variant!(serializer, "Markup", 10, "Suspend" {
+                "at_span": &Span(*at_span),
+                "future": &Tokens(future),
+                "body": body,
+            }),
+            Markup::Provide {
+                at_span,
+                value,
+                body,
+            } => variant!(serializer, "Markup", 11, "Provide" {
+                "at_span": &Span(*at_span),
+                "value": &Tokens(value),
+                "body": body,
+            }),
+            Markup::Portal {
+                at_span,
+                target,
+                body,
+            } => variant!(serializer, "Markup", 12, "Portal" {
+                "at_span": &Span(*at_span),
+                "target": target,
+                "body": body,
+            }),
+            Markup::Minify { at_span } => variant!(serializer, "Markup", 13, "Minify" {
+                "at_span": &Span(*at_span),
+            }),
+            Markup::MinimalQuotes { at_span } => {
+                variant!(serializer, "Markup", 14, "MinimalQuotes" {
+                    "at_span": &Span(*at_span),
+                })
+            }
+            Markup::Profile { at_span, name } => variant!(serializer, "Markup", 15, "Profile" {
+                "at_span": &Span(*at_span),
+                "name": &name.to_string(),
+            }),
+            Markup::ProcessingInstruction {
+                at_span,
+                name,
+                attrs,
+            } => variant!(serializer, "Markup", 16, "ProcessingInstruction" {
+                "at_span": &Span(*at_span),
+                "name": &Tokens(name),
+                "attrs": attrs,
+            }),
+            Markup::Special { segments } => variant!(serializer, "Markup", 17, "Special" {
+                "segments": segments,
+            }),
+            Markup::Match {
+                at_span,
+                head,
+                arms,
+                arms_span,
+            } => variant!(serializer, "Markup", 18, "Match" {
+                "at_span": &Span(*at_span),
+                "head": &Tokens(head),
+                "arms": arms,
+                "arms_span": &Span(*arms_span),
+            }),
+            Markup::Style {
+                at_span,
+                css,
+                body_span,
+                scope,
+            } => variant!(serializer, "Markup", 19, "Style" {
+                "at_span": &Span(*at_span),
+                "css": css,
+                "body_span": &Span(*body_span),
+                "scope": scope,
+            }),
+        }
+    }
+}
+
+impl Serialize for Attr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Attr::Class {
+                dot_span,
+                name,
+                toggler,
+            } => variant!(serializer, "Attr", 0, "Class" {
+                "dot_span": &Span(*dot_span),
+                "name": name,
+                "toggler": toggler,
+            }),
+            Attr::Id { hash_span, name } => variant!(serializer, "Attr", 1, "Id" {
+                "hash_span": &Span(*hash_span),
+                "name": name,
+            }),
+            Attr::Named { named_attr } => variant!(serializer, "Attr", 2, "Named" {
+                "named_attr": named_attr,
+            }),
+            Attr::Spread {
+                dots_span,
+                expr,
+                expr_span,
+            } => variant!(serializer, "Attr", 3, "Spread" {
+                "dots_span": &Span(*dots_span),
+                "expr": &Tokens(expr),
+                "expr_span": &Span(*expr_span),
+            }),
+        }
+    }
+}
+
+impl Serialize for ElementBody {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ElementBody::Void { semi_span } => variant!(serializer, "ElementBody", 0, "Void" {
+                "semi_span": &Span(*semi_span),
+            }),
+            ElementBody::Block { block } => variant!(serializer, "ElementBody", 1, "Block" {
+                "block": block,
+            }),
+        }
+    }
+}
+
+impl Serialize for Block {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields!(serializer, "Block" {
+            "markups": &self.markups,
+            "outer_span": &Span(self.outer_span),
+        })
+    }
+}
+
+impl Serialize for Special {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields!(serializer, "Special" {
+            "at_span": &Span(self.at_span),
+            "head": &Tokens(&self.head),
+            "body": &self.body,
+        })
+    }
+}
+
+impl Serialize for NamedAttr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields!(serializer, "NamedAttr" {
+            "name": &Tokens(&self.name),
+            "attr_type": &self.attr_type,
+        })
+    }
+}
+
+impl Serialize for AttrType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            AttrType::Normal { value } => variant!(serializer, "AttrType", 0, "Normal" {
+                "value": value,
+            }),
+            AttrType::Optional { toggler } => variant!(serializer, "AttrType", 1, "Optional" {
+                "toggler": toggler,
+            }),
+            AttrType::Empty { toggler } => variant!(serializer, "AttrType", 2, "Empty" {
+                "toggler": toggler,
+            }),
+            AttrType::Fields {
+                dots_span,
+                value,
+                value_span,
+            } => variant!(serializer, "AttrType", 3, "Fields" {
+                "dots_span": &Span(*dots_span),
+                "value": &Tokens(value),
+                "value_span": &Span(*value_span),
+            }),
+        }
+    }
+}
+
+impl Serialize for Toggler {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields!(serializer, "Toggler" {
+            "cond": &Tokens(&self.cond),
+            "cond_span": &Span(self.cond_span),
+        })
+    }
+}
+
+impl Serialize for MatchArm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        fields!(serializer, "MatchArm" {
+            "head": &Tokens(&self.head),
+            "body": &self.body,
+        })
+    }
+}
@@ -0,0 +1,113 @@
+//! Walking the syntax tree.
+//!
+//! Each method of [`Visit`] goes into the children of its node by default,
+//! by calling the `walk_` function of the same name. Override the ones for
+//! the nodes you're after, and call the `walk_` function from them to keep
+//! going down.
+
+use crate::{Attr, AttrType, Block, ElementBody, Markup, Toggler};
+
+/// Visits the nodes of a template, in the order they're written.
+pub trait Visit<'ast> {
+    /// Visits each markup in a list.
+    fn visit_markups(&mut self, markups: &'ast [Markup]) {
+        walk_markups(self, markups);
+    }
+
+    /// Visits a markup node.
+    fn visit_markup(&mut self, markup: &'ast Markup) {
+        walk_markup(self, markup);
+    }
+
+    /// Visits a block, like the body of an element or an `@if`.
+    fn visit_block(&mut self, block: &'ast Block) {
+        walk_block(self, block);
+    }
+
+    /// Visits an attribute, class or ID.
+    fn visit_attr(&mut self, attr: &'ast Attr) {
+        walk_attr(self, attr);
+    }
+
+    /// Visits the condition of a toggled class or attribute, which has no
+    /// children.
+    fn visit_toggler(&mut self, _toggler: &'ast Toggler) {}
+}
+
+pub fn walk_markups<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, markups: &'ast [Markup]) {
+    for markup in markups {
+        visitor.visit_markup(markup);
+    }
+}
+
+pub fn walk_markup<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, markup: &'ast Markup) {
+    match markup {
+        Markup::Block(block)
+        | Markup::Suspend { body: block, .. }
+        | Markup::Provide { body: block, .. } => visitor.visit_block(block),
+        Markup::Element { attrs, body, .. } => {
+            for attr in attrs {
+                visitor.visit_attr(attr);
+            }
+            if let ElementBody::Block { block } = body {
+                visitor.visit_block(block);
+            }
+        }
+        Markup::Portal { target, body, .. } => {
+            visitor.visit_markup(target);
+            visitor.visit_block(body);
+        }
+        Markup::ProcessingInstruction { attrs, .. } => {
+            for attr in attrs {
+                visitor.visit_attr(attr);
+            }
+        }
+        Markup::Special { segments } => {
+            for segment in segments {
+                visitor.visit_block(&segment.body);
+            }
+        }
+        Markup::Match { arms, .. } => {
+            for arm in arms {
+                visitor.visit_block(&arm.body);
+            }
+        }
+        Markup::ParseError { .. }
+        | Markup::Literal { .. }
+        | Markup::Symbol { .. }
+        | Markup::Splice { .. }
+        | Markup::Let { .. }
+        | Markup::Control { .. }
+        | Markup::Return { .. }
+        | Markup::Flush { .. }
+        | Markup::Minify { .. }
+        | Markup::MinimalQuotes { .. }
+        | Markup::Profile { .. }
+        | Markup::Style { .. } => {}
+    }
+}
+
+pub fn walk_block<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, block: &'ast Block) {
+    visitor.visit_markups(&block.markups);
+}
+
+pub fn walk_attr<'ast, V: Visit<'ast> + ?Sized>(visitor: &mut V, attr: &'ast Attr) {
+    match attr {
+        Attr::Class { name, toggler, .. } => {
+            visitor.visit_markup(name);
+            if let Some(toggler) = toggler {
+                visitor.visit_toggler(toggler);
+            }
+        }
+        Attr::Id { name, .. } => visitor.visit_markup(name),
+        Attr::Named { named_attr } => match &named_attr.attr_type {
+            AttrType::Normal { value } => visitor.visit_markup(value),
+            AttrType::Optional { toggler }
+            | AttrType::Empty {
+                toggler: Some(toggler),
+            } => visitor.visit_toggler(toggler),
+            AttrType::Empty { toggler: None } | AttrType::Fields { .. } => {}
+        },
+        Attr::Spread { .. } => {}
+    }
+}
@@ -0,0 +1,146 @@
+use maud_ast::{proc_macro2::TokenStream, visit, Attr, Level, Markup, Toggler, Visit};
+
+fn parse(source: &str) -> maud_ast::Template {
+    maud_ast::parse(source.parse::<TokenStream>().unwrap()).unwrap()
+}
+
+#[test]
+fn elements() {
+    let template = parse(r#"p.lead { "Hello, " (name) "!" } br;"#);
+    let [Markup::Element { name, attrs, .. }, Markup::Element { .. }] = &template.markups[..]
+    else {
+        panic!("expected two elements, got {:?}", template.markups);
+    };
+    assert_eq!(name.to_string(), "p");
+    assert!(matches!(attrs[..], [Attr::Class { .. }]));
+}
+
+#[test]
+fn errors() {
+    let messages = maud_ast::parse(r#"p { "Hi" } br"#.parse().unwrap()).unwrap_err();
+    assert!(messages.iter().any(|message| message.level == Level::Error));
+}
+
+#[test]
+fn warnings() {
+    let template = parse(r#"button onclck="go()" { "Go" }"#);
+    assert_eq!(template.warnings.len(), 1);
+    assert!(template.warnings[0].text.contains("onclick"));
+}
+
+#[test]
+fn xml_attributes_arent_checked() {
+    let template = maud_ast::parse_xml(r#"entry onclck="x" {}"#.parse().unwrap()).unwrap();
+    assert!(template.warnings.is_empty());
+}
+
+#[test]
+fn spans() {
+    let template = parse("p {\n    (name)\n}");
+    let span = template.markups[0].span();
+    assert_eq!((span.first.start().line, span.last.end().line), (1, 3));
+}
+
+#[test]
+fn print_round_trips() {
+    let source = r#"ul#list { @for item in items { li.done[item.done] { (item.name) } } }"#;
+    let printed = maud_ast::print(&parse(source).markups);
+    assert_eq!(maud_ast::print(&parse(&printed).markups), printed);
+}
+
+/// Collects the elements and conditions in a template.
+#[derive(Default)]
+struct Collect {
+    elements: Vec<String>,
+    togglers: Vec<String>,
+}
+
+impl<'ast> Visit<'ast> for Collect {
+    fn visit_markup(&mut self, markup: &'ast Markup) {
+        if let Markup::Element { name, .. } = markup {
+            self.elements.push(name.to_string());
+        }
+        visit::walk_markup(self, markup);
+    }
+
+    fn visit_toggler(&mut self, toggler: &'ast Toggler) {
+        self.togglers.push(toggler.cond.to_string());
+    }
+}
+
+#[test]
+fn visit() {
+    let template = parse(
+        r#"
+        main {
+            @if admin {
+                a.active[current] href="/admin" { "Admin" }
+            } @else {
+                input checked[on];
+            }
+            @match page {
+                Page::Home => { h1 { "Home" } }
+                _ => {}
+            }
+        }
+        "#,
+    );
+    let mut collect = Collect::default();
+    collect.visit_markups(&template.markups);
+    assert_eq!(collect.elements, ["main", "a", "input", "h1"]);
+    assert_eq!(collect.togglers, ["current", "on"]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serialize() {
+    let template = parse(r#"p title=(title) { "Hi" }"#);
+    let json = serde_json::to_value(&template.markups).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([{
+            "Element": {
+                "name": "p",
+                "attrs": [{
+                    "Named": {
+                        "named_attr": {
+                            "name": "title",
+                            "attr_type": {
+                                "Normal": {
+                                    "value": {
+                                        "Splice": {
+                                            "expr": "title",
+                                            "outer_span": {
+                                                "start": { "line": 1, "column": 8 },
+                                                "end": { "line": 1, "column": 15 },
+                                            },
+                                        },
+                                    },
+                                },
+                            },
+                        },
+                    },
+                }],
+                "body": {
+                    "Block": {
+                        "block": {
+                            "markups": [{
+                                "Literal": {
+                                    "content": "Hi",
+                                    "span": {
+                                        "start": { "line": 1, "column": 18 },
+                                        "end": { "line": 1, "column": 22 },
+                                    },
+                                },
+                            }],
+                            "outer_span": {
+                                "start": { "line": 1, "column": 16 },
+                                "end": { "line": 1, "column": 24 },
+                            },
+                        },
+                    },
+                },
+            },
+        }])
+    );
+}
@@ -0,0 +1,132 @@
+use proc_macro2::{Literal, TokenStream, TokenTree};
+use proc_macro_error::{abort, abort_call_site, emit_error};
+use quote::quote;
+use std::{
+    env, fs,
+    path::{Component, Path},
+};
+use syn::Lit;
+
+use crate::{ast, hash::fnv1a};
+
+/// The directory that assets are read from, relative to the crate root.
+const ASSET_DIR_VAR: &str = "MAUD_ASSET_DIR";
+const DEFAULT_ASSET_DIR: &str = "static";
+
+/// The URL prefix that assets are served under.
+const ASSET_URL_VAR: &str = "MAUD_ASSET_URL";
+const DEFAULT_ASSET_URL: &str = "/static";
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let (literal, path) = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Literal(literal)), None) => match ast::lit(&literal) {
+            Some(Lit::Str(lit_str)) => (literal, lit_str.value()),
+            _ => abort!(literal, "expected string"),
+        },
+        _ => abort_call_site!("expected a path, e.g. `asset!(\"app.css\")`"),
+    };
+    // A bad path or a missing file still expands to a string, so that the
+    // error is the only one reported
+    let url_path = match url_path(&path) {
+        Some(url_path) => url_path,
+        None => {
+            emit_error!(
+                literal,
+                "asset paths can't be absolute or contain `.` or `..`";
+                help = "give the path relative to the `{}` directory, e.g. `css/app.css`",
+                DEFAULT_ASSET_DIR
+            );
+            return quote!("");
+        }
+    };
+    let manifest_dir = env::var_os("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let asset_dir = env::var(ASSET_DIR_VAR).unwrap_or_else(|_| DEFAULT_ASSET_DIR.to_string());
+    let url_prefix = env::var(ASSET_URL_VAR).unwrap_or_else(|_| DEFAULT_ASSET_URL.to_string());
+    let file_path = Path::new(&manifest_dir).join(asset_dir).join(&path);
+    let contents = match fs::read(&file_path) {
+        Ok(contents) => contents,
+        Err(error) => {
+            emit_error!(
+                literal,
+                "could not read asset `{}`: {}", file_path.display(), error;
+                help = "assets are read from the `{}` directory, which can be changed with `{}`",
+                DEFAULT_ASSET_DIR, ASSET_DIR_VAR
+            );
+            return quote!("");
+        }
+    };
+    let url = TokenTree::Literal(Literal::string(&asset_url(
+        &url_prefix,
+        &url_path,
+        &contents,
+    )));
+    let file_path = TokenTree::Literal(Literal::string(&file_path.to_string_lossy()));
+    // Mention the file with `include_bytes!`, and the variables with
+    // `option_env!`, so that the URL is regenerated whenever the asset or
+    // either variable changes
+    quote!({
+        const _: &[u8] = include_bytes!(#file_path);
+        const _: ::core::option::Option<&str> = option_env!(#ASSET_DIR_VAR);
+        const _: ::core::option::Option<&str> = option_env!(#ASSET_URL_VAR);
+        #url
+    })
+}
+
+/// Turns an asset's path into the path part of its URL, or `None` if it's
+/// absolute or steps outside the asset directory.
+fn url_path(path: &str) -> Option<String> {
+    let names = Path::new(path)
+        .components()
+        .map(|component| match component {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if names.is_empty() {
+        return None;
+    }
+    Some(names.join("/"))
+}
+
+/// Builds the URL for an asset, with a query string derived from its
+/// contents.
+fn asset_url(url_prefix: &str, url_path: &str, contents: &[u8]) -> String {
+    format!(
+        "{}/{}?v={:08x}",
+        url_prefix.trim_end_matches('/'),
+        url_path,
+        fnv1a(contents)
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{asset_url, url_path};
+
+    #[test]
+    fn url() {
+        assert_eq!(
+            asset_url("/static", "app.css", b"body {}"),
+            asset_url("/static/", "app.css", b"body {}"),
+        );
+        assert!(
+            asset_url("/static", "css/app.css", b"body {}").starts_with("/static/css/app.css?v=")
+        );
+        assert_ne!(
+            asset_url("/static", "app.css", b"body {}"),
+            asset_url("/static", "app.css", b"body { color: red }"),
+        );
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(url_path("css/app.css").as_deref(), Some("css/app.css"));
+        assert_eq!(url_path("css//app.css").as_deref(), Some("css/app.css"));
+        assert_eq!(url_path("./app.css"), None);
+        assert_eq!(url_path("../Cargo.toml"), None);
+        assert_eq!(url_path("css/../../Cargo.toml"), None);
+        assert_eq!(url_path("/etc/passwd"), None);
+        assert_eq!(url_path(""), None);
+    }
+}
@@ -1,5 +1,6 @@
-use proc_macro2::{TokenStream, TokenTree};
+use proc_macro2::{Ident, Literal, Punct, Spacing, TokenStream, TokenTree};
 use proc_macro_error::SpanRange;
+use syn::Lit;
 
 #[derive(Debug)]
 pub enum Markup {
@@ -28,6 +29,62 @@ pub enum Markup {
         at_span: SpanRange,
         tokens: TokenStream,
     },
+    /// A `@break` or `@continue`, passed through as is.
+    Control {
+        at_span: SpanRange,
+        tokens: TokenStream,
+    },
+    /// A `@return`, which ends the template early.
+    Return {
+        at_span: SpanRange,
+    },
+    /// A `@flush`, which sends what's been rendered so far to the sink in
+    /// `html_stream!`.
+    Flush {
+        at_span: SpanRange,
+    },
+    /// A `@suspend(future) { placeholder }`, which shows the placeholder
+    /// until the future resolves in `html_stream!`.
+    Suspend {
+        at_span: SpanRange,
+        future: TokenStream,
+        body: Block,
+    },
+    /// A `@provide(value) { ... }`, which makes the value available to
+    /// everything rendered inside it.
+    Provide {
+        at_span: SpanRange,
+        value: TokenStream,
+        body: Block,
+    },
+    /// A `@portal(target = "#id") { ... }`, whose body is moved into
+    /// another element on the page.
+    Portal {
+        at_span: SpanRange,
+        target: Box<Markup>,
+        body: Block,
+    },
+    /// A `@minify;`, which shrinks the static parts of the template.
+    Minify {
+        at_span: SpanRange,
+    },
+    /// A `@quotes minimal;`, which leaves the quotes off static attribute
+    /// values where HTML allows it.
+    MinimalQuotes {
+        at_span: SpanRange,
+    },
+    /// A `@profile name;`, which checks the template against a validation
+    /// profile.
+    Profile {
+        at_span: SpanRange,
+        name: Ident,
+    },
+    /// A `@pi name attrs;` processing instruction, for `xml!`.
+    ProcessingInstruction {
+        at_span: SpanRange,
+        name: TokenStream,
+        attrs: Vec<Attr>,
+    },
     Special {
         segments: Vec<Special>,
     },
@@ -37,6 +94,13 @@ pub enum Markup {
         arms: Vec<MatchArm>,
         arms_span: SpanRange,
     },
+    Style {
+        at_span: SpanRange,
+        css: String,
+        body_span: SpanRange,
+        /// The generated class name, filled in by `scoped::apply`.
+        scope: Option<String>,
+    },
 }
 
 impl Markup {
@@ -57,10 +121,42 @@ impl Markup {
                 at_span,
                 ref tokens,
             } => at_span.join_range(span_tokens(tokens.clone())),
+            Markup::Control {
+                at_span,
+                ref tokens,
+            } => at_span.join_range(span_tokens(tokens.clone())),
+            Markup::Return { at_span }
+            | Markup::Flush { at_span }
+            | Markup::Minify { at_span }
+            | Markup::MinimalQuotes { at_span } => at_span,
+            Markup::Suspend {
+                at_span, ref body, ..
+            }
+            | Markup::Provide {
+                at_span, ref body, ..
+            }
+            | Markup::Portal {
+                at_span, ref body, ..
+            } => at_span.join_range(body.span()),
+            Markup::Profile { at_span, ref name } => {
+                at_span.join_range(SpanRange::single_span(name.span()))
+            }
+            Markup::ProcessingInstruction {
+                at_span,
+                ref name,
+                ref attrs,
+            } => join_ranges(
+                [at_span, span_tokens(name.clone())]
+                    .into_iter()
+                    .chain(attrs.iter().map(Attr::span)),
+            ),
             Markup::Special { ref segments } => join_ranges(segments.iter().map(Special::span)),
             Markup::Match {
                 at_span, arms_span, ..
             } => at_span.join_range(arms_span),
+            Markup::Style {
+                at_span, body_span, ..
+            } => at_span.join_range(body_span),
         }
     }
 }
@@ -79,6 +175,12 @@ pub enum Attr {
     Named {
         named_attr: NamedAttr,
     },
+    /// `..rest`, which writes the attributes in a `maud::Attributes`.
+    Spread {
+        dots_span: SpanRange,
+        expr: TokenStream,
+        expr_span: SpanRange,
+    },
 }
 
 impl Attr {
@@ -105,6 +207,11 @@ impl Attr {
                 hash_span.join_range(name_span)
             }
             Attr::Named { ref named_attr } => named_attr.span(),
+            Attr::Spread {
+                dots_span,
+                expr_span,
+                ..
+            } => dots_span.join_range(expr_span),
         }
     }
 }
@@ -169,9 +276,22 @@ impl NamedAttr {
 
 #[derive(Debug)]
 pub enum AttrType {
-    Normal { value: Markup },
-    Optional { toggler: Toggler },
-    Empty { toggler: Option<Toggler> },
+    Normal {
+        value: Markup,
+    },
+    Optional {
+        toggler: Toggler,
+    },
+    Empty {
+        toggler: Option<Toggler>,
+    },
+    /// `data..=(value)`, which writes one `data-*` attribute per field of
+    /// the value.
+    Fields {
+        dots_span: SpanRange,
+        value: TokenStream,
+        value_span: SpanRange,
+    },
 }
 
 impl AttrType {
@@ -180,6 +300,11 @@ impl AttrType {
             AttrType::Normal { ref value } => Some(value.span()),
             AttrType::Optional { ref toggler } => Some(toggler.span()),
             AttrType::Empty { ref toggler } => toggler.as_ref().map(Toggler::span),
+            AttrType::Fields {
+                dots_span,
+                value_span,
+                ..
+            } => Some(dots_span.join_range(value_span)),
         }
     }
 }
@@ -216,6 +341,28 @@ pub fn join_ranges<I: IntoIterator<Item = SpanRange>>(ranges: I) -> SpanRange {
     first.join_range(last)
 }
 
+/// Parses a literal token.
+///
+/// Returns `None` for C string literals (`c"..."`), which `syn` can't parse
+/// and would panic on.
+pub fn lit(literal: &Literal) -> Option<Lit> {
+    if literal.to_string().starts_with('c') {
+        None
+    } else {
+        Some(Lit::new(literal.clone()))
+    }
+}
+
 pub fn name_to_string(name: TokenStream) -> String {
     name.into_iter().map(|token| token.to_string()).collect()
 }
+
+/// Whether an `=` is an assignment, rather than part of `==`, `<=`, `=>`,
+/// `..=` and so on.
+pub fn is_plain_eq(prev: Option<&TokenTree>, eq: &Punct, next: Option<&TokenTree>) -> bool {
+    let joined_to_prev =
+        matches!(prev, Some(TokenTree::Punct(prev)) if prev.spacing() == Spacing::Joint);
+    let joined_to_next = eq.spacing() == Spacing::Joint
+        && matches!(next, Some(TokenTree::Punct(next)) if matches!(next.as_char(), '=' | '>'));
+    !joined_to_prev && !joined_to_next
+}
@@ -0,0 +1,113 @@
+//! Stand-ins for the `proc_macro_error` macros, so that the parser can run
+//! outside of a macro expansion, in tests, under the fuzzer, in maudfmt and
+//! in maud_ast.
+//!
+//! `proc_macro_error` reports through the compiler, which isn't there when
+//! we call the parser directly. These macros record each message instead,
+//! and [`capture`] hands them back.
+
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    Error,
+    Warning,
+}
+
+#[derive(Debug)]
+pub struct Message {
+    pub level: Level,
+    pub text: String,
+}
+
+/// The payload of the panic that `abort!` unwinds with.
+struct Abort;
+
+thread_local! {
+    static MESSAGES: RefCell<Vec<Message>> = const { RefCell::new(Vec::new()) };
+}
+
+pub fn record(level: Level, text: String) {
+    MESSAGES.with(|messages| messages.borrow_mut().push(Message { level, text }));
+}
+
+pub fn abort_now() -> ! {
+    // `resume_unwind` skips the panic hook, so aborts don't fill the test
+    // output with backtraces
+    panic::resume_unwind(Box::new(Abort))
+}
+
+/// Runs `f`, and returns what it reported along with its result, or `None`
+/// if it aborted.
+///
+/// Any other panic is passed on, as it's a bug.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (Option<T>, Vec<Message>) {
+    MESSAGES.with(|messages| messages.borrow_mut().clear());
+    let result = match panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(value) => Some(value),
+        Err(payload) if payload.is::<Abort>() => None,
+        Err(payload) => panic::resume_unwind(payload),
+    };
+    (result, MESSAGES.with(RefCell::take))
+}
+
+/// Formats a message, with its notes on the lines after it.
+macro_rules! message {
+    (
+        $format:literal $(, $arg:expr)* $(,)?
+        $(; $note:ident = $note_format:literal $(, $note_arg:expr)* $(,)?)* $(;)?
+    ) => {{
+        #[allow(unused_mut)]
+        let mut text = format!($format $(, $arg)*);
+        $(
+            text.push_str(&format!(
+                concat!("\n", stringify!($note), ": ", $note_format)
+                $(, $note_arg)*
+            ));
+        )*
+        text
+    }};
+}
+
+macro_rules! emit_error {
+    ($span:expr, $($message:tt)*) => {{
+        let _ = &$span;
+        $crate::diagnostics::record(
+            $crate::diagnostics::Level::Error,
+            $crate::diagnostics::message!($($message)*),
+        );
+    }};
+}
+
+macro_rules! emit_warning {
+    ($span:expr, $($message:tt)*) => {{
+        let _ = &$span;
+        $crate::diagnostics::record(
+            $crate::diagnostics::Level::Warning,
+            $crate::diagnostics::message!($($message)*),
+        );
+    }};
+}
+
+macro_rules! abort {
+    ($span:expr, $($message:tt)*) => {{
+        $crate::diagnostics::emit_error!($span, $($message)*);
+        $crate::diagnostics::abort_now()
+    }};
+}
+
+macro_rules! abort_call_site {
+    ($($message:tt)*) => {{
+        $crate::diagnostics::emit_error!((), $($message)*);
+        $crate::diagnostics::abort_now()
+    }};
+}
+
+pub(crate) use abort;
+pub(crate) use abort_call_site;
+pub(crate) use emit_error;
+pub(crate) use emit_warning;
+pub(crate) use message;
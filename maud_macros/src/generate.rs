@@ -1,26 +1,268 @@
-use proc_macro2::{Delimiter, Group, Ident, Literal, Span, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Group, Ident, Literal, Spacing, Span, TokenStream, TokenTree};
 use proc_macro_error::SpanRange;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use std::cell::{Cell, RefCell};
 
-use crate::{ast::*, escape};
+#[cfg(not(any(test, fuzzing)))]
+use proc_macro_error::emit_error;
 
-pub fn generate(markups: Vec<Markup>, output_ident: TokenTree) -> TokenStream {
-    let mut build = Builder::new(output_ident.clone());
-    Generator::new(output_ident).markups(markups, &mut build);
-    build.finish()
+#[cfg(any(test, fuzzing))]
+use crate::diagnostics::emit_error;
+#[cfg(feature = "gzip")]
+use crate::gzip;
+use crate::{
+    ast::*,
+    escape,
+    lint::{self, find_attr},
+};
+
+/// Generates the statements that render a template.
+///
+/// If `fallible` is set, splices use `TryRender` when they can, and break
+/// out of the block labeled [`try_label`] with the first error.
+pub fn generate(markups: Vec<Markup>, output_ident: TokenTree, fallible: bool) -> TokenStream {
+    generate_with(Generator::new(output_ident, fallible), markups)
+}
+
+/// Generates code for a template without the checks and markers that the
+/// debugging features add, so that tests see the same output whichever
+/// features are on.
+#[cfg(test)]
+pub fn generate_plain(markups: Vec<Markup>, output_ident: TokenTree) -> TokenStream {
+    let mut generator = Generator::new(output_ident, false);
+    generator.instrument = false;
+    generate_with(generator, markups)
+}
+
+/// Generates the statements that render a template for `html_gzip!`.
+///
+/// Long static parts are compressed here, and passed to `gzip_ident` (a
+/// `maud::__GzipWriter`) ready to be copied into the response. Everything
+/// else is written to `output_ident` for it to compress at runtime.
+#[cfg(feature = "gzip")]
+pub fn generate_gzip(
+    markups: Vec<Markup>,
+    output_ident: TokenTree,
+    gzip_ident: TokenTree,
+) -> TokenStream {
+    let mut generator = Generator::new(output_ident, false);
+    generator.gzip_ident = Some(gzip_ident);
+    generate_with(generator, markups)
+}
+
+/// Generates the statements that render a template for `xml!`.
+///
+/// Void elements close themselves, and `@pi` writes processing
+/// instructions. Nothing that only HTML allows, like leaving out quotes or
+/// end tags, is done.
+pub fn generate_xml(markups: Vec<Markup>, output_ident: TokenTree) -> TokenStream {
+    let mut generator = Generator::new(output_ident, false);
+    generator.xml = true;
+    generate_with(generator, markups)
+}
+
+/// Generates the statements that render a template for `html_stream!`,
+/// where each `@flush` sends the output so far to `sink_ident` (a
+/// `&maud::Sink`).
+pub fn generate_stream(
+    markups: Vec<Markup>,
+    output_ident: TokenTree,
+    sink_ident: TokenTree,
+) -> TokenStream {
+    let mut generator = Generator::new(output_ident, false);
+    generator.sink_ident = Some(sink_ident);
+    generate_with(generator, markups)
+}
+
+fn generate_with(mut generator: Generator, mut markups: Vec<Markup>) -> TokenStream {
+    // Those at the top apply to the whole template; any others are errors
+    markups.retain(|markup| match markup {
+        Markup::Minify { at_span } => {
+            if generator.xml {
+                emit_error!(*at_span, "`@minify` doesn't work in `xml!`");
+            }
+            generator.minify = true;
+            false
+        }
+        Markup::MinimalQuotes { at_span } => {
+            if generator.xml {
+                emit_error!(*at_span, "`@quotes` doesn't work in `xml!`");
+            }
+            generator.minimal_quotes = true;
+            false
+        }
+        _ => true,
+    });
+    let depth = max_depth(&markups);
+    let mut build = generator.builder();
+    generator.markups(markups, &mut build);
+    let stmts = build.finish();
+    let stmts = if generator.has_return.get() {
+        let label = return_label();
+        quote!(#label: { #stmts })
+    } else {
+        stmts
+    };
+    let stmts = if generator.limits() {
+        let frame = limit_frame();
+        let output_ident = &generator.output_ident;
+        quote!(
+            let #frame = maud::__LimitFrame::enter(#depth);
+            #stmts
+            #frame.check(#output_ident.len(), 0);
+        )
+    } else {
+        stmts
+    };
+    generator.source_comments(stmts)
+}
+
+/// How deep the elements in a template nest.
+fn max_depth(markups: &[Markup]) -> usize {
+    let mut max = 0;
+    lint::for_each_child(markups, &mut |markup| {
+        if let Markup::Element { body, .. } = markup {
+            let depth = match body {
+                ElementBody::Block { block } => 1 + max_depth(&block.markups),
+                ElementBody::Void { .. } => 1,
+            };
+            max = max.max(depth);
+        }
+    });
+    max
+}
+
+/// The `maud::__LimitFrame` that tracks a template with the `limits`
+/// feature.
+fn limit_frame() -> TokenStream {
+    quote_spanned!(Span::mixed_site()=> __maud_limits)
+}
+
+/// The label of the block that `@return` breaks out of.
+fn return_label() -> TokenStream {
+    quote_spanned!(Span::mixed_site()=> '__maud_return)
+}
+
+/// The label of the block that a failed splice in `try_html!` breaks out of.
+pub fn try_label() -> TokenStream {
+    quote_spanned!(Span::mixed_site()=> '__maud_try)
 }
 
 struct Generator {
     output_ident: TokenTree,
+    fallible: bool,
+    gzip_ident: Option<TokenTree>,
+    sink_ident: Option<TokenTree>,
+    xml: bool,
+    /// Whether the template starts with `@minify`.
+    minify: bool,
+    /// Whether the template starts with `@quotes minimal`.
+    minimal_quotes: bool,
+    /// Whether the template contains a `@return`.
+    has_return: Cell<bool>,
+    /// How many of the enclosing elements and attributes keep their
+    /// whitespace when minifying.
+    verbatim: Cell<usize>,
+    /// The names of the enclosing elements, for test IDs.
+    path: RefCell<Vec<String>>,
+    /// The enclosing elements, written like `div.card`, for panic
+    /// messages.
+    labels: RefCell<Vec<String>>,
+    /// Whether to add the test IDs, source comments, panic context and
+    /// limit checks of the features that turn them on.
+    instrument: bool,
 }
 
 impl Generator {
-    fn new(output_ident: TokenTree) -> Generator {
-        Generator { output_ident }
+    fn new(output_ident: TokenTree, fallible: bool) -> Generator {
+        Generator {
+            output_ident,
+            fallible,
+            gzip_ident: None,
+            sink_ident: None,
+            xml: false,
+            minify: false,
+            minimal_quotes: false,
+            has_return: Cell::new(false),
+            verbatim: Cell::new(0),
+            path: RefCell::new(Vec::new()),
+            labels: RefCell::new(Vec::new()),
+            instrument: true,
+        }
     }
 
     fn builder(&self) -> Builder {
-        Builder::new(self.output_ident.clone())
+        Builder::new(self.output_ident.clone(), self.gzip_ident.clone())
+    }
+
+    /// Whether to minify the output. XML keeps everything as written.
+    fn minify(&self) -> bool {
+        self.minify && !self.xml
+    }
+
+    /// With the `source-comments` feature, puts comments saying where the
+    /// template is written around its output, like
+    /// `<!-- src/views/user.rs:42 -->` and `<!-- /src/views/user.rs:42 -->`.
+    fn source_comments(&self, stmts: TokenStream) -> TokenStream {
+        // Spans only know their position while the macro is running
+        if !cfg!(feature = "source-comments")
+            || !self.instrument
+            || self.xml
+            || !proc_macro::is_available()
+        {
+            return stmts;
+        }
+        let call_site = proc_macro::Span::call_site();
+        // `--` would end the comment early
+        let location = format!("{}:{}", call_site.file(), call_site.line()).replace("--", "- -");
+        let comment = |text: String| {
+            let mut build = self.builder();
+            build.push_str(&text);
+            let comment = build.finish();
+            // Checked where the template is compiled, so that release
+            // builds of the calling crate leave it out
+            quote!(if ::core::cfg!(debug_assertions) { #comment })
+        };
+        let start = comment(format!("<!-- {} -->", location));
+        let end = comment(format!("<!-- /{} -->", location));
+        quote!(#start #stmts #end)
+    }
+
+    /// Whether to check templates against `maud::Limits`.
+    fn limits(&self) -> bool {
+        cfg!(feature = "limits") && self.instrument
+    }
+
+    /// With the `limits` feature, checks the output so far against
+    /// `maud::Limits`, and ends the template if it's gone over.
+    fn limit_check(&self) -> TokenStream {
+        if !self.limits() {
+            return TokenStream::new();
+        }
+        self.has_return.set(true);
+        let frame = limit_frame();
+        let label = return_label();
+        let output_ident = &self.output_ident;
+        let depth = self.labels.borrow().len();
+        quote!(if #frame.check(#output_ident.len(), #depth) { break #label; })
+    }
+
+    /// Sends what's been rendered so far to the sink in `html_stream!`.
+    ///
+    /// With the `limits` feature, the frame counts what was sent, as it's
+    /// no longer in the buffer.
+    fn send(&self, sink_ident: &TokenTree) -> TokenStream {
+        let output_ident = &self.output_ident;
+        let count = if self.limits() {
+            let frame = limit_frame();
+            quote!(#frame.sent(#output_ident.len());)
+        } else {
+            TokenStream::new()
+        };
+        quote!(
+            #count
+            maud::Sink::send(#sink_ident, ::core::mem::take(&mut #output_ident));
+        )
     }
 
     fn markups(&self, markups: Vec<Markup>, build: &mut Builder) {
@@ -51,15 +293,86 @@ impl Generator {
                     self.markups(markups, build);
                 }
             }
-            Markup::Literal { content, .. } => build.push_escaped(&content),
+            Markup::Literal { content, .. } => {
+                if self.minify() && self.verbatim.get() == 0 {
+                    build.push_escaped(&collapse_whitespace(&content));
+                } else {
+                    build.push_escaped(&content);
+                }
+            }
             Markup::Symbol { symbol } => self.name(symbol, build),
             Markup::Splice { expr, .. } => self.splice(expr, build),
-            Markup::Element { name, attrs, body } => self.element(name, attrs, body, build),
-            Markup::Let { tokens, .. } => build.push_tokens(tokens),
+            Markup::Element { name, attrs, body } => self.element(name, attrs, body, true, build),
+            Markup::Let { tokens, .. } | Markup::Control { tokens, .. } => {
+                build.push_tokens(tokens)
+            }
+            Markup::Return { .. } => {
+                self.has_return.set(true);
+                let label = return_label();
+                build.push_tokens(quote!(break #label;));
+            }
+            Markup::Flush { at_span } => match &self.sink_ident {
+                Some(sink_ident) => build.push_tokens(self.send(sink_ident)),
+                None => emit_error!(at_span, "`@flush` only works in `html_stream!`"),
+            },
+            Markup::Suspend {
+                at_span,
+                future,
+                body,
+            } => match &self.sink_ident {
+                Some(sink_ident) => self.suspend(sink_ident, future, body, build),
+                None => emit_error!(at_span, "`@suspend` only works in `html_stream!`"),
+            },
+            Markup::Provide { value, body, .. } => self.provide(value, body, build),
+            Markup::Portal { target, body, .. } => {
+                build.push_str("<template data-maud-portal=\"");
+                self.verbatim(|| self.markup(*target, build));
+                build.push_str("\">");
+                self.block(body, build);
+                build.push_str("</template>");
+            }
+            Markup::Minify { at_span } => {
+                emit_error!(at_span, "`@minify` only works at the top of a template")
+            }
+            Markup::MinimalQuotes { at_span } => {
+                emit_error!(at_span, "`@quotes` only works at the top of a template")
+            }
+            // Checked by `profile::apply`
+            Markup::Profile { .. } => {}
+            Markup::ProcessingInstruction {
+                at_span,
+                name,
+                attrs,
+            } => {
+                if !self.xml {
+                    emit_error!(at_span, "`@pi` only works in `xml!`");
+                }
+                build.push_str("<?");
+                self.name(name, build);
+                self.attrs(attrs, build);
+                build.push_str("?>");
+            }
             Markup::Special { segments } => {
-                for Special { head, body, .. } in segments {
-                    build.push_tokens(head);
-                    self.block(body, build);
+                if segments
+                    .iter()
+                    .any(|segment| split_let_chain(&segment.head).is_some())
+                {
+                    self.let_chain(segments, build);
+                } else {
+                    for Special { head, body, .. } in segments {
+                        // Loops are checked on each pass, as they can run
+                        // for ever without a splice
+                        let is_loop = matches!(
+                            head.clone().into_iter().next(),
+                            Some(TokenTree::Ident(keyword)) if keyword == "for" || keyword == "while"
+                        );
+                        build.push_tokens(head);
+                        if is_loop {
+                            self.block_with(self.limit_check(), body, build);
+                        } else {
+                            self.block(body, build);
+                        }
+                    }
                 }
             }
             Markup::Match {
@@ -80,11 +393,26 @@ impl Generator {
                 body.set_span(arms_span.collapse());
                 build.push_tokens(quote!(#head #body));
             }
+            Markup::Style { css, scope, .. } => {
+                build.push_str("<style data-maud-scope=\"");
+                build.push_escaped(scope.as_deref().unwrap_or_default());
+                build.push_str("\"");
+                self.splice(quote!(maud::Nonce::__attr()), build);
+                build.push_str(">");
+                build.push_str(&css);
+                build.push_str("</style>");
+            }
         }
     }
 
-    fn block(
+    fn block(&self, block: Block, build: &mut Builder) {
+        self.block_with(TokenStream::new(), block, build);
+    }
+
+    /// Generates a block that starts with `prefix`.
+    fn block_with(
         &self,
+        prefix: TokenStream,
         Block {
             markups,
             outer_span,
@@ -93,6 +421,7 @@ impl Generator {
     ) {
         let block = {
             let mut build = self.builder();
+            build.push_tokens(prefix);
             self.markups(markups, &mut build);
             build.finish()
         };
@@ -101,22 +430,241 @@ impl Generator {
         build.push_tokens(TokenStream::from(block));
     }
 
+    /// Generates an `@if` chain where some of the conditions are let chains,
+    /// like `@if let Some(x) = a && x > 1`.
+    ///
+    /// Each condition becomes a nested `if`, and a flag records whether a
+    /// branch was taken so that the following branches are skipped. This
+    /// works on every edition, unlike native let chains.
+    fn let_chain(&self, segments: Vec<Special>, build: &mut Builder) {
+        let matched = quote_spanned!(Span::mixed_site()=> __maud_matched);
+        let mut inner = self.builder();
+        inner.push_tokens(quote!(let mut #matched = false;));
+        for Special { head, body, .. } in segments {
+            let conditions = match split_let_chain(&head) {
+                Some(conditions) => conditions,
+                None => match strip_if(&head) {
+                    Some(condition) => vec![condition],
+                    // A plain `@else`
+                    None => vec![],
+                },
+            };
+            // Set the flag first, in case the body breaks out of a loop
+            let mut branch = self.builder();
+            branch.push_tokens(quote!(#matched = true;));
+            self.block(body, &mut branch);
+            let mut tokens = branch.finish();
+            for condition in conditions.into_iter().rev() {
+                tokens = quote!(if #condition { #tokens });
+            }
+            inner.push_tokens(quote!(if !#matched { #tokens }));
+        }
+        let inner = inner.finish();
+        build.push_tokens(quote!({ #inner }));
+    }
+
+    /// Generates a `@suspend` slot: the placeholder between two markers,
+    /// sent straight away. The future is only handed to the sink after
+    /// that, so that the slot is there before the future's content.
+    fn suspend(
+        &self,
+        sink_ident: &TokenTree,
+        future: TokenStream,
+        body: Block,
+        build: &mut Builder,
+    ) {
+        let output_ident = &self.output_ident;
+        let slot = quote_spanned!(Span::mixed_site()=> __maud_slot);
+        let push_slot = quote!(maud::Render::render_to(&#slot, &mut #output_ident););
+        let suspended = quote_spanned!(Span::mixed_site()=> __maud_suspended);
+        let mut inner = self.builder();
+        inner.push_tokens(quote!(
+            let #suspended = (#future);
+            let #slot = maud::Sink::slot(#sink_ident);
+        ));
+        inner.push_str("<template id=\"maud-slot-");
+        inner.push_tokens(push_slot.clone());
+        inner.push_str("\"></template>");
+        self.block(body, &mut inner);
+        inner.push_str("<!--/maud-slot-");
+        inner.push_tokens(push_slot);
+        inner.push_str("-->");
+        inner.push_tokens(self.send(sink_ident));
+        inner.push_tokens(quote!(maud::Sink::suspend(#sink_ident, #slot, #suspended);));
+        let inner = inner.finish();
+        // In a block of its own, so that nested slots don't mix up their
+        // numbers
+        build.push_tokens(quote!({ #inner }));
+    }
+
+    fn provide(&self, value: TokenStream, body: Block, build: &mut Builder) {
+        let provided = quote_spanned!(Span::mixed_site()=> __maud_provided);
+        let mut inner = self.builder();
+        inner.push_tokens(quote!(let #provided = maud::context::__Provided::new((#value));));
+        self.markups(body.markups, &mut inner);
+        let inner = inner.finish();
+        // The value is taken off the stack when the block ends, however it
+        // ends
+        build.push_tokens(quote!({ #inner }));
+    }
+
     fn splice(&self, expr: TokenStream, build: &mut Builder) {
         let output_ident = self.output_ident.clone();
-        build.push_tokens(quote!(maud::Render::render_to(&#expr, &mut #output_ident);));
+        // Parenthesize the expression, so that `&` applies to all of it
+        let span = first_span(&expr);
+        let group = parens(expr, span);
+        let render = if self.fallible {
+            let label = try_label();
+            let error = quote_spanned!(Span::mixed_site()=> __maud_error);
+            let call = quote_spanned!(span=>
+                (&maud::__TryWrap(&#group)).__maud_try_render_to(&mut #output_ident)
+            );
+            quote!(
+                if let ::core::result::Result::Err(#error) = #call {
+                    break #label ::core::result::Result::Err(#error);
+                }
+            )
+        } else {
+            let expr = quote_spanned!(span=> &#group);
+            quote!(maud::Render::render_to(#expr, &mut #output_ident);)
+        };
+        build.push_tokens(self.limit_check());
+        match self.panic_context(span) {
+            Some(context) => build.push_tokens(quote!({ #context #render })),
+            None => build.push_tokens(render),
+        }
     }
 
-    fn element(&self, name: TokenStream, attrs: Vec<Attr>, body: ElementBody, build: &mut Builder) {
+    /// With the `panic-context` feature, a guard that prints which element
+    /// and line a splice is in if it panics and unwinds.
+    fn panic_context(&self, span: Span) -> Option<TokenStream> {
+        // Spans only know their position while the macro is running
+        if !cfg!(feature = "panic-context") || !self.instrument || !proc_macro::is_available() {
+            return None;
+        }
+        let span = span.unwrap();
+        let location = format!("{}:{}", span.file(), span.line());
+        let element = match self.labels.borrow().last() {
+            Some(label) => label.clone(),
+            None => "the template".to_string(),
+        };
+        let guard = quote_spanned!(Span::mixed_site()=> __maud_panic_context);
+        Some(quote!(let #guard = maud::__PanicContext::new(#element, #location);))
+    }
+
+    fn element(
+        &self,
+        name: TokenStream,
+        attrs: Vec<Attr>,
+        body: ElementBody,
+        close: bool,
+        build: &mut Builder,
+    ) {
+        let element_name = name_to_string(name.clone()).to_ascii_lowercase();
+        let test_id = self.test_id(&element_name, &name, &attrs);
+        self.labels.borrow_mut().push(label(&element_name, &attrs));
         build.push_str("<");
         self.name(name.clone(), build);
         self.attrs(attrs, build);
-        build.push_str(">");
-        if let ElementBody::Block { block } = body {
-            self.markups(block.markups, build);
-            build.push_str("</");
-            self.name(name, build);
+        if let Some(test_id) = test_id {
+            let mut attr = self.builder();
+            attr.push_str(" data-testid=\"");
+            attr.push_escaped(&test_id);
+            attr.push_str("\"");
+            let attr = attr.finish();
+            build.push_tokens(quote!(if ::core::cfg!(debug_assertions) { #attr }));
+        }
+        if self.xml && matches!(body, ElementBody::Void { .. }) {
+            build.push_str("/>");
+        } else {
             build.push_str(">");
         }
+        if let ElementBody::Block { block } = body {
+            self.path.borrow_mut().push(element_name.clone());
+            if matches!(&*element_name, "pre" | "textarea" | "script" | "style") {
+                self.verbatim(|| self.children(&element_name, block.markups, build));
+            } else {
+                self.children(&element_name, block.markups, build);
+            }
+            self.path.borrow_mut().pop();
+            if close {
+                build.push_str("</");
+                self.name(name, build);
+                build.push_str(">");
+            }
+        }
+        self.labels.borrow_mut().pop();
+    }
+
+    /// The `data-testid` to stamp on an element with the `test-ids`
+    /// feature: the file and line it's written on, then the names of the
+    /// elements it's in, like `src/views/user.rs:42:form/button`.
+    ///
+    /// Only interactive elements get one, and only when they don't have
+    /// a `data-testid` of their own.
+    fn test_id(&self, element_name: &str, name: &TokenStream, attrs: &[Attr]) -> Option<String> {
+        if !cfg!(feature = "test-ids")
+            || !self.instrument
+            || self.xml
+            || find_attr(attrs, "data-testid").is_some()
+        {
+            return None;
+        }
+        let has_handler = attrs.iter().any(|attr| match attr {
+            Attr::Named { named_attr } => name_to_string(named_attr.name.clone()).starts_with("on"),
+            _ => false,
+        });
+        let interactive = matches!(
+            element_name,
+            "a" | "button" | "details" | "form" | "input" | "select" | "summary" | "textarea"
+        );
+        if !interactive && !has_handler {
+            return None;
+        }
+        let mut id = String::new();
+        // Spans only know their position while the macro is running
+        if proc_macro::is_available() {
+            let span = first_span(name).unwrap();
+            id = format!("{}:{}:", span.file().replace('\\', "/"), span.line());
+        }
+        for parent in self.path.borrow().iter() {
+            id.push_str(parent);
+            id.push('/');
+        }
+        id.push_str(element_name);
+        Some(id)
+    }
+
+    /// Generates the body of an element. When minifying, this leaves out the
+    /// end tags of children that HTML closes on its own.
+    fn children(&self, parent: &str, markups: Vec<Markup>, build: &mut Builder) {
+        if !self.minify() {
+            return self.markups(markups, build);
+        }
+        let mut markups = markups.into_iter().peekable();
+        while let Some(markup) = markups.next() {
+            match markup {
+                Markup::Element { name, attrs, body } => {
+                    let element_name = name_to_string(name.clone()).to_ascii_lowercase();
+                    let close = match markups.peek() {
+                        Some(Markup::Element { name: next, .. }) => {
+                            let next = name_to_string(next.clone()).to_ascii_lowercase();
+                            !end_tag_optional(&element_name, Some(&next), parent)
+                        }
+                        Some(_) => true,
+                        None => !end_tag_optional(&element_name, None, parent),
+                    };
+                    self.element(name, attrs, body, close, build);
+                }
+                markup => self.markup(markup, build),
+            }
+        }
+    }
+
+    fn verbatim(&self, f: impl FnOnce()) {
+        self.verbatim.set(self.verbatim.get() + 1);
+        f();
+        self.verbatim.set(self.verbatim.get() - 1);
     }
 
     fn name(&self, name: TokenStream, build: &mut Builder) {
@@ -124,19 +672,85 @@ impl Generator {
     }
 
     fn attrs(&self, attrs: Vec<Attr>, build: &mut Builder) {
-        for NamedAttr { name, attr_type } in desugar_attrs(attrs) {
+        let (spreads, attrs): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| matches!(attr, Attr::Spread { .. }));
+        let spreads = spreads
+            .into_iter()
+            .filter_map(|attr| match attr {
+                Attr::Spread { expr, .. } => {
+                    let span = first_span(&expr);
+                    Some(quote_spanned!(span=> &#expr))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let mut attrs = desugar_attrs(attrs);
+        // Attributes written on the element win over those in a spread,
+        // except for the classes, which are merged
+        let own_names = attrs
+            .iter()
+            .map(|named_attr| name_to_string(named_attr.name.clone()))
+            .collect::<Vec<_>>();
+        let class = attrs
+            .iter_mut()
+            .find(|named_attr| name_to_string(named_attr.name.clone()) == "class")
+            .map(|named_attr| &mut named_attr.attr_type)
+            .filter(|_| !spreads.is_empty());
+        if let Some(AttrType::Normal { value }) = class {
+            let mut markups = vec![std::mem::replace(
+                value,
+                Markup::Block(Block {
+                    markups: Vec::new(),
+                    outer_span: SpanRange::call_site(),
+                }),
+            )];
+            markups.extend(spreads.iter().map(|spread| Markup::Splice {
+                expr: quote!(maud::Attributes::__classes(#spread)),
+                outer_span: SpanRange::call_site(),
+            }));
+            *value = Markup::Block(Block {
+                markups,
+                outer_span: SpanRange::call_site(),
+            });
+        }
+        self.named_attrs(attrs, build);
+        for spread in spreads {
+            self.splice(
+                quote!(maud::Attributes::__except(#spread, &[#(#own_names),*])),
+                build,
+            );
+        }
+    }
+
+    fn named_attrs(&self, attrs: Vec<NamedAttr>, build: &mut Builder) {
+        for NamedAttr { name, attr_type } in attrs {
             match attr_type {
+                AttrType::Fields { value, .. } => {
+                    let span = first_span(&value);
+                    let value = parens(value, span);
+                    self.splice(quote_spanned!(span=> maud::data_attributes(&#value)), build);
+                }
                 AttrType::Normal { value } => {
                     build.push_str(" ");
                     self.name(name, build);
-                    build.push_str("=\"");
-                    self.markup(value, build);
-                    build.push_str("\"");
+                    match static_value(&value).filter(|value| {
+                        self.minimal_quotes
+                            && !self.xml
+                            && (value.is_ascii() || !cfg!(feature = "escape-non-ascii"))
+                    }) {
+                        Some(value) => build.push_str(&quote_minimally(&value)),
+                        None => {
+                            build.push_str("=\"");
+                            self.verbatim(|| self.markup(value, build));
+                            build.push_str("\"");
+                        }
+                    }
                 }
                 AttrType::Optional {
                     toggler: Toggler { cond, .. },
                 } => {
-                    let inner_value = quote!(inner_value);
+                    let inner_value = quote_spanned!(Span::mixed_site()=> inner_value);
                     let body = {
                         let mut build = self.builder();
                         build.push_str(" ");
@@ -148,6 +762,13 @@ impl Generator {
                     };
                     build.push_tokens(quote!(if let Some(#inner_value) = (#cond) { #body }));
                 }
+                AttrType::Empty { .. } if self.xml => {
+                    emit_error!(
+                        span_tokens(name.clone()),
+                        "attributes need a value in `xml!`";
+                        help = "give it one, like `{0}=\"{0}\"`", name_to_string(name)
+                    );
+                }
                 AttrType::Empty { toggler: None } => {
                     build.push_str(" ");
                     self.name(name, build);
@@ -189,6 +810,8 @@ fn desugar_attrs(attrs: Vec<Attr>) -> Vec<NamedAttr> {
             } => classes_static.push(name),
             Attr::Id { name, .. } => ids.push(name),
             Attr::Named { named_attr } => named_attrs.push(named_attr),
+            // Spreads are written after everything else, in `attrs`
+            Attr::Spread { .. } => {}
         }
     }
     let classes = desugar_classes_or_ids("class", classes_static, classes_toggled);
@@ -247,18 +870,251 @@ fn prepend_leading_space(name: Markup, leading_space: &mut bool) -> Vec<Markup>
     markups
 }
 
+/// Replaces each run of whitespace with a single space, which renders the
+/// same outside of `<pre>` and the like.
+fn collapse_whitespace(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut in_space = false;
+    for c in text.chars() {
+        if matches!(c, ' ' | '\t' | '\n' | '\x0C' | '\r') {
+            if !in_space {
+                output.push(' ');
+            }
+            in_space = true;
+        } else {
+            output.push(c);
+            in_space = false;
+        }
+    }
+    output
+}
+
+/// Whether HTML lets an element leave out its end tag, given the element
+/// after it (or `None` if it's the last thing in `parent`).
+///
+/// See <https://html.spec.whatwg.org/multipage/syntax.html#optional-tags>.
+fn end_tag_optional(name: &str, next: Option<&str>, parent: &str) -> bool {
+    match name {
+        "li" => matches!(next, None | Some("li")),
+        "dt" => matches!(next, Some("dt" | "dd")),
+        "dd" => matches!(next, None | Some("dt" | "dd")),
+        "option" => matches!(next, None | Some("option" | "optgroup")),
+        "optgroup" => matches!(next, None | Some("optgroup")),
+        "thead" => matches!(next, Some("tbody" | "tfoot")),
+        "tbody" => matches!(next, None | Some("tbody" | "tfoot")),
+        "tfoot" => next.is_none(),
+        "tr" => matches!(next, None | Some("tr")),
+        "td" | "th" => matches!(next, None | Some("td" | "th")),
+        "p" => match next {
+            Some(next) => matches!(
+                next,
+                "address"
+                    | "article"
+                    | "aside"
+                    | "blockquote"
+                    | "details"
+                    | "div"
+                    | "dl"
+                    | "fieldset"
+                    | "figcaption"
+                    | "figure"
+                    | "footer"
+                    | "form"
+                    | "h1"
+                    | "h2"
+                    | "h3"
+                    | "h4"
+                    | "h5"
+                    | "h6"
+                    | "header"
+                    | "hgroup"
+                    | "hr"
+                    | "main"
+                    | "menu"
+                    | "nav"
+                    | "ol"
+                    | "p"
+                    | "pre"
+                    | "section"
+                    | "table"
+                    | "ul"
+            ),
+            // The parent's content model might not allow a `p` to end
+            // there, and a custom element's is unknown
+            None => {
+                !(matches!(
+                    parent,
+                    "a" | "audio" | "del" | "ins" | "map" | "noscript" | "video"
+                ) || parent.contains('-'))
+            }
+        },
+        _ => false,
+    }
+}
+
+/// The text of an attribute value made only of literals.
+fn static_value(value: &Markup) -> Option<String> {
+    match value {
+        Markup::Literal { content, .. } => Some(content.clone()),
+        Markup::Symbol { symbol } => Some(name_to_string(symbol.clone())),
+        Markup::Block(block) => block.markups.iter().map(static_value).collect(),
+        _ => None,
+    }
+}
+
+/// Writes `=value` with as few quotes as HTML allows: none if the value has
+/// no spaces or quote-like characters, single quotes if it has a double quote
+/// but no single one, and double quotes otherwise.
+fn quote_minimally(value: &str) -> String {
+    let mut output = String::from("=");
+    let unquoted = !value.is_empty()
+        && !value.contains(|c| {
+            matches!(
+                c,
+                '\t' | '\n' | '\x0C' | '\r' | ' ' | '"' | '\'' | '=' | '<' | '>' | '`'
+            )
+        });
+    if unquoted {
+        escape::escape_to_string(value, &mut output);
+    } else if value.contains('"') && !value.contains('\'') {
+        output.push('\'');
+        for (i, part) in value.split('"').enumerate() {
+            if i > 0 {
+                output.push('"');
+            }
+            escape::escape_to_string(part, &mut output);
+        }
+        output.push('\'');
+    } else {
+        output.push('"');
+        escape::escape_to_string(value, &mut output);
+        output.push('"');
+    }
+    output
+}
+
+////////////////////////////////////////////////////////
+
+/// Wraps the tokens in parentheses with the given span, so that errors in
+/// the generated code point back at the template.
+fn parens(tokens: TokenStream, span: Span) -> TokenTree {
+    let mut group = Group::new(Delimiter::Parenthesis, tokens);
+    group.set_span(span);
+    TokenTree::Group(group)
+}
+
+/// Writes an element like a CSS selector, with its static classes and ID,
+/// as in `div.card#intro`.
+fn label(element_name: &str, attrs: &[Attr]) -> String {
+    let mut label = element_name.to_string();
+    for attr in attrs {
+        match attr {
+            Attr::Class {
+                name,
+                toggler: None,
+                ..
+            } => {
+                if let Some(name) = lint::static_value(name) {
+                    label.push('.');
+                    label.push_str(&name);
+                }
+            }
+            Attr::Id { name, .. } => {
+                if let Some(name) = lint::static_value(name) {
+                    label.push('#');
+                    label.push_str(&name);
+                }
+            }
+            _ => {}
+        }
+    }
+    label
+}
+
+/// The span of the first token, or the call site if there are no tokens.
+fn first_span(tokens: &TokenStream) -> Span {
+    tokens
+        .clone()
+        .into_iter()
+        .next()
+        .map_or_else(Span::call_site, |token| token.span())
+}
+
 ////////////////////////////////////////////////////////
 
+/// Returns the condition of an `if` or `else if` head, without the
+/// keywords.
+fn strip_if(head: &TokenStream) -> Option<TokenStream> {
+    let mut tokens = head.clone().into_iter().peekable();
+    if matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident == "else") {
+        tokens.next();
+    }
+    match tokens.next() {
+        Some(TokenTree::Ident(ident)) if ident == "if" => Some(tokens.collect()),
+        _ => None,
+    }
+}
+
+/// Splits the condition of an `if` head into the parts of a let chain, e.g.
+/// `if let Some(x) = a && x > 1` into `let Some(x) = a` and `x > 1`.
+///
+/// Returns `None` if the condition isn't a let chain.
+fn split_let_chain(head: &TokenStream) -> Option<Vec<TokenStream>> {
+    let tokens = strip_if(head)?.into_iter().collect::<Vec<_>>();
+    let mut conditions = Vec::new();
+    let mut current = Vec::new();
+    // Whether the current part is a `let` whose `=` we've seen. Patterns can
+    // contain `&&`, so it only ends the part after that point.
+    let mut is_let = false;
+    let mut after_eq = false;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = &tokens[i];
+        let next = tokens.get(i + 1);
+        match token {
+            TokenTree::Ident(ident) if ident == "let" && current.is_empty() => is_let = true,
+            TokenTree::Punct(punct)
+                if punct.as_char() == '=' && is_plain_eq(current.last(), punct, next) =>
+            {
+                after_eq = true;
+            }
+            TokenTree::Punct(punct)
+                if punct.as_char() == '&'
+                    && punct.spacing() == Spacing::Joint
+                    && matches!(next, Some(TokenTree::Punct(next)) if next.as_char() == '&')
+                    && (!is_let || after_eq) =>
+            {
+                conditions.push((is_let, current.drain(..).collect::<TokenStream>()));
+                is_let = false;
+                after_eq = false;
+                i += 2;
+                continue;
+            }
+            _ => {}
+        }
+        current.push(token.clone());
+        i += 1;
+    }
+    conditions.push((is_let, current.into_iter().collect()));
+    if conditions.len() > 1 && conditions.iter().any(|&(is_let, _)| is_let) {
+        Some(conditions.into_iter().map(|(_, tokens)| tokens).collect())
+    } else {
+        None
+    }
+}
+
 struct Builder {
     output_ident: TokenTree,
+    gzip_ident: Option<TokenTree>,
     tokens: Vec<TokenTree>,
     tail: String,
 }
 
 impl Builder {
-    fn new(output_ident: TokenTree) -> Builder {
+    fn new(output_ident: TokenTree, gzip_ident: Option<TokenTree>) -> Builder {
         Builder {
             output_ident,
+            gzip_ident,
             tokens: Vec::new(),
             tail: String::new(),
         }
@@ -269,7 +1125,15 @@ impl Builder {
     }
 
     fn push_escaped(&mut self, string: &str) {
-        escape::escape_to_string(string, &mut self.tail);
+        // `maud::NonAscii` can change how these are written, which is only
+        // known when the template is rendered
+        if cfg!(feature = "escape-non-ascii") && !string.is_ascii() {
+            let output_ident = self.output_ident.clone();
+            let string = Literal::string(string);
+            self.push_tokens(quote!(maud::Render::render_to(#string, &mut #output_ident);));
+        } else {
+            escape::escape_to_string(string, &mut self.tail);
+        }
     }
 
     fn push_tokens(&mut self, tokens: TokenStream) {
@@ -281,10 +1145,18 @@ impl Builder {
         if self.tail.is_empty() {
             return;
         }
-        let push_str_expr = {
-            let output_ident = self.output_ident.clone();
-            let string = TokenTree::Literal(Literal::string(&self.tail));
-            quote!(#output_ident.push_str(#string);)
+        let output_ident = self.output_ident.clone();
+        let push_str_expr = match &self.gzip_ident {
+            #[cfg(feature = "gzip")]
+            Some(gzip_ident) if self.tail.len() >= gzip::MIN_LEN => {
+                let gzip::Segment { deflated, crc, len } = gzip::compress(&self.tail);
+                let deflated = Literal::byte_string(&deflated);
+                quote!(#gzip_ident.push_static(&mut #output_ident, #deflated, #crc, #len);)
+            }
+            _ => {
+                let string = TokenTree::Literal(Literal::string(&self.tail));
+                quote!(#output_ident.push_str(#string);)
+            }
         };
         self.tail.clear();
         self.tokens.extend(push_str_expr);
@@ -295,3 +1167,103 @@ impl Builder {
         self.tokens.into_iter().collect()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use proc_macro2::{Ident, Span, TokenStream, TokenTree};
+
+    use super::{collapse_whitespace, end_tag_optional, generate_plain, quote_minimally};
+    use crate::parse::parse;
+
+    fn expand(input: &str) -> TokenStream {
+        let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+        generate_plain(parse(input.parse().unwrap()), output_ident)
+    }
+
+    /// The line and column of every token in `tokens` with the given text.
+    fn positions(tokens: TokenStream, text: &str) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        for token in tokens {
+            if let TokenTree::Group(group) = &token {
+                result.extend(positions(group.stream(), text));
+            } else if token.to_string() == text {
+                let start = token.span().start();
+                result.push((start.line, start.column));
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn splices() {
+        let tokens = expand("p { (user.name) }\np title=(user.bio) {}");
+        assert_eq!(positions(tokens.clone(), "user"), [(1, 5), (2, 9)]);
+        assert_eq!(positions(tokens, "bio"), [(2, 14)]);
+    }
+
+    #[test]
+    fn let_bindings() {
+        let tokens = expand("@let name = user.name;\n(name)");
+        assert_eq!(positions(tokens.clone(), "let"), [(1, 1)]);
+        assert_eq!(positions(tokens, "name"), [(1, 5), (1, 17), (2, 1)]);
+    }
+
+    #[test]
+    fn match_arms() {
+        let tokens =
+            expand("@match pony {\n    Pony::Rarity => \"Rarity\",\n    other => (other),\n}");
+        assert_eq!(positions(tokens.clone(), "pony"), [(1, 7)]);
+        assert_eq!(positions(tokens.clone(), "Rarity"), [(2, 10)]);
+        assert_eq!(positions(tokens, "other"), [(3, 4), (3, 14)]);
+    }
+
+    #[test]
+    fn control_flow() {
+        let tokens = expand("@for item in items {\n    @if let Some(x) = item && x > 1 { (x) }\n}");
+        assert_eq!(positions(tokens.clone(), "item"), [(1, 5), (2, 22)]);
+        assert_eq!(positions(tokens, "x"), [(2, 17), (2, 30), (2, 39)]);
+    }
+
+    #[test]
+    fn toggles() {
+        let tokens = expand("input checked[is_checked] .active[is_active] value=[maybe];");
+        assert_eq!(positions(tokens.clone(), "is_checked"), [(1, 14)]);
+        assert_eq!(positions(tokens.clone(), "is_active"), [(1, 34)]);
+        assert_eq!(positions(tokens, "maybe"), [(1, 52)]);
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(
+            collapse_whitespace("\n    Rocks,\n\t these  "),
+            " Rocks, these "
+        );
+        assert_eq!(collapse_whitespace("plain"), "plain");
+    }
+
+    #[test]
+    fn optional_end_tags() {
+        assert!(end_tag_optional("li", Some("li"), "ul"));
+        assert!(end_tag_optional("li", None, "ul"));
+        assert!(!end_tag_optional("li", Some("p"), "ul"));
+        assert!(!end_tag_optional("dt", None, "dl"));
+        assert!(end_tag_optional("p", Some("div"), "body"));
+        assert!(!end_tag_optional("p", Some("span"), "body"));
+        assert!(end_tag_optional("p", None, "div"));
+        assert!(!end_tag_optional("p", None, "a"));
+        assert!(!end_tag_optional("p", None, "my-card"));
+        assert!(!end_tag_optional("div", None, "body"));
+    }
+
+    #[test]
+    fn minimal_quotes() {
+        assert_eq!(quote_minimally("main"), "=main");
+        assert_eq!(quote_minimally("/a/b?c&d"), "=/a/b?c&amp;d");
+        assert_eq!(quote_minimally(""), "=\"\"");
+        assert_eq!(quote_minimally("two words"), "=\"two words\"");
+        assert_eq!(quote_minimally("a=b"), "=\"a=b\"");
+        assert_eq!(quote_minimally(r#"say "hi""#), r#"='say "hi"'"#);
+        assert_eq!(quote_minimally(r#"it's "hi""#), r#"="it's &quot;hi&quot;""#);
+        assert_eq!(quote_minimally("it's"), r#"="it's""#);
+    }
+}
@@ -0,0 +1,38 @@
+//! Compresses the static parts of `html_gzip!` templates at compile time.
+
+use miniz_oxide::deflate::core::{
+    compress_to_output, create_comp_flags_from_zip_params, CompressorOxide, TDEFLFlush,
+};
+
+/// Statics shorter than this are left for the runtime to compress along
+/// with the splices around them, as a segment of their own would cost more
+/// than it saves.
+pub const MIN_LEN: usize = 64;
+
+/// A compressed static segment, with what the gzip trailer needs to know
+/// about it.
+pub struct Segment {
+    pub deflated: Vec<u8>,
+    pub crc: u32,
+    pub len: u64,
+}
+
+/// Compresses `text` into raw deflate blocks that end on a byte boundary
+/// and refer to nothing before them, so they can be placed between any
+/// other such blocks.
+pub fn compress(text: &str) -> Segment {
+    // Negative window bits leave out the zlib header
+    let mut compressor = CompressorOxide::new(create_comp_flags_from_zip_params(9, -15, 0));
+    let mut deflated = Vec::new();
+    compress_to_output(&mut compressor, text.as_bytes(), TDEFLFlush::Sync, |out| {
+        deflated.extend_from_slice(out);
+        true
+    });
+    let mut crc = crc32fast::Hasher::new();
+    crc.update(text.as_bytes());
+    Segment {
+        deflated,
+        crc: crc.finalize(),
+        len: text.len() as u64,
+    }
+}
@@ -0,0 +1,10 @@
+/// A small, stable hash, so that generated names don't change between
+/// builds.
+pub fn fnv1a(bytes: &[u8]) -> u32 {
+    let mut hash = 0x811c_9dc5_u32;
+    for &byte in bytes {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    hash
+}
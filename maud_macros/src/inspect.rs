@@ -0,0 +1,352 @@
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    mem,
+    path::Path,
+};
+
+/// The environment variable that selects a template to inspect, as
+/// `FILE:LINE`.
+const LOCATION_VAR: &str = "MAUD_EXPAND";
+
+/// The environment variable that names the file to append the generated code
+/// to. If unset, it's printed to standard error instead.
+const OUTPUT_VAR: &str = "MAUD_EXPAND_OUT";
+
+/// Writes out the code generated for the current template, if its
+/// invocation covers the location in `MAUD_EXPAND`.
+///
+/// This is the compiler half of `cargo maud-expand`.
+pub fn dump(output: &TokenStream) {
+    let location = match env::var(LOCATION_VAR) {
+        Ok(location) => location,
+        Err(_) => return,
+    };
+    let call_site = proc_macro::Span::call_site();
+    let file = match call_site.local_file() {
+        Some(file) => file,
+        None => return,
+    };
+    if !covers(&location, &file, call_site.line(), call_site.end().line()) {
+        return;
+    }
+    let listing = format!(
+        "// {}:{}\n{}",
+        file.display(),
+        call_site.line(),
+        annotate(output),
+    );
+    match env::var_os(OUTPUT_VAR) {
+        Some(path) => {
+            // Failing to write is not a reason to fail the build
+            if let Ok(mut out) = OpenOptions::new().create(true).append(true).open(path) {
+                let _ = out.write_all(listing.as_bytes());
+            }
+        }
+        None => eprint!("{}", listing),
+    }
+}
+
+/// Whether `location` (as `FILE:LINE`) falls within the given lines of
+/// `file`.
+fn covers(location: &str, file: &Path, start_line: usize, end_line: usize) -> bool {
+    let (path, line) = match location.rsplit_once(':') {
+        Some((path, line)) => match line.parse::<usize>() {
+            Ok(line) => (Path::new(path), line),
+            Err(_) => return false,
+        },
+        None => return false,
+    };
+    if !(start_line..=end_line).contains(&line) {
+        return false;
+    }
+    match (fs::canonicalize(path), fs::canonicalize(file)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => path == file,
+    }
+}
+
+/// Prints generated code one statement per line, with a comment marking
+/// each static write and each dynamic splice, and a summary at the end.
+pub fn annotate(tokens: &TokenStream) -> String {
+    let mut printer = Printer::default();
+    printer.stmts(tokens.clone(), 0);
+    let Printer {
+        mut out,
+        static_writes,
+        static_bytes,
+        splices,
+    } = printer;
+    out.push_str(&format!(
+        "// {} static {} ({} {}), {} dynamic {}\n",
+        static_writes,
+        plural(static_writes, "write", "writes"),
+        static_bytes,
+        plural(static_bytes, "byte", "bytes"),
+        splices,
+        plural(splices, "splice", "splices"),
+    ));
+    out
+}
+
+fn plural<'a>(n: usize, one: &'a str, many: &'a str) -> &'a str {
+    if n == 1 {
+        one
+    } else {
+        many
+    }
+}
+
+#[derive(Default)]
+struct Printer {
+    out: String,
+    static_writes: usize,
+    static_bytes: usize,
+    splices: usize,
+}
+
+impl Printer {
+    /// Prints a sequence of statements.
+    ///
+    /// A statement ends at a `;`, or at a brace-delimited block that isn't
+    /// followed by `else`.
+    fn stmts(&mut self, tokens: TokenStream, indent: usize) {
+        let mut line = Vec::new();
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match token {
+                TokenTree::Punct(ref punct) if punct.as_char() == ';' => {
+                    line.push(token);
+                    self.line(&line, indent);
+                    line.clear();
+                }
+                TokenTree::Group(ref group) if group.delimiter() == Delimiter::Brace => {
+                    let head = mem::take(&mut line);
+                    self.annotation(&head, indent);
+                    self.indent(indent);
+                    self.out.push_str(&join(&head));
+                    if !head.is_empty() {
+                        self.out.push(' ');
+                    }
+                    self.out.push_str("{\n");
+                    self.stmts(group.stream(), indent + 1);
+                    self.indent(indent);
+                    self.out.push('}');
+                    match tokens.peek() {
+                        Some(TokenTree::Ident(ident)) if ident == "else" => {
+                            // Keep `} else` on one line by printing the rest
+                            // of the chain as a statement of its own, without
+                            // annotation or indent
+                            let mut rest = Vec::new();
+                            while let Some(token) = tokens.next() {
+                                let is_block = matches!(
+                                    &token,
+                                    TokenTree::Group(group) if group.delimiter() == Delimiter::Brace
+                                );
+                                rest.push(token);
+                                if is_block
+                                    && !matches!(tokens.peek(), Some(TokenTree::Ident(ident)) if ident == "else")
+                                {
+                                    break;
+                                }
+                            }
+                            self.chain(rest, indent);
+                        }
+                        _ => self.out.push('\n'),
+                    }
+                }
+                _ => line.push(token),
+            }
+        }
+        if !line.is_empty() {
+            self.line(&line, indent);
+        }
+    }
+
+    /// Prints the `else` branches of an `if` chain, continuing the current
+    /// line.
+    fn chain(&mut self, tokens: Vec<TokenTree>, indent: usize) {
+        let mut head = Vec::new();
+        for token in tokens {
+            match token {
+                TokenTree::Group(ref group) if group.delimiter() == Delimiter::Brace => {
+                    self.out.push_str(&join(&head));
+                    self.out.push_str(" {\n");
+                    self.stmts(group.stream(), indent + 1);
+                    self.indent(indent);
+                    self.out.push('}');
+                    head.clear();
+                }
+                _ => {
+                    if head.is_empty() {
+                        self.out.push(' ');
+                    }
+                    head.push(token);
+                }
+            }
+        }
+        self.out.push('\n');
+    }
+
+    fn line(&mut self, tokens: &[TokenTree], indent: usize) {
+        self.annotation(tokens, indent);
+        self.indent(indent);
+        self.out.push_str(&join(tokens));
+        self.out.push('\n');
+    }
+
+    /// Adds a comment above a write to the output, saying whether it's
+    /// static or dynamic.
+    fn annotation(&mut self, tokens: &[TokenTree], indent: usize) {
+        let comment = if let Some(bytes) = static_write(tokens) {
+            self.static_writes += 1;
+            self.static_bytes += bytes;
+            format!("// static: {} {}", bytes, plural(bytes, "byte", "bytes"))
+        } else if is_splice(tokens) {
+            self.splices += 1;
+            "// dynamic".to_string()
+        } else {
+            return;
+        };
+        self.indent(indent);
+        self.out.push_str(&comment);
+        self.out.push('\n');
+    }
+
+    fn indent(&mut self, indent: usize) {
+        for _ in 0..indent {
+            self.out.push_str("    ");
+        }
+    }
+}
+
+/// If these tokens are `output.push_str("...");`, the length of the string.
+fn static_write(tokens: &[TokenTree]) -> Option<usize> {
+    match tokens {
+        [TokenTree::Ident(_), TokenTree::Punct(dot), TokenTree::Ident(method), TokenTree::Group(args), TokenTree::Punct(_)]
+            if dot.as_char() == '.' && method == "push_str" =>
+        {
+            let literal = syn::parse2::<syn::LitStr>(args.stream()).ok()?;
+            Some(literal.value().len())
+        }
+        _ => None,
+    }
+}
+
+/// Whether these tokens render a splice.
+fn is_splice(tokens: &[TokenTree]) -> bool {
+    tokens.iter().any(|token| match token {
+        TokenTree::Ident(ident) => ident == "render_to" || ident == "__maud_try_render_to",
+        _ => false,
+    })
+}
+
+/// Joins tokens with spaces, except where rustfmt wouldn't put them.
+fn join(tokens: &[TokenTree]) -> String {
+    let mut out = String::new();
+    let mut space = false;
+    for (i, token) in tokens.iter().enumerate() {
+        let text = match token {
+            TokenTree::Group(group) => {
+                let inner = join(&group.stream().into_iter().collect::<Vec<_>>());
+                match group.delimiter() {
+                    Delimiter::Parenthesis => format!("({})", inner),
+                    Delimiter::Bracket => format!("[{}]", inner),
+                    Delimiter::Brace => format!("{{ {} }}", inner),
+                    Delimiter::None => inner,
+                }
+            }
+            _ => token.to_string(),
+        };
+        let tight_before = match token {
+            TokenTree::Punct(punct) => matches!(punct.as_char(), '.' | ',' | ';' | ':' | '?'),
+            // Calls and indexing
+            TokenTree::Group(group) if group.delimiter() != Delimiter::Brace => {
+                match i.checked_sub(1).map(|i| &tokens[i]) {
+                    Some(TokenTree::Ident(ident)) => !is_keyword(&ident.to_string()),
+                    Some(TokenTree::Punct(punct)) => punct.as_char() == '!',
+                    _ => false,
+                }
+            }
+            _ => false,
+        };
+        if i > 0 && space && !tight_before {
+            out.push(' ');
+        }
+        out.push_str(&text);
+        space = match token {
+            TokenTree::Punct(punct) => {
+                punct.spacing() == Spacing::Alone
+                    && !matches!(punct.as_char(), '.' | '&' | '!' | '#')
+                    && !(punct.as_char() == ':' && is_path_separator(tokens, i))
+            }
+            _ => true,
+        };
+    }
+    out
+}
+
+/// Whether the `:` at `i` is the second half of a `::`.
+fn is_path_separator(tokens: &[TokenTree], i: usize) -> bool {
+    matches!(
+        i.checked_sub(1).map(|i| &tokens[i]),
+        Some(TokenTree::Punct(punct)) if punct.as_char() == ':' && punct.spacing() == Spacing::Joint
+    )
+}
+
+fn is_keyword(ident: &str) -> bool {
+    matches!(
+        ident,
+        "if" | "in" | "match" | "while" | "return" | "break" | "mut" | "let"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use proc_macro2::{Ident, Span, TokenTree};
+
+    use super::annotate;
+    use crate::{generate::generate_plain, parse::parse};
+
+    fn inspect(input: &str) -> String {
+        let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::call_site()));
+        annotate(&generate_plain(parse(input.parse().unwrap()), output_ident))
+    }
+
+    #[test]
+    fn static_and_dynamic() {
+        assert_eq!(
+            inspect(r#"p.greeting { "Hello, " (name) "!" }"#),
+            r#"// static: 27 bytes
+__maud_output.push_str("<p class=\"greeting\">Hello, ");
+// dynamic
+maud::Render::render_to(&(name), &mut __maud_output);
+// static: 5 bytes
+__maud_output.push_str("!</p>");
+// 2 static writes (32 bytes), 1 dynamic splice
+"#
+        );
+    }
+
+    #[test]
+    fn control_structures() {
+        assert_eq!(
+            inspect(r#"@if ok { "yes" } @else { "no" } @for x in xs { (x) }"#),
+            r#"if ok {
+    // static: 3 bytes
+    __maud_output.push_str("yes");
+} else {
+    // static: 2 bytes
+    __maud_output.push_str("no");
+}
+for x in xs {
+    // dynamic
+    maud::Render::render_to(&(x), &mut __maud_output);
+}
+// 2 static writes (5 bytes), 1 dynamic splice
+"#
+        );
+    }
+}
@@ -5,13 +5,28 @@
 
 extern crate proc_macro;
 
+mod asset;
 mod ast;
+#[cfg(any(test, fuzzing))]
+mod diagnostics;
 mod escape;
 mod generate;
+#[cfg(feature = "gzip")]
+mod gzip;
+mod hash;
+mod inspect;
+mod lint;
 mod parse;
+#[cfg(any(test, fuzzing))]
+mod pretty;
+mod profile;
+mod routes;
+mod scoped;
+mod suggest;
+mod tailwind;
 
 use proc_macro2::{Ident, Span, TokenStream, TokenTree};
-use proc_macro_error::proc_macro_error;
+use proc_macro_error::{abort_call_site, proc_macro_error};
 use quote::quote;
 
 #[proc_macro]
@@ -20,18 +35,236 @@ pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     expand(input.into()).into()
 }
 
+/// Like `html!`, but splices can fail.
+///
+/// Splices of types that implement `maud::TryRender` stop rendering at the
+/// first error, and the macro evaluates to a `Result<Markup, E>`. Errors are
+/// converted with `From`, so `E` is usually inferred from the return type of
+/// the enclosing function.
+#[proc_macro]
+#[proc_macro_error]
+pub fn try_html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_try(input.into()).into()
+}
+
+/// Like `html!`, but renders straight to a gzip-compressed `maud::Gzipped`.
+///
+/// Static parts of the template are compressed when it's compiled, so only
+/// the splices are compressed at runtime. Check that the client sends
+/// `Accept-Encoding: gzip` before using it.
+#[cfg(feature = "gzip")]
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_gzip(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_gzip(input.into()).into()
+}
+
+/// Like `html!`, but sends the output to a `maud::Sink` in chunks.
+///
+/// The first argument is the sink, and the template follows after a comma.
+/// Each `@flush` sends what's been rendered so far, and the rest is sent at
+/// the end. A `@suspend(future) { placeholder }` sends the placeholder, and
+/// fills it in once the future resolves. See `maud::stream` for turning the
+/// chunks into a response.
+///
+/// ```rust,ignore
+/// maud::stream(|sink| async move {
+///     maud::html_stream!(sink,
+///         head { title { "Dashboard" } }
+///         @flush;
+///         body { (load_dashboard().await) }
+///     )
+/// })
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_stream(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_stream(input.into()).into()
+}
+
+/// Like `html!`, but for XML documents like feeds and sitemaps.
+///
+/// Void elements are written as `<name/>`, and `@pi name attrs;` writes a
+/// processing instruction, like `<?xml version="1.0"?>`. Every attribute
+/// needs a value.
+#[proc_macro]
+#[proc_macro_error]
+pub fn xml(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_xml(input.into()).into()
+}
+
+/// Returns the URL of a static asset, with a hash of its contents appended
+/// for cache busting.
+///
+/// The path is relative to the crate's `static` directory, which can be
+/// changed with the `MAUD_ASSET_DIR` environment variable, and can't use
+/// `.` or `..`. URLs start with `/static`, or the value of `MAUD_ASSET_URL`.
+///
+/// ```rust,ignore
+/// html! {
+///     link rel="stylesheet" href=(maud::asset!("app.css"));
+/// }
+/// ```
+///
+/// This renders `href="/static/app.css?v=..."`, where the query string
+/// changes whenever the file does. There's no separate manifest: each URL is
+/// worked out when the template is compiled, and worked out again when the
+/// file or either variable changes.
+#[proc_macro]
+#[proc_macro_error]
+pub fn asset(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    asset::expand(input.into()).into()
+}
+
+/// Declares typed constructors for the paths of an app's routes.
+///
+/// Each route becomes a function that returns a `maud::Route`. Parameters
+/// are percent-encoded into the path, and every `{name}` in the path must
+/// match a parameter, so links can't drift out of sync with the routes they
+/// point to.
+///
+/// ```rust,ignore
+/// maud::routes! {
+///     pub home => "/";
+///     pub user_profile(id: u32) => "/users/{id}";
+/// }
+///
+/// html! {
+///     a href=(user_profile(42)) { "Profile" }
+/// }
+/// ```
+#[proc_macro]
+#[proc_macro_error]
+pub fn routes(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    routes::expand(input.into()).into()
+}
+
 fn expand(input: TokenStream) -> TokenStream {
     let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
     // Heuristic: the size of the resulting markup tends to correlate with the
     // code size of the template itself
     let size_hint = input.to_string().len();
-    let markups = parse::parse(input);
-    let stmts = generate::generate(markups, output_ident.clone());
-    quote!({
+    let (markups, tracking) = parse_template(input);
+    let stmts = generate::generate(markups, output_ident.clone(), false);
+    let output = quote!({
         extern crate alloc;
         extern crate maud;
+        #tracking
         let mut #output_ident = alloc::string::String::with_capacity(#size_hint);
         #stmts
         maud::PreEscaped(#output_ident)
-    })
+    });
+    inspect::dump(&output);
+    output
+}
+
+fn expand_try(input: TokenStream) -> TokenStream {
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let size_hint = input.to_string().len();
+    let (markups, tracking) = parse_template(input);
+    let stmts = generate::generate(markups, output_ident.clone(), true);
+    let label = generate::try_label();
+    let output = quote!(#label: {
+        extern crate alloc;
+        extern crate maud;
+        #tracking
+        #[allow(unused_imports)]
+        use maud::{__RenderKind as _, __TryRenderKind as _};
+        let mut #output_ident = alloc::string::String::with_capacity(#size_hint);
+        #stmts
+        ::core::result::Result::Ok(maud::PreEscaped(#output_ident))
+    });
+    inspect::dump(&output);
+    output
+}
+
+#[cfg(feature = "gzip")]
+fn expand_gzip(input: TokenStream) -> TokenStream {
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let gzip_ident = TokenTree::Ident(Ident::new("__maud_gzip", Span::mixed_site()));
+    let (markups, tracking) = parse_template(input);
+    let stmts = generate::generate_gzip(markups, output_ident.clone(), gzip_ident.clone());
+    let output = quote!({
+        extern crate alloc;
+        extern crate maud;
+        #tracking
+        let mut #gzip_ident = maud::__GzipWriter::new();
+        let mut #output_ident = alloc::string::String::new();
+        #stmts
+        #gzip_ident.finish(&mut #output_ident)
+    });
+    inspect::dump(&output);
+    output
+}
+
+fn expand_xml(input: TokenStream) -> TokenStream {
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let size_hint = input.to_string().len();
+    // The HTML lints don't apply here
+    let mut markups = parse::parse_xml(input);
+    scoped::apply(&mut markups);
+    let stmts = generate::generate_xml(markups, output_ident.clone());
+    // Splices are escaped by the same code as in `html!`, so tell it not to
+    // use HTML's named references
+    let non_ascii = if cfg!(feature = "escape-non-ascii") {
+        let guard = Ident::new("__maud_non_ascii", Span::mixed_site());
+        quote!(let #guard = maud::__XmlNonAscii::enter();)
+    } else {
+        TokenStream::new()
+    };
+    let output = quote!({
+        extern crate alloc;
+        extern crate maud;
+        #non_ascii
+        let mut #output_ident = alloc::string::String::with_capacity(#size_hint);
+        #stmts
+        maud::PreEscaped(#output_ident)
+    });
+    inspect::dump(&output);
+    output
+}
+
+fn expand_stream(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let sink = tokens
+        .by_ref()
+        .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+        .collect::<TokenStream>();
+    if sink.is_empty() {
+        abort_call_site!("expected a sink, then a comma, then the template");
+    }
+    let input = tokens.collect::<TokenStream>();
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let sink_ident = TokenTree::Ident(Ident::new("__maud_sink", Span::mixed_site()));
+    let size_hint = input.to_string().len();
+    let (markups, tracking) = parse_template(input);
+    let stmts = generate::generate_stream(markups, output_ident.clone(), sink_ident.clone());
+    let output = quote!({
+        extern crate alloc;
+        extern crate maud;
+        #tracking
+        let #sink_ident: &maud::Sink = &(#sink);
+        let mut #output_ident = alloc::string::String::with_capacity(#size_hint);
+        #stmts
+        maud::Sink::send(#sink_ident, #output_ident);
+    });
+    inspect::dump(&output);
+    output
+}
+
+/// Parses and checks a template, returning it along with any items that
+/// track the files it was checked against.
+fn parse_template(input: TokenStream) -> (Vec<ast::Markup>, TokenStream) {
+    let mut markups = parse::parse(input);
+    scoped::apply(&mut markups);
+    profile::apply(&markups);
+    if cfg!(feature = "a11y") {
+        lint::lint(&markups);
+    }
+    let tracking = if cfg!(feature = "tailwind") {
+        tailwind::check(&markups)
+    } else {
+        TokenStream::new()
+    };
+    (markups, tracking)
 }
@@ -0,0 +1,307 @@
+use proc_macro_error::{emit_warning, SpanRange};
+use std::collections::HashSet;
+
+use crate::ast::*;
+
+/// A problem found by the lint pass.
+#[derive(Debug)]
+pub struct Lint {
+    pub span: SpanRange,
+    pub message: String,
+    pub help: &'static str,
+}
+
+/// Runs the accessibility lints over the given markups, emitting a warning
+/// for each problem found.
+pub fn lint(markups: &[Markup]) {
+    for Lint {
+        span,
+        message,
+        help,
+    } in check(markups)
+    {
+        emit_warning!(span, message; help = help);
+    }
+}
+
+/// Runs the accessibility lints over the given markups.
+pub fn check(markups: &[Markup]) -> Vec<Lint> {
+    let mut labels = Labels::default();
+    labels.collect(markups);
+    let mut checker = Checker {
+        labels,
+        inside_label: false,
+        lints: Vec::new(),
+    };
+    checker.markups(markups);
+    checker.lints
+}
+
+/// The `for` attributes of every `label` in the template.
+#[derive(Default)]
+struct Labels {
+    targets: HashSet<String>,
+    /// Set if any `label` has a `for` attribute we can't evaluate at compile
+    /// time. In that case, we can't rule out any control being labeled.
+    has_dynamic_target: bool,
+}
+
+impl Labels {
+    fn collect(&mut self, markups: &[Markup]) {
+        for_each_child(markups, &mut |markup| {
+            if let Markup::Element { name, attrs, body } = markup {
+                if name_to_string(name.clone()) == "label" {
+                    match find_attr(attrs, "for") {
+                        Some(AttrType::Normal { value }) => match static_value(value) {
+                            Some(target) => {
+                                self.targets.insert(target);
+                            }
+                            None => self.has_dynamic_target = true,
+                        },
+                        Some(_) => self.has_dynamic_target = true,
+                        None => {}
+                    }
+                }
+                if let ElementBody::Block { block } = body {
+                    self.collect(&block.markups);
+                }
+            }
+        });
+    }
+
+    fn contains(&self, id: &str) -> bool {
+        self.has_dynamic_target || self.targets.contains(id)
+    }
+}
+
+struct Checker {
+    labels: Labels,
+    inside_label: bool,
+    lints: Vec<Lint>,
+}
+
+impl Checker {
+    fn markups(&mut self, markups: &[Markup]) {
+        for_each_child(markups, &mut |markup| {
+            if let Markup::Element { name, attrs, body } = markup {
+                self.element(name_to_string(name.clone()), attrs, body, markup.span());
+            }
+        });
+    }
+
+    fn element(&mut self, name: String, attrs: &[Attr], body: &ElementBody, span: SpanRange) {
+        // A `..rest` spread might supply whatever attribute is missing
+        let has_spread = attrs.iter().any(|attr| matches!(attr, Attr::Spread { .. }));
+        match name.as_str() {
+            _ if has_spread => {}
+            "html" if find_attr(attrs, "lang").is_none() => self.push(
+                span,
+                "`html` element is missing a `lang` attribute".to_string(),
+                "add the language of the document, e.g. `lang=\"en\"`",
+            ),
+            "img" if find_attr(attrs, "alt").is_none() => self.push(
+                span,
+                "`img` element is missing an `alt` attribute".to_string(),
+                "if the image is purely decorative, use an empty `alt=\"\"`",
+            ),
+            "input" | "select" | "textarea" if !self.is_labeled(&name, attrs) => self.push(
+                span,
+                format!("`{}` element has no associated label", name),
+                "wrap it in a `label`, or point a `label for=\"...\"` at its `id`",
+            ),
+            _ => {}
+        }
+
+        if find_attr(attrs, "onclick").is_some()
+            && !is_interactive(&name)
+            && find_attr(attrs, "role").is_none()
+        {
+            self.push(
+                span,
+                format!("click handler on non-interactive element `{}`", name),
+                "use a `button` instead, or add a `role` and `tabindex`",
+            );
+        }
+
+        if let ElementBody::Block { block } = body {
+            let outer_inside_label = self.inside_label;
+            self.inside_label |= name == "label";
+            self.markups(&block.markups);
+            self.inside_label = outer_inside_label;
+        }
+    }
+
+    fn is_labeled(&self, name: &str, attrs: &[Attr]) -> bool {
+        if name == "input" {
+            if let Some(AttrType::Normal { value }) = find_attr(attrs, "type") {
+                match static_value(value).as_deref() {
+                    // These controls are labeled by their own value, or not
+                    // shown at all
+                    Some("hidden" | "submit" | "reset" | "button" | "image") => return true,
+                    Some(_) => {}
+                    None => return true,
+                }
+            }
+        }
+        if self.inside_label
+            || find_attr(attrs, "aria-label").is_some()
+            || find_attr(attrs, "aria-labelledby").is_some()
+            || find_attr(attrs, "title").is_some()
+        {
+            return true;
+        }
+        match find_id(attrs) {
+            Some(Some(id)) => self.labels.contains(&id),
+            // Give dynamic IDs the benefit of the doubt
+            Some(None) => true,
+            None => false,
+        }
+    }
+
+    fn push(&mut self, span: SpanRange, message: String, help: &'static str) {
+        self.lints.push(Lint {
+            span,
+            message,
+            help,
+        });
+    }
+}
+
+/// Calls `f` on each markup, descending into blocks and control structures
+/// but not into elements.
+pub fn for_each_child<'a>(markups: &'a [Markup], f: &mut impl FnMut(&'a Markup)) {
+    for markup in markups {
+        match markup {
+            Markup::Block(block)
+            | Markup::Suspend { body: block, .. }
+            | Markup::Provide { body: block, .. }
+            | Markup::Portal { body: block, .. } => for_each_child(&block.markups, f),
+            Markup::Special { segments } => {
+                for segment in segments {
+                    for_each_child(&segment.body.markups, f);
+                }
+            }
+            Markup::Match { arms, .. } => {
+                for arm in arms {
+                    for_each_child(&arm.body.markups, f);
+                }
+            }
+            markup => f(markup),
+        }
+    }
+}
+
+fn is_interactive(name: &str) -> bool {
+    matches!(
+        name,
+        "a" | "button"
+            | "details"
+            | "input"
+            | "label"
+            | "option"
+            | "select"
+            | "summary"
+            | "textarea"
+    )
+}
+
+pub fn find_attr<'a>(attrs: &'a [Attr], name: &str) -> Option<&'a AttrType> {
+    attrs.iter().find_map(|attr| match attr {
+        Attr::Named { named_attr } if name_to_string(named_attr.name.clone()) == name => {
+            Some(&named_attr.attr_type)
+        }
+        _ => None,
+    })
+}
+
+/// Returns `Some(Some(id))` for a static ID, `Some(None)` for a dynamic one,
+/// and `None` if the element has no ID.
+fn find_id(attrs: &[Attr]) -> Option<Option<String>> {
+    attrs.iter().find_map(|attr| match attr {
+        Attr::Id { name, .. } => Some(static_value(name)),
+        Attr::Named { named_attr } if name_to_string(named_attr.name.clone()) == "id" => {
+            match named_attr.attr_type {
+                AttrType::Normal { ref value } => Some(static_value(value)),
+                _ => Some(None),
+            }
+        }
+        _ => None,
+    })
+}
+
+/// Returns the value of an attribute, if it's known at compile time.
+pub fn static_value(markup: &Markup) -> Option<String> {
+    match markup {
+        Markup::Literal { content, .. } => Some(content.clone()),
+        Markup::Symbol { symbol } => Some(name_to_string(symbol.clone())),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::check;
+    use crate::parse::parse;
+
+    fn lints(input: &str) -> Vec<String> {
+        let markups = parse(input.parse().unwrap());
+        check(&markups)
+            .into_iter()
+            .map(|lint| lint.message)
+            .collect()
+    }
+
+    #[test]
+    fn missing_alt() {
+        assert_eq!(
+            lints(r#"img src="cat.png"; img src="dog.png" alt="A dog";"#),
+            ["`img` element is missing an `alt` attribute"],
+        );
+    }
+
+    #[test]
+    fn missing_lang() {
+        assert_eq!(
+            lints(r#"html { body {} }"#),
+            ["`html` element is missing a `lang` attribute"],
+        );
+        assert!(lints(r#"html lang="en" { body {} }"#).is_empty());
+    }
+
+    #[test]
+    fn unlabeled_inputs() {
+        assert_eq!(
+            lints(r#"input type="text"; input #name; textarea {}"#),
+            [
+                "`input` element has no associated label",
+                "`input` element has no associated label",
+                "`textarea` element has no associated label",
+            ],
+        );
+    }
+
+    #[test]
+    fn labeled_inputs() {
+        assert!(lints(
+            r#"
+            label for="name" { "Name" }
+            input #name;
+            label { "Age" input type="number"; }
+            @if true { input aria-label="Search"; }
+            input type="hidden" name="token";
+            input id=(dynamic_id);
+            "#
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn click_on_non_interactive() {
+        assert_eq!(
+            lints(
+                r#"div onclick="go()" {} button onclick="go()" {} span role="button" onclick="go()" {}"#
+            ),
+            ["click handler on non-interactive element `div`"],
+        );
+    }
+}
@@ -1,19 +1,38 @@
-use proc_macro2::{Delimiter, Ident, Literal, Spacing, Span, TokenStream, TokenTree};
-use proc_macro_error::{abort, abort_call_site, emit_error, SpanRange};
+use proc_macro2::{Delimiter, Group, Ident, Literal, Punct, Spacing, Span, TokenStream, TokenTree};
+use proc_macro_error::SpanRange;
+#[cfg(not(any(test, fuzzing, maudfmt, maud_ast)))]
+use proc_macro_error::{abort, abort_call_site, emit_error, emit_warning};
+use quote::{quote, quote_spanned};
 use std::collections::HashMap;
 
 use syn::Lit;
 
-use crate::ast;
+// Outside of a macro, as in the tests, the fuzzer, maudfmt and maud_ast,
+// record errors instead
+#[cfg(any(test, fuzzing, maudfmt, maud_ast))]
+use crate::diagnostics::{abort, abort_call_site, emit_error, emit_warning};
+use crate::{ast, suggest};
 
 pub fn parse(input: TokenStream) -> Vec<ast::Markup> {
     Parser::new(input).markups()
 }
 
+/// Parses the contents of an `xml!` template, whose attributes aren't
+/// checked against HTML's.
+pub fn parse_xml(input: TokenStream) -> Vec<ast::Markup> {
+    let mut parser = Parser::new(input);
+    parser.xml = true;
+    parser.markups()
+}
+
 #[derive(Clone)]
 struct Parser {
     /// If we're inside an attribute, then this contains the attribute name.
     current_attr: Option<String>,
+    /// Whether we're inside an `@for` or `@while` body.
+    in_loop: bool,
+    /// Whether this is an `xml!` template.
+    xml: bool,
     input: <TokenStream as IntoIterator>::IntoIter,
 }
 
@@ -29,6 +48,8 @@ impl Parser {
     fn new(input: TokenStream) -> Parser {
         Parser {
             current_attr: None,
+            in_loop: false,
+            xml: false,
             input: input.into_iter(),
         }
     }
@@ -36,6 +57,8 @@ impl Parser {
     fn with_input(&self, input: TokenStream) -> Parser {
         Parser {
             current_attr: self.current_attr.clone(),
+            in_loop: self.in_loop,
+            xml: self.xml,
             input: input.into_iter(),
         }
     }
@@ -76,6 +99,13 @@ impl Parser {
                     let keyword = TokenTree::Ident(ident.clone());
                     result.push(self.let_expr(punct.span(), keyword));
                 }
+                Some((TokenTree::Punct(ref punct), Some(TokenTree::Ident(ref ident))))
+                    if punct.as_char() == '@' && *ident == "consume" =>
+                {
+                    self.advance2();
+                    let keyword = TokenTree::Ident(ident.clone());
+                    result.push(self.consume_expr(punct.span(), keyword));
+                }
                 _ => result.push(self.markup()),
             }
         }
@@ -101,35 +131,17 @@ impl Parser {
                 self.advance();
                 let at_span = punct.span();
                 match self.next() {
-                    Some(TokenTree::Ident(ident)) => {
-                        let keyword = TokenTree::Ident(ident.clone());
-                        match ident.to_string().as_str() {
-                            "if" => {
-                                let mut segments = Vec::new();
-                                self.if_expr(at_span, vec![keyword], &mut segments);
-                                ast::Markup::Special { segments }
-                            }
-                            "while" => self.while_expr(at_span, keyword),
-                            "for" => self.for_expr(at_span, keyword),
-                            "match" => self.match_expr(at_span, keyword),
-                            "let" => {
-                                let span = SpanRange {
-                                    first: at_span,
-                                    last: ident.span(),
-                                };
-                                abort!(span, "`@let` only works inside a block");
-                            }
-                            other => {
-                                let span = SpanRange {
-                                    first: at_span,
-                                    last: ident.span(),
-                                };
-                                abort!(span, "unknown keyword `@{}`", other);
-                            }
-                        }
+                    Some(TokenTree::Ident(ident)) => self.special(at_span, ident),
+                    Some(TokenTree::Punct(quote)) if quote.as_char() == '\'' => {
+                        self.labeled_loop(at_span, quote)
                     }
-                    _ => {
-                        abort!(at_span, "expected keyword after `@`");
+                    token => {
+                        emit_error!(at_span, "expected keyword after `@`");
+                        let mut span = SpanRange::single_span(at_span);
+                        if let Some(token) = token {
+                            span.last = token.span();
+                        }
+                        ast::Markup::ParseError { span }
                     }
                 }
             }
@@ -138,11 +150,22 @@ impl Parser {
                 let ident_string = ident.to_string();
                 match ident_string.as_str() {
                     "if" | "while" | "for" | "match" | "let" => {
-                        abort!(
+                        emit_error!(
                             ident,
                             "found keyword `{}`", ident_string;
                             help = "should this be a `@{}`?", ident_string
                         );
+                        // Carry on as if the `@` were there
+                        self.advance();
+                        let keyword = TokenTree::Ident(ident.clone());
+                        let markup = if ident_string == "let" {
+                            self.let_expr(ident.span(), keyword)
+                        } else {
+                            self.special(ident.span(), ident.clone())
+                        };
+                        return ast::Markup::ParseError {
+                            span: markup.span(),
+                        };
                     }
                     "true" | "false" => {
                         if let Some(attr_name) = &self.current_attr {
@@ -187,16 +210,135 @@ impl Parser {
             }
             // ???
             token => {
-                abort!(token, "invalid syntax");
+                self.advance();
+                emit_error!(token, "invalid syntax");
+                ast::Markup::ParseError {
+                    span: SpanRange::single_span(token.span()),
+                }
             }
         };
         markup
     }
 
+    /// Parses a special form, like `@if` or `@for`.
+    ///
+    /// The leading `@` and keyword should already be consumed.
+    fn special(&mut self, at_span: Span, ident: Ident) -> ast::Markup {
+        let keyword = TokenTree::Ident(ident.clone());
+        let span = SpanRange {
+            first: at_span,
+            last: ident.span(),
+        };
+        match ident.to_string().as_str() {
+            "if" => {
+                let mut segments = Vec::new();
+                self.if_expr(at_span, vec![keyword], &mut segments);
+                ast::Markup::Special { segments }
+            }
+            "while" => self.while_expr(at_span, keyword),
+            "for" => self.for_expr(at_span, keyword),
+            "match" => self.match_expr(at_span, keyword),
+            "style" => self.style_expr(at_span, keyword),
+            "json_ld" => self.json_ld_expr(at_span, keyword),
+            "suspend" => self.suspend_expr(at_span, keyword),
+            "provide" => self.provide_expr(at_span, keyword),
+            "portal" => self.portal_expr(at_span, keyword),
+            "pi" => self.pi_expr(at_span, keyword),
+            "profile" => match self.next() {
+                Some(TokenTree::Ident(name)) => {
+                    self.semicolon();
+                    ast::Markup::Profile {
+                        at_span: span,
+                        name,
+                    }
+                }
+                _ => abort!(
+                    span,
+                    "expected profile name for this `@profile`";
+                    help = "the profiles are `email` and `amp`"
+                ),
+            },
+            "break" | "continue" => self.control_expr(at_span, keyword),
+            "return" => {
+                self.semicolon();
+                ast::Markup::Return { at_span: span }
+            }
+            "flush" => {
+                self.semicolon();
+                ast::Markup::Flush { at_span: span }
+            }
+            "minify" => {
+                self.semicolon();
+                ast::Markup::Minify { at_span: span }
+            }
+            "quotes" => match self.next() {
+                Some(TokenTree::Ident(style)) if style == "minimal" => {
+                    self.semicolon();
+                    ast::Markup::MinimalQuotes {
+                        at_span: SpanRange {
+                            first: at_span,
+                            last: style.span(),
+                        },
+                    }
+                }
+                _ => abort!(
+                    span,
+                    "expected quote style for this `@quotes`";
+                    help = "the only style is `minimal`: `@quotes minimal;`"
+                ),
+            },
+            "let" | "consume" => {
+                emit_error!(span, "`@{}` only works inside a block", ident);
+                let markup = if ident == "let" {
+                    self.let_expr(at_span, keyword)
+                } else {
+                    self.consume_expr(at_span, keyword)
+                };
+                ast::Markup::ParseError {
+                    span: markup.span(),
+                }
+            }
+            other => {
+                match suggest::did_you_mean(other, suggest::KEYWORDS) {
+                    Some(keyword) => emit_error!(
+                        span,
+                        "unknown keyword `@{}`", other;
+                        help = "did you mean `@{}`?", keyword
+                    ),
+                    None => emit_error!(span, "unknown keyword `@{}`", other),
+                }
+                self.skip_special(span)
+            }
+        }
+    }
+
+    /// Skips over the rest of a special form that couldn't be parsed, up to
+    /// and including its first body or `;`, so that parsing can carry on.
+    fn skip_special(&mut self, mut span: SpanRange) -> ast::Markup {
+        loop {
+            match self.peek() {
+                Some(TokenTree::Punct(ref punct)) if punct.as_char() == '@' => break,
+                Some(token) => {
+                    self.advance();
+                    span.last = token.span();
+                    match token {
+                        TokenTree::Group(ref group) if group.delimiter() == Delimiter::Brace => {
+                            break
+                        }
+                        TokenTree::Punct(ref punct) if punct.as_char() == ';' => break,
+                        _ => {}
+                    }
+                }
+                None => break,
+            }
+        }
+        ast::Markup::ParseError { span }
+    }
+
     /// Parses a literal string.
     fn literal(&mut self, literal: Literal) -> ast::Markup {
-        match Lit::new(literal.clone()) {
-            Lit::Str(lit_str) => {
+        match ast::lit(&literal) {
+            Some(Lit::Str(lit_str)) => {
                 return ast::Markup::Literal {
                     content: lit_str.value(),
                     span: SpanRange::single_span(literal.span()),
@@ -204,10 +346,10 @@ impl Parser {
             }
             // Boolean literals are idents, so `Lit::Bool` is handled in
             // `markup`, not here.
-            Lit::Int(..) | Lit::Float(..) => {
+            Some(Lit::Int(..) | Lit::Float(..)) => {
                 emit_error!(literal, r#"literal must be double-quoted: `"{}"`"#, literal);
             }
-            Lit::Char(lit_char) => {
+            Some(Lit::Char(lit_char)) => {
                 emit_error!(
                     literal,
                     r#"literal must be double-quoted: `"{}"`"#,
@@ -230,7 +372,9 @@ impl Parser {
         let mut head = prefix;
         let body = loop {
             match self.next() {
-                Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                Some(TokenTree::Group(ref block))
+                    if block.delimiter() == Delimiter::Brace && !in_let_pattern(&head) =>
+                {
                     break self.block(block.stream(), SpanRange::single_span(block.span()));
                 }
                 Some(token) => head.push(token),
@@ -303,8 +447,10 @@ impl Parser {
         let mut head = vec![keyword];
         let body = loop {
             match self.next() {
-                Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
-                    break self.block(block.stream(), SpanRange::single_span(block.span()));
+                Some(TokenTree::Group(ref block))
+                    if block.delimiter() == Delimiter::Brace && !in_let_pattern(&head) =>
+                {
+                    break self.loop_body(block);
                 }
                 Some(token) => head.push(token),
                 None => {
@@ -325,12 +471,112 @@ impl Parser {
         }
     }
 
+    /// Parses the body of a `@for` or `@while`, where `@break` and
+    /// `@continue` are allowed.
+    fn loop_body(&mut self, block: &Group) -> ast::Block {
+        let in_loop = std::mem::replace(&mut self.in_loop, true);
+        let body = self.block(block.stream(), SpanRange::single_span(block.span()));
+        self.in_loop = in_loop;
+        body
+    }
+
+    /// Parses a `@break` or `@continue`, with an optional label.
+    ///
+    /// The leading `@break` or `@continue` should already be consumed.
+    fn control_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        let mut tokens = vec![keyword.clone()];
+        if let Some((TokenTree::Punct(ref quote), Some(TokenTree::Ident(ref label)))) = self.peek2()
+        {
+            if quote.as_char() == '\'' {
+                self.advance2();
+                tokens.push(TokenTree::Punct(quote.clone()));
+                tokens.push(TokenTree::Ident(label.clone()));
+            }
+        }
+        // Consume the trailing semicolon, if there is one
+        let semi = match self.peek() {
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == ';' => {
+                self.advance();
+                TokenTree::Punct(punct.clone())
+            }
+            _ => TokenTree::Punct(Punct::new(';', Spacing::Alone)),
+        };
+        if !self.in_loop {
+            let span = SpanRange {
+                first: at_span,
+                last: tokens.last().map_or(keyword.span(), TokenTree::span),
+            };
+            emit_error!(
+                span,
+                "`@{}` outside of a loop", keyword;
+                help = "`@{}` only works inside `@for` and `@while`", keyword
+            );
+            return ast::Markup::ParseError { span };
+        }
+        tokens.push(semi);
+        ast::Markup::Control {
+            at_span: SpanRange::single_span(at_span),
+            tokens: tokens.into_iter().collect(),
+        }
+    }
+
+    /// Parses a labeled `@for` or `@while`, e.g. `@'rows: for row in rows`.
+    ///
+    /// The leading `@'` should already be consumed.
+    fn labeled_loop(&mut self, at_span: Span, quote: Punct) -> ast::Markup {
+        let mut label = vec![TokenTree::Punct(quote)];
+        label.extend(self.next());
+        label.extend(self.next());
+        let keyword = match (&label[..], self.next()) {
+            (
+                [_, TokenTree::Ident(_), TokenTree::Punct(colon)],
+                Some(TokenTree::Ident(keyword)),
+            ) if colon.as_char() == ':' && (keyword == "for" || keyword == "while") => keyword,
+            _ => {
+                let mut span = ast::span_tokens(label);
+                span.first = at_span;
+                abort!(span, "expected `@'label: for` or `@'label: while`");
+            }
+        };
+        let mut markup = if keyword == "for" {
+            self.for_expr(at_span, TokenTree::Ident(keyword))
+        } else {
+            self.while_expr(at_span, TokenTree::Ident(keyword))
+        };
+        // A `@for await` is a block containing the loop
+        let special = match &mut markup {
+            ast::Markup::Block(block) => block.markups.last_mut(),
+            markup => Some(markup),
+        };
+        if let Some(ast::Markup::Special { segments }) = special {
+            let head = std::mem::take(&mut segments[0].head);
+            segments[0].head = label.into_iter().chain(head).collect();
+        }
+        markup
+    }
+
+    /// Skips over a `;`, if there is one.
+    fn semicolon(&mut self) {
+        if let Some(TokenTree::Punct(ref punct)) = self.peek() {
+            if punct.as_char() == ';' {
+                self.advance();
+            }
+        }
+    }
+
     /// Parses a `@for` expression.
     ///
     /// The leading `@for` should already be consumed.
     fn for_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
         let keyword_span = keyword.span();
         let mut head = vec![keyword];
+        let await_keyword = match self.peek() {
+            Some(TokenTree::Ident(ref ident)) if *ident == "await" => {
+                self.advance();
+                Some(ident.clone())
+            }
+            _ => None,
+        };
         loop {
             match self.next() {
                 Some(TokenTree::Ident(ref in_keyword)) if *in_keyword == "in" => {
@@ -350,7 +596,7 @@ impl Parser {
         let body = loop {
             match self.next() {
                 Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
-                    break self.block(block.stream(), SpanRange::single_span(block.span()));
+                    break self.loop_body(block);
                 }
                 Some(token) => head.push(token),
                 None => {
@@ -362,6 +608,9 @@ impl Parser {
                 }
             }
         };
+        if let Some(await_keyword) = await_keyword {
+            return for_await(at_span, head, await_keyword, body);
+        }
         ast::Markup::Special {
             segments: vec![ast::Special {
                 at_span: SpanRange::single_span(at_span),
@@ -379,6 +628,20 @@ impl Parser {
         let mut head = vec![keyword];
         let (arms, arms_span) = loop {
             match self.next() {
+                // Blocks in an `if`/`else` or `unsafe` expression belong to
+                // the scrutinee, e.g. `@match if a { 1 } else { 2 } { .. }`
+                Some(TokenTree::Group(ref group))
+                    if group.delimiter() == Delimiter::Brace
+                        && (matches!(
+                            head.last(),
+                            Some(TokenTree::Ident(ident)) if ident == "else" || ident == "unsafe"
+                        ) || matches!(
+                            self.peek(),
+                            Some(TokenTree::Ident(ident)) if ident == "else"
+                        )) =>
+                {
+                    head.push(TokenTree::Group(group.clone()));
+                }
                 Some(TokenTree::Group(ref body)) if body.delimiter() == Delimiter::Brace => {
                     let span = SpanRange::single_span(body.span());
                     break (self.with_input(body.stream()).match_arms(), span);
@@ -454,6 +717,20 @@ impl Parser {
                 let mut span = SpanRange::single_span(first_token.span());
                 let mut body = vec![first_token];
                 loop {
+                    if let Some((TokenTree::Punct(ref eq), Some(TokenTree::Punct(ref gt)))) =
+                        self.peek2()
+                    {
+                        if eq.as_char() == '='
+                            && gt.as_char() == '>'
+                            && eq.spacing() == Spacing::Joint
+                        {
+                            abort!(
+                                span,
+                                "expected `,` after this match arm";
+                                help = "arms that aren't wrapped in braces must end with a comma"
+                            );
+                        }
+                    }
                     match self.next() {
                         Some(TokenTree::Punct(ref punct)) if punct.as_char() == ',' => break,
                         Some(token) => {
@@ -476,6 +753,259 @@ impl Parser {
         })
     }
 
+    /// Parses a `@style` block.
+    ///
+    /// The leading `@style` should already be consumed.
+    fn style_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        let body = match self.next() {
+            Some(TokenTree::Group(ref body)) if body.delimiter() == Delimiter::Brace => {
+                body.clone()
+            }
+            _ => {
+                let span = SpanRange {
+                    first: at_span,
+                    last: keyword.span(),
+                };
+                abort!(span, "expected body for this `@style`");
+            }
+        };
+        let mut css = String::new();
+        for token in body.stream() {
+            match token {
+                TokenTree::Literal(ref literal) => match ast::lit(literal) {
+                    Some(Lit::Str(lit_str)) => {
+                        css.push_str(&lit_str.value());
+                        css.push('\n');
+                    }
+                    _ => emit_error!(literal, "expected string"),
+                },
+                token => {
+                    emit_error!(
+                        token,
+                        "`@style` blocks can only contain string literals";
+                        help = "wrap the stylesheet in double quotes"
+                    );
+                }
+            }
+        }
+        ast::Markup::Style {
+            at_span: SpanRange::single_span(at_span),
+            css,
+            body_span: SpanRange::single_span(body.span()),
+            scope: None,
+        }
+    }
+
+    /// Parses a `@json_ld(value)` splice.
+    ///
+    /// The leading `@json_ld` should already be consumed.
+    fn json_ld_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                let expr = group.stream();
+                ast::Markup::Splice {
+                    expr: quote!(maud::JsonLd(&(#expr))),
+                    outer_span: SpanRange {
+                        first: at_span,
+                        last: group.span(),
+                    },
+                }
+            }
+            _ => {
+                let span = SpanRange {
+                    first: at_span,
+                    last: keyword.span(),
+                };
+                abort!(
+                    span,
+                    "expected value for this `@json_ld`";
+                    help = "wrap the value in parentheses: `@json_ld(value)`"
+                );
+            }
+        }
+    }
+
+    /// Parses a `@suspend(future) { placeholder }` slot.
+    ///
+    /// The leading `@suspend` should already be consumed.
+    fn suspend_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let future = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream()
+            }
+            _ => abort!(
+                span,
+                "expected future for this `@suspend`";
+                help = "wrap the future in parentheses: `@suspend(future) {{ ... }}`"
+            ),
+        };
+        let body = match self.next() {
+            Some(TokenTree::Group(ref body)) if body.delimiter() == Delimiter::Brace => {
+                self.block(body.stream(), SpanRange::single_span(body.span()))
+            }
+            _ => abort!(span, "expected placeholder for this `@suspend`"),
+        };
+        ast::Markup::Suspend {
+            at_span: span,
+            future,
+            body,
+        }
+    }
+
+    /// Parses a `@provide(value) { ... }` expression.
+    ///
+    /// The leading `@provide` should already be consumed.
+    fn provide_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let value = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream()
+            }
+            _ => abort!(
+                span,
+                "expected value for this `@provide`";
+                help = "wrap the value in parentheses: `@provide(value) {{ ... }}`"
+            ),
+        };
+        let body = match self.next() {
+            Some(TokenTree::Group(ref body)) if body.delimiter() == Delimiter::Brace => {
+                self.block(body.stream(), SpanRange::single_span(body.span()))
+            }
+            _ => abort!(span, "expected body for this `@provide`"),
+        };
+        ast::Markup::Provide {
+            at_span: span,
+            value,
+            body,
+        }
+    }
+
+    /// Parses a `@portal(target = "#id") { ... }` expression.
+    ///
+    /// The leading `@portal` should already be consumed.
+    fn portal_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let head = match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+            _ => abort!(
+                span,
+                "expected target for this `@portal`";
+                help = "name the element to move it into: `@portal(target = \"#modals\") {{ ... }}`"
+            ),
+        };
+        let mut parser = self.with_input(head.stream());
+        match parser.peek2() {
+            Some((TokenTree::Ident(ref ident), Some(TokenTree::Punct(ref punct))))
+                if *ident == "target" && punct.as_char() == '=' =>
+            {
+                parser.advance2();
+            }
+            _ => abort!(
+                head.span(),
+                "expected `target = ...` in this `@portal`";
+                help = "name the element to move it into: `@portal(target = \"#modals\") {{ ... }}`"
+            ),
+        }
+        parser.current_attr = Some("target".to_string());
+        let target = parser.markup();
+        if let Some(token) = parser.next() {
+            emit_error!(token, "unexpected token after the `@portal` target");
+        }
+        let body = match self.next() {
+            Some(TokenTree::Group(ref body)) if body.delimiter() == Delimiter::Brace => {
+                self.block(body.stream(), SpanRange::single_span(body.span()))
+            }
+            _ => abort!(span, "expected body for this `@portal`"),
+        };
+        ast::Markup::Portal {
+            at_span: span,
+            target: Box::new(target),
+            body,
+        }
+    }
+
+    /// Parses a `@consume(name: Type);` expression, which is short for
+    /// `@let name: Option<Type> = maud::context::get::<Type>();`.
+    ///
+    /// The leading `@consume` should already be consumed.
+    fn consume_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let group = match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+            _ => abort!(
+                span,
+                "expected binding for this `@consume`";
+                help = "name the value and its type: `@consume(theme: Theme);`"
+            ),
+        };
+        let tokens = group.stream().into_iter().collect::<Vec<_>>();
+        let colon = tokens.iter().position(|token| {
+            matches!(token, TokenTree::Punct(punct) if punct.as_char() == ':' && punct.spacing() == Spacing::Alone)
+        });
+        let (name, ty) = match colon {
+            Some(colon) if colon > 0 && colon + 1 < tokens.len() => {
+                let name = tokens[..colon].iter().cloned().collect::<TokenStream>();
+                let ty = tokens[colon + 1..].iter().cloned().collect::<TokenStream>();
+                (name, ty)
+            }
+            _ => abort!(
+                group.span(),
+                "expected `name: Type` in this `@consume`";
+                help = "name the value and its type: `@consume(theme: Theme);`"
+            ),
+        };
+        self.semicolon();
+        let span = group.span();
+        let let_keyword = Ident::new("let", keyword.span());
+        ast::Markup::Let {
+            at_span: SpanRange::single_span(at_span),
+            tokens: quote_spanned!(span=>
+                #let_keyword #name: ::core::option::Option<#ty> = maud::context::get::<#ty>();
+            ),
+        }
+    }
+
+    /// Parses a `@pi name attrs;` processing instruction.
+    ///
+    /// The leading `@pi` should already be consumed.
+    fn pi_expr(&mut self, at_span: Span, keyword: TokenTree) -> ast::Markup {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let name = match self.try_namespaced_name() {
+            Some(name) => name,
+            None => abort!(
+                span,
+                "expected target for this `@pi`";
+                help = "name the target after it: `@pi xml version=\"1.0\";`"
+            ),
+        };
+        let attrs = self.attrs();
+        match self.next() {
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == ';' => {}
+            _ => abort!(span, "expected `;` after this `@pi`"),
+        }
+        ast::Markup::ProcessingInstruction {
+            at_span: span,
+            name,
+            attrs,
+        }
+    }
+
     /// Parses a `@let` expression.
     ///
     /// The leading `@let` should already be consumed.
@@ -528,10 +1058,19 @@ impl Parser {
     /// The element name should already be consumed.
     fn element(&mut self, name: TokenStream) -> ast::Markup {
         if self.current_attr.is_some() {
-            let span = ast::span_tokens(name);
-            abort!(span, "unexpected element");
+            let span = ast::span_tokens(name.clone());
+            emit_error!(span, "unexpected element");
+            // Parse the rest of the element anyway, to find more errors
+            let current_attr = self.current_attr.take();
+            self.element(name);
+            self.current_attr = current_attr;
+            return ast::Markup::ParseError { span };
         }
         let attrs = self.attrs();
+        // XML has its own attributes, so only HTML's are checked for typos
+        if !self.xml {
+            check_attr_names(&attrs);
+        }
         let body = match self.peek() {
             Some(TokenTree::Punct(ref punct))
                 if punct.as_char() == ';' || punct.as_char() == '/' =>
@@ -552,16 +1091,54 @@ impl Parser {
             }
             Some(_) => match self.markup() {
                 ast::Markup::Block(block) => ast::ElementBody::Block { block },
+                // The error has already been reported
+                markup @ ast::Markup::ParseError { .. } => ast::ElementBody::Block {
+                    block: ast::Block {
+                        outer_span: markup.span(),
+                        markups: vec![markup],
+                    },
+                },
                 markup => {
                     let markup_span = markup.span();
+                    let name_string = ast::name_to_string(name.clone());
+                    if is_void_element(&name_string) {
+                        emit_error!(
+                            markup_span,
+                            "element body must be wrapped in braces";
+                            help = "did you mean `;`? `{}` is a void element", name_string
+                        );
+                    } else {
+                        emit_error!(
+                            markup_span,
+                            "element body must be wrapped in braces";
+                            help = "see https://github.com/lambda-fairy/maud/pull/137 for details"
+                        );
+                    }
+                    ast::ElementBody::Block {
+                        block: ast::Block {
+                            markups: vec![markup],
+                            outer_span: markup_span,
+                        },
+                    }
+                }
+            },
+            None => {
+                let name_string = ast::name_to_string(name.clone());
+                let span = ast::span_tokens(name);
+                if is_void_element(&name_string) {
                     abort!(
-                        markup_span,
-                        "element body must be wrapped in braces";
-                        help = "see https://github.com/lambda-fairy/maud/pull/137 for details"
+                        span,
+                        "expected `;`, found end of macro";
+                        help = "`{}` is a void element, so it ends with `;`", name_string
+                    );
+                } else {
+                    abort!(
+                        span,
+                        "expected `;`, found end of macro";
+                        help = "add a body: `{} {{ ... }}`", name_string
                     );
                 }
-            },
-            None => abort_call_site!("expected `;`, found end of macro"),
+            }
         };
         ast::Markup::Element { name, attrs, body }
     }
@@ -572,6 +1149,12 @@ impl Parser {
         loop {
             if let Some(name) = self.try_namespaced_name() {
                 // Attribute
+                if let Some(dots_span) = self.try_fields_marker() {
+                    if let Some(named_attr) = self.fields_attr(name, dots_span) {
+                        attrs.push(ast::Attr::Named { named_attr });
+                    }
+                    continue;
+                }
                 match self.peek() {
                     // Non-empty attribute
                     Some(TokenTree::Punct(ref punct)) if punct.as_char() == '=' => {
@@ -614,11 +1197,48 @@ impl Parser {
                     }
                 }
             } else {
-                match self.peek() {
+                match self.peek2() {
+                    // Spread
+                    Some((TokenTree::Punct(ref first), Some(TokenTree::Punct(ref second))))
+                        if first.as_char() == '.'
+                            && first.spacing() == Spacing::Joint
+                            && second.as_char() == '.' =>
+                    {
+                        self.advance2();
+                        let dots_span = SpanRange {
+                            first: first.span(),
+                            last: second.span(),
+                        };
+                        match self.next() {
+                            Some(token @ TokenTree::Ident(_)) => {
+                                attrs.push(ast::Attr::Spread {
+                                    dots_span,
+                                    expr_span: SpanRange::single_span(token.span()),
+                                    expr: token.into(),
+                                });
+                            }
+                            Some(TokenTree::Group(ref group))
+                                if group.delimiter() == Delimiter::Parenthesis =>
+                            {
+                                attrs.push(ast::Attr::Spread {
+                                    dots_span,
+                                    expr: group.stream(),
+                                    expr_span: SpanRange::single_span(group.span()),
+                                });
+                            }
+                            _ => {
+                                emit_error!(
+                                    dots_span,
+                                    "expected a name or a `(...)` expression after `..`";
+                                    help = "write `..rest` or `..(expr)`"
+                                );
+                            }
+                        }
+                    }
                     // Class shorthand
-                    Some(TokenTree::Punct(ref punct)) if punct.as_char() == '.' => {
+                    Some((TokenTree::Punct(ref punct), _)) if punct.as_char() == '.' => {
                         self.advance();
-                        let name = self.class_or_id_name();
+                        let name = self.class_or_id_name("class");
                         let toggler = self.attr_toggler();
                         attrs.push(ast::Attr::Class {
                             dot_span: SpanRange::single_span(punct.span()),
@@ -627,9 +1247,9 @@ impl Parser {
                         });
                     }
                     // ID shorthand
-                    Some(TokenTree::Punct(ref punct)) if punct.as_char() == '#' => {
+                    Some((TokenTree::Punct(ref punct), _)) if punct.as_char() == '#' => {
                         self.advance();
-                        let name = self.class_or_id_name();
+                        let name = self.class_or_id_name("id");
                         attrs.push(ast::Attr::Id {
                             hash_span: SpanRange::single_span(punct.span()),
                             name,
@@ -654,6 +1274,8 @@ impl Parser {
                     "class".to_string()
                 }
                 ast::Attr::Id { .. } => "id".to_string(),
+                // A spread can't know its names until runtime
+                ast::Attr::Spread { .. } => continue,
                 ast::Attr::Named { named_attr } => named_attr
                     .name
                     .clone()
@@ -669,19 +1291,86 @@ impl Parser {
             if spans.len() > 1 {
                 let mut spans = spans.into_iter();
                 let first_span = spans.next().expect("spans should be non-empty");
-                abort!(first_span, "duplicate attribute `{}`", name);
+                emit_error!(first_span, "duplicate attribute `{}`", name);
             }
         }
 
         attrs
     }
 
+    /// Skips over the `..=` in `data..=(value)`, if there is one.
+    fn try_fields_marker(&mut self) -> Option<SpanRange> {
+        let mut ahead = self.clone();
+        match (ahead.next(), ahead.next(), ahead.next()) {
+            (
+                Some(TokenTree::Punct(ref first)),
+                Some(TokenTree::Punct(ref second)),
+                Some(TokenTree::Punct(ref eq)),
+            ) if first.as_char() == '.'
+                && first.spacing() == Spacing::Joint
+                && second.as_char() == '.'
+                && second.spacing() == Spacing::Joint
+                && eq.as_char() == '=' =>
+            {
+                *self = ahead;
+                Some(SpanRange {
+                    first: first.span(),
+                    last: eq.span(),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses the value of a `data..=(value)` attribute.
+    ///
+    /// The name and `..=` should already be consumed.
+    fn fields_attr(&mut self, name: TokenStream, dots_span: SpanRange) -> Option<ast::NamedAttr> {
+        let (value, value_span) = match self.next() {
+            Some(token @ TokenTree::Ident(_)) => {
+                (token.clone().into(), SpanRange::single_span(token.span()))
+            }
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                (group.stream(), SpanRange::single_span(group.span()))
+            }
+            _ => {
+                emit_error!(
+                    dots_span,
+                    "expected a name or a `(...)` expression after `..=`";
+                    help = "write `data..=fields` or `data..=(expr)`"
+                );
+                return None;
+            }
+        };
+        if ast::name_to_string(name.clone()) != "data" {
+            emit_error!(
+                ast::span_tokens(name).join_range(dots_span),
+                "only `data` can be filled from fields";
+                help = "write `data..=(value)` for one `data-*` attribute per field"
+            );
+            return None;
+        }
+        Some(ast::NamedAttr {
+            name,
+            attr_type: ast::AttrType::Fields {
+                dots_span,
+                value,
+                value_span,
+            },
+        })
+    }
+
     /// Parses the name of a class or ID.
-    fn class_or_id_name(&mut self) -> ast::Markup {
+    fn class_or_id_name(&mut self, attr_name: &str) -> ast::Markup {
         if let Some(symbol) = self.try_name() {
             ast::Markup::Symbol { symbol }
         } else {
-            self.markup()
+            // Parse the name as an attribute value, so that an element here
+            // (as in `. .foo`) is an error
+            let current_attr = self.current_attr.replace(attr_name.to_string());
+            let name = self.markup();
+            self.current_attr = current_attr;
+            name
         }
     }
 
@@ -731,7 +1420,9 @@ impl Parser {
     /// if necessary.
     fn try_namespaced_name(&mut self) -> Option<TokenStream> {
         let mut result = vec![self.try_name()?];
-        if let Some(TokenTree::Punct(ref punct)) = self.peek() {
+        // Only take the `:` if a name follows it, so that `a:` on its own
+        // leaves the colon for the caller to report
+        if let Some((TokenTree::Punct(ref punct), Some(TokenTree::Ident(_)))) = self.peek2() {
             if punct.as_char() == ':' {
                 self.advance();
                 result.push(TokenStream::from(TokenTree::Punct(punct.clone())));
@@ -750,3 +1441,334 @@ impl Parser {
         }
     }
 }
+
+/// Warns about attribute names that look like typos of HTML's.
+fn check_attr_names(attrs: &[ast::Attr]) {
+    for attr in attrs {
+        if let ast::Attr::Named { named_attr } = attr {
+            let name = ast::name_to_string(named_attr.name.clone());
+            if let Some(suggestion) = suggest::attribute(&name) {
+                emit_warning!(
+                    ast::span_tokens(named_attr.name.clone()),
+                    "unknown attribute `{}`", name;
+                    help = "did you mean `{}`?", suggestion
+                );
+            }
+        }
+    }
+}
+
+/// Desugars `@for await pattern in stream { ... }` into a `while let` loop
+/// that polls the stream.
+///
+/// `head` is `for pattern in stream`, without the `await`.
+fn for_await(
+    at_span: Span,
+    head: Vec<TokenTree>,
+    await_keyword: Ident,
+    body: ast::Block,
+) -> ast::Markup {
+    let in_index = head
+        .iter()
+        .position(|token| matches!(token, TokenTree::Ident(ident) if ident == "in"))
+        .unwrap_or(head.len());
+    let pattern: TokenStream = head[1..in_index].iter().cloned().collect();
+    let stream: TokenStream = head[in_index + 1..].iter().cloned().collect();
+    let stream_ident = quote_spanned!(Span::mixed_site()=> __maud_stream);
+    let await_span = await_keyword.span();
+    let next =
+        quote_spanned!(await_span=> maud::__stream_next(#stream_ident.as_mut()).#await_keyword);
+    let at_span = SpanRange::single_span(at_span);
+    ast::Markup::Block(ast::Block {
+        markups: vec![
+            ast::Markup::Let {
+                at_span,
+                tokens: quote!(let mut #stream_ident = ::core::pin::pin!(#stream);),
+            },
+            ast::Markup::Special {
+                segments: vec![ast::Special {
+                    at_span,
+                    head: quote!(while let ::core::option::Option::Some(#pattern) = #next),
+                    body,
+                }],
+            },
+        ],
+        outer_span: at_span,
+    })
+}
+
+/// Whether the end of an `@if` or `@while` head is inside a `let` pattern,
+/// where braces belong to a struct pattern rather than starting the body.
+fn in_let_pattern(head: &[TokenTree]) -> bool {
+    let mut in_pattern = false;
+    for (i, token) in head.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident) if ident == "let" => in_pattern = true,
+            TokenTree::Punct(punct)
+                if punct.as_char() == '='
+                    && ast::is_plain_eq(
+                        i.checked_sub(1).map(|i| &head[i]),
+                        punct,
+                        head.get(i + 1),
+                    ) =>
+            {
+                in_pattern = false;
+            }
+            _ => {}
+        }
+    }
+    in_pattern
+}
+
+/// Whether the element has no closing tag, and so no body.
+fn is_void_element(name: &str) -> bool {
+    matches!(
+        name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+// maudfmt and maud_ast compile this module in too, but run their own tests
+#[cfg(all(test, not(any(maudfmt, maud_ast))))]
+mod test {
+    use proc_macro2::TokenStream;
+    use proptest::prelude::*;
+
+    use super::{parse, parse_xml};
+    use crate::{
+        ast,
+        diagnostics::{self, Level, Message},
+        pretty,
+    };
+
+    fn errors(messages: &[Message]) -> Vec<&str> {
+        messages
+            .iter()
+            .filter(|message| message.level == Level::Error)
+            .map(|message| message.text.as_str())
+            .collect()
+    }
+
+    /// Parses a template and prints it back out, if it parsed without
+    /// errors.
+    fn reprint(source: &str) -> Option<String> {
+        let tokens = source.parse::<TokenStream>().ok()?;
+        let (markups, messages) = diagnostics::capture(|| parse(tokens));
+        let markups = markups?;
+        if !errors(&messages).is_empty() {
+            return None;
+        }
+        Some(pretty::print(&markups))
+    }
+
+    /// Checks that a template that parses survives being printed and parsed
+    /// again.
+    fn check_round_trip(source: &str) -> Result<(), TestCaseError> {
+        if let Some(printed) = reprint(source) {
+            prop_assert_eq!(reprint(&printed), Some(printed), "source: {}", source);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn recovers_from_errors() {
+        let tokens = r#"@iff x {} p { 'x' } br;"#.parse().unwrap();
+        let (markups, messages) = diagnostics::capture(|| parse(tokens));
+        let markups = markups.expect("the parser should not abort");
+        assert_eq!(
+            errors(&messages),
+            [
+                "unknown keyword `@iff`\nhelp: did you mean `@if`?",
+                "literal must be double-quoted: `\"x\"`",
+            ],
+        );
+        assert!(matches!(markups[0], ast::Markup::ParseError { .. }));
+        assert_eq!(markups.len(), 3);
+    }
+
+    #[test]
+    fn aborts_at_end_of_input() {
+        let tokens = r#"p { "Hello" } br"#.parse().unwrap();
+        let (markups, messages) = diagnostics::capture(|| parse(tokens));
+        assert!(markups.is_none());
+        assert_eq!(
+            errors(&messages),
+            ["expected `;`, found end of macro\nhelp: `br` is a void element, so it ends with `;`"],
+        );
+    }
+
+    #[test]
+    fn c_string_literal() {
+        let tokens = r#"c"text""#.parse().unwrap();
+        let (markups, messages) = diagnostics::capture(|| parse(tokens));
+        assert!(markups.is_some());
+        assert_eq!(errors(&messages), ["expected string"]);
+    }
+
+    #[test]
+    fn xml_attributes_arent_checked() {
+        let source = r#"@pi xml version="1.0" encoding="UTF-8"; feed { item onclck="x"; }"#;
+        let (_, messages) = diagnostics::capture(|| parse_xml(source.parse().unwrap()));
+        assert!(messages.is_empty(), "{:?}", messages);
+        let (_, messages) =
+            diagnostics::capture(|| parse(r#"button onclck="x" {}"#.parse().unwrap()));
+        assert_eq!(messages.len(), 1);
+    }
+
+    #[test]
+    fn element_in_class_name() {
+        let tokens = r#"p. .foo {} {}"#.parse().unwrap();
+        let (_, messages) = diagnostics::capture(|| parse(tokens));
+        assert_eq!(errors(&messages), ["unexpected element"]);
+    }
+
+    /// Tokens that come up in templates, for gluing together at random.
+    const TOKENS: &[&str] = &[
+        "@", "if", "else", "for", "await", "in", "while", "let", "match", "break", "continue",
+        "return", "flush", "minify", "quotes", "minimal", "suspend", "pi", "profile", "email",
+        "provide", "consume", "portal", "target", "style", "json_ld", "'a", "'a:", "=>", "=", ",",
+        ";", ".", "#", "-", ":", "?", "/", "!", "&", "x", "xs", "p", "br", "input", "div", "class",
+        "href", "true", "\"text\"", "'c'", "1", "2.5",
+    ];
+
+    /// Random but balanced sequences of tokens and groups.
+    fn token_soup() -> impl Strategy<Value = String> {
+        let token = prop::sample::select(TOKENS).prop_map(String::from);
+        let tree = token.prop_recursive(4, 64, 8, |inner| {
+            (
+                prop::sample::select(&[("(", ")"), ("[", "]"), ("{", "}")][..]),
+                prop::collection::vec(inner, 0..8),
+            )
+                .prop_map(|((open, close), items)| format!("{}{}{}", open, items.join(" "), close))
+        });
+        prop::collection::vec(tree, 0..16).prop_map(|trees| trees.join(" "))
+    }
+
+    fn element_head() -> impl Strategy<Value = String> {
+        (
+            prop::sample::select(&["p", "div", "my-element", "svg:rect"][..]),
+            prop::collection::vec(
+                prop::sample::select(&[".a", ".b-c", ".\"d\"", ".(e)", ".f[on]"][..]),
+                0..3,
+            ),
+            prop::option::of(prop::sample::select(&["#i", "#(id)"][..])),
+            prop::sample::subsequence(
+                &[
+                    "title=\"t\"",
+                    "href=(url)",
+                    "data-x={ \"a\" (b) }",
+                    "hidden",
+                    "checked[on]",
+                    "lang=[lang]",
+                ][..],
+                0..3,
+            ),
+        )
+            .prop_map(|(name, classes, id, named)| {
+                let mut head = name.to_string();
+                for attr in classes.into_iter().chain(id).chain(named) {
+                    head.push(' ');
+                    head.push_str(attr);
+                }
+                head
+            })
+    }
+
+    /// Templates that parse without errors.
+    fn template() -> impl Strategy<Value = String> {
+        let leaf = prop_oneof![
+            any::<String>().prop_map(|text| format!("{:?}", text)),
+            prop::sample::select(&["(x)", "(x + 1)", "(a.b(c)?)", "(&xs[0])"][..])
+                .prop_map(String::from),
+            prop::sample::select(
+                &[
+                    "br;",
+                    "input type=\"checkbox\" checked[on];",
+                    "@let y = x * 2;",
+                    "@return;",
+                    "@json_ld(data)",
+                    "@pi xml-stylesheet href=\"/feed.xsl\";",
+                    "@profile email;",
+                    "@consume(theme: Theme);",
+                    "@style { \"p { color: red }\" \".a\" }",
+                ][..]
+            )
+            .prop_map(String::from),
+            (element_head(), prop::sample::select(&[";", " {}"][..]))
+                .prop_map(|(head, body)| format!("{}{}", head, body)),
+        ];
+        leaf.prop_recursive(4, 48, 4, |inner| {
+            let body = prop::collection::vec(inner, 0..4)
+                .prop_map(|items| format!("{{ {} }}", items.join(" ")));
+            // `@break` and `@continue` are only allowed in loops
+            let loop_body = (
+                body.clone(),
+                prop::sample::select(&["", "@break;", "@continue"][..]),
+            )
+                .prop_map(|(body, control)| format!("{{ {} {} }}", body, control));
+            prop_oneof![
+                body.clone(),
+                body.clone()
+                    .prop_map(|body| format!("@suspend(load(x)) {}", body)),
+                body.clone()
+                    .prop_map(|body| format!("@provide(Theme::Dark) {}", body)),
+                body.clone()
+                    .prop_map(|body| format!("@portal(target = \"#modals\") {}", body)),
+                (element_head(), body.clone())
+                    .prop_map(|(head, body)| format!("{} {}", head, body)),
+                (body.clone(), prop::option::of(body.clone())).prop_map(|(then, otherwise)| {
+                    match otherwise {
+                        Some(otherwise) => {
+                            format!("@if x {} @else if let Some(y) = z {}", then, otherwise)
+                        }
+                        None => format!("@if x > 0 {}", then),
+                    }
+                }),
+                (
+                    prop::sample::select(
+                        &[
+                            "@for x in xs",
+                            "@for await x in stream",
+                            "@while let Some(x) = it.next()",
+                        ][..]
+                    ),
+                    loop_body.clone(),
+                )
+                    .prop_map(|(head, body)| format!("{} {}", head, body)),
+                loop_body
+                    .prop_map(|body| format!("@'outer: for x in xs {{ {} @break 'outer; }}", body)),
+                (body.clone(), body).prop_map(|(some, none)| {
+                    format!(
+                        "@match x {{ Some(y) if y > 1 => {} None => {}, _ => \"other\" }}",
+                        some, none
+                    )
+                }),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn parser_never_panics(source in token_soup()) {
+            check_round_trip(&source)?;
+        }
+
+        #[test]
+        fn valid_templates_round_trip(source in template()) {
+            prop_assert!(reprint(&source).is_some(), "failed to parse: {}", source);
+            check_round_trip(&source)?;
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Prints a parsed template back out as source, for checking the parser.
+//!
+//! The output isn't meant to look like the original, only to parse back to
+//! the same tree. So printing, parsing and printing again should give the
+//! same text.
+
+use proc_macro2::TokenStream;
+
+use crate::ast::*;
+
+pub fn print(markups: &[Markup]) -> String {
+    let mut out = String::new();
+    self::markups(markups, &mut out);
+    out
+}
+
+fn markups(markups: &[Markup], out: &mut String) {
+    for (i, markup) in markups.iter().enumerate() {
+        if i > 0 {
+            out.push(' ');
+        }
+        self::markup(markup, out);
+    }
+}
+
+fn markup(markup: &Markup, out: &mut String) {
+    match markup {
+        Markup::ParseError { .. } => panic!("can't print a template with errors"),
+        Markup::Block(block) => self::block(block, out),
+        Markup::Literal { content, .. } => out.push_str(&format!("{:?}", content)),
+        Markup::Symbol { symbol } => tokens(symbol, out),
+        Markup::Splice { expr, .. } => {
+            out.push('(');
+            tokens(expr, out);
+            out.push(')');
+        }
+        Markup::Element { name, attrs, body } => {
+            tokens(name, out);
+            for attr in attrs {
+                out.push(' ');
+                self::attr(attr, out);
+            }
+            match body {
+                ElementBody::Void { .. } => out.push(';'),
+                ElementBody::Block { block } => {
+                    out.push(' ');
+                    self::block(block, out);
+                }
+            }
+        }
+        Markup::Let { tokens: stmt, .. } | Markup::Control { tokens: stmt, .. } => {
+            out.push('@');
+            tokens(stmt, out);
+        }
+        Markup::Return { .. } => out.push_str("@return;"),
+        Markup::Minify { .. } => out.push_str("@minify;"),
+        Markup::MinimalQuotes { .. } => out.push_str("@quotes minimal;"),
+        Markup::Flush { .. } => out.push_str("@flush;"),
+        Markup::Suspend { future, body, .. } => {
+            out.push_str("@suspend(");
+            tokens(future, out);
+            out.push_str(") ");
+            self::block(body, out);
+        }
+        Markup::Provide { value, body, .. } => {
+            out.push_str("@provide(");
+            tokens(value, out);
+            out.push_str(") ");
+            self::block(body, out);
+        }
+        Markup::Portal { target, body, .. } => {
+            out.push_str("@portal(target = ");
+            self::markup(target, out);
+            out.push_str(") ");
+            self::block(body, out);
+        }
+        Markup::Profile { name, .. } => out.push_str(&format!("@profile {};", name)),
+        Markup::ProcessingInstruction { name, attrs, .. } => {
+            out.push_str("@pi ");
+            tokens(name, out);
+            for attr in attrs {
+                out.push(' ');
+                self::attr(attr, out);
+            }
+            out.push(';');
+        }
+        Markup::Special { segments } => {
+            for (i, segment) in segments.iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                out.push('@');
+                tokens(&segment.head, out);
+                out.push(' ');
+                self::block(&segment.body, out);
+            }
+        }
+        Markup::Match { head, arms, .. } => {
+            out.push('@');
+            tokens(head, out);
+            out.push_str(" {");
+            for arm in arms {
+                out.push(' ');
+                tokens(&arm.head, out);
+                out.push(' ');
+                self::block(&arm.body, out);
+            }
+            out.push_str(" }");
+        }
+        Markup::Style { css, .. } => {
+            // Each string in the block gets a newline after it
+            let css = css.strip_suffix('\n').unwrap_or(css);
+            out.push_str(&format!("@style {{ {:?} }}", css));
+        }
+    }
+}
+
+fn block(block: &Block, out: &mut String) {
+    out.push('{');
+    if !block.markups.is_empty() {
+        out.push(' ');
+        markups(&block.markups, out);
+        out.push(' ');
+    }
+    out.push('}');
+}
+
+fn attr(attr: &Attr, out: &mut String) {
+    match attr {
+        Attr::Class { name, toggler, .. } => {
+            out.push('.');
+            markup(name, out);
+            if let Some(toggler) = toggler {
+                self::toggler(toggler, out);
+            }
+        }
+        Attr::Id { name, .. } => {
+            out.push('#');
+            markup(name, out);
+        }
+        Attr::Spread { expr, .. } => {
+            out.push_str("..(");
+            tokens(expr, out);
+            out.push(')');
+        }
+        Attr::Named { named_attr } => {
+            tokens(&named_attr.name, out);
+            match &named_attr.attr_type {
+                AttrType::Normal { value } => {
+                    out.push('=');
+                    markup(value, out);
+                }
+                AttrType::Optional { toggler } => {
+                    out.push('=');
+                    self::toggler(toggler, out);
+                }
+                AttrType::Empty { toggler } => {
+                    if let Some(toggler) = toggler {
+                        self::toggler(toggler, out);
+                    }
+                }
+                AttrType::Fields { value, .. } => {
+                    out.push_str("..=(");
+                    tokens(value, out);
+                    out.push(')');
+                }
+            }
+        }
+    }
+}
+
+fn toggler(toggler: &Toggler, out: &mut String) {
+    out.push('[');
+    tokens(&toggler.cond, out);
+    out.push(']');
+}
+
+fn tokens(tokens: &TokenStream, out: &mut String) {
+    out.push_str(&tokens.to_string());
+}
@@ -0,0 +1,366 @@
+//! Validation profiles, chosen per template with `@profile name;`.
+//!
+//! A profile checks a template against the HTML that somewhere other than
+//! a browser accepts, like an email client, and warns about anything that
+//! would be stripped or shown wrong there.
+
+use proc_macro_error::{emit_error, emit_warning, SpanRange};
+
+use crate::{
+    ast::*,
+    lint::{find_attr, for_each_child, static_value, Lint},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Profile {
+    /// HTML that email clients render consistently.
+    Email,
+    /// AMP HTML, which allows no custom scripts and only AMP components.
+    Amp,
+}
+
+impl Profile {
+    fn from_name(name: &str) -> Option<Profile> {
+        match name {
+            "email" => Some(Profile::Email),
+            "amp" => Some(Profile::Amp),
+            _ => None,
+        }
+    }
+}
+
+/// Checks the template against the profile in its `@profile`, if it has
+/// one, emitting a warning for each problem found.
+pub fn apply(markups: &[Markup]) {
+    let mut profiles = markups.iter().filter_map(|markup| match markup {
+        Markup::Profile { name, .. } => Some((name, markup.span())),
+        _ => None,
+    });
+    let (name, span) = match profiles.next() {
+        Some(profile) => profile,
+        None => return,
+    };
+    for (_, span) in profiles {
+        emit_error!(span, "a template can only have one `@profile`");
+    }
+    let profile = match Profile::from_name(&name.to_string()) {
+        Some(profile) => profile,
+        None => {
+            emit_error!(
+                span,
+                "unknown profile `{}`", name;
+                help = "the profiles are `email` and `amp`"
+            );
+            return;
+        }
+    };
+    for Lint {
+        span,
+        message,
+        help,
+    } in check(profile, markups)
+    {
+        emit_warning!(span, message; help = help);
+    }
+}
+
+/// Checks the given markups against a profile.
+pub fn check(profile: Profile, markups: &[Markup]) -> Vec<Lint> {
+    let mut checker = Checker {
+        profile,
+        lints: Vec::new(),
+    };
+    checker.markups(markups);
+    checker.lints
+}
+
+struct Checker {
+    profile: Profile,
+    lints: Vec<Lint>,
+}
+
+impl Checker {
+    fn markups(&mut self, markups: &[Markup]) {
+        for_each_child(markups, &mut |markup| match markup {
+            Markup::Element { name, attrs, body } => {
+                self.element(&name_to_string(name.clone()), attrs, body, markup.span());
+            }
+            Markup::Style { .. } => self.stylesheet(markup.span()),
+            _ => {}
+        });
+    }
+
+    fn element(&mut self, name: &str, attrs: &[Attr], body: &ElementBody, span: SpanRange) {
+        match self.profile {
+            Profile::Email => self.email_element(name, attrs, body, span),
+            Profile::Amp => self.amp_element(name, attrs, span),
+        }
+        for attr in attrs {
+            if let Attr::Named { named_attr } = attr {
+                let attr_name = name_to_string(named_attr.name.clone());
+                if attr_name.starts_with("on") {
+                    self.push(
+                        attr.span(),
+                        format!("`{}` handlers aren't allowed in this profile", attr_name),
+                        "scripts don't run here, so leave the handler out",
+                    );
+                }
+            }
+        }
+        if let Some(AttrType::Normal { value }) = find_attr(attrs, "style") {
+            match self.profile {
+                Profile::Email => {
+                    if let Some(css) = static_value(value) {
+                        self.inline_css(&css, value.span());
+                    }
+                }
+                Profile::Amp => self.push(
+                    value.span(),
+                    "inline styles aren't allowed in AMP".to_string(),
+                    "move the styles into a `style amp-custom` element",
+                ),
+            }
+        }
+        if let ElementBody::Block { block } = body {
+            self.markups(&block.markups);
+        }
+    }
+
+    fn email_element(&mut self, name: &str, attrs: &[Attr], body: &ElementBody, span: SpanRange) {
+        match name {
+            "script" | "iframe" | "object" | "embed" | "applet" | "frame" | "frameset" | "base"
+            | "canvas" => self.push(
+                span,
+                format!("`{}` isn't supported in email", name),
+                "email clients strip it, so leave it out",
+            ),
+            "form" | "input" | "select" | "textarea" | "button" | "video" | "audio" => self.push(
+                span,
+                format!("`{}` only works in some email clients", name),
+                "link to a web page for anything interactive",
+            ),
+            "link" => self.push(
+                span,
+                "external stylesheets aren't supported in email".to_string(),
+                "use inline `style` attributes instead",
+            ),
+            "style" => self.stylesheet(span),
+            "table" => {
+                let is_presentation = matches!(
+                    find_attr(attrs, "role"),
+                    Some(AttrType::Normal { value }) if static_value(value).as_deref() == Some("presentation")
+                );
+                if !is_presentation && !has_header_cells(body) {
+                    self.push(
+                        span,
+                        "layout `table` without `role=\"presentation\"`".to_string(),
+                        "add `role=\"presentation\"` so screen readers don't read it as data",
+                    );
+                }
+            }
+            "img" if find_attr(attrs, "width").is_none() => self.push(
+                span,
+                "`img` has no `width` attribute".to_string(),
+                "some clients show images at full size unless the width is set",
+            ),
+            _ => {}
+        }
+    }
+
+    fn amp_element(&mut self, name: &str, attrs: &[Attr], span: SpanRange) {
+        let replacement = match name {
+            "img" => Some("use `amp-img` instead"),
+            "video" => Some("use `amp-video` instead"),
+            "audio" => Some("use `amp-audio` instead"),
+            "iframe" => Some("use `amp-iframe` instead"),
+            _ => None,
+        };
+        if let Some(help) = replacement {
+            self.push(span, format!("`{}` isn't allowed in AMP", name), help);
+            return;
+        }
+        match name {
+            "html" if find_attr(attrs, "amp").is_none() => self.push(
+                span,
+                "`html` element is missing the `amp` attribute".to_string(),
+                "add `amp`, so the page is treated as AMP",
+            ),
+            "script" if !is_amp_script(attrs) => self.push(
+                span,
+                "custom scripts aren't allowed in AMP".to_string(),
+                "only AMP's own scripts and `application/ld+json` are allowed",
+            ),
+            "object" | "embed" | "frame" | "frameset" | "base" => self.push(
+                span,
+                format!("`{}` isn't allowed in AMP", name),
+                "leave it out, or use an AMP component",
+            ),
+            "style"
+                if find_attr(attrs, "amp-custom").is_none()
+                    && find_attr(attrs, "amp-boilerplate").is_none() =>
+            {
+                self.push(
+                    span,
+                    "AMP pages can only have one custom stylesheet".to_string(),
+                    "mark it with `amp-custom`",
+                )
+            }
+            _ => {}
+        }
+    }
+
+    fn stylesheet(&mut self, span: SpanRange) {
+        if self.profile == Profile::Email {
+            self.push(
+                span,
+                "stylesheets are stripped by some email clients".to_string(),
+                "use inline `style` attributes instead",
+            );
+        }
+    }
+
+    /// Checks the properties in a static `style` attribute.
+    fn inline_css(&mut self, css: &str, span: SpanRange) {
+        for declaration in css.split(';') {
+            let (property, value) = match declaration.split_once(':') {
+                Some((property, value)) => (property.trim(), value.trim()),
+                None => continue,
+            };
+            let unsupported = match property {
+                "position" | "float" | "z-index" | "transform" | "animation" | "transition" => true,
+                "display" => matches!(value, "flex" | "inline-flex" | "grid" | "inline-grid"),
+                _ => property.starts_with("flex") || property.starts_with("grid"),
+            };
+            if unsupported {
+                self.push(
+                    span,
+                    format!(
+                        "`{}: {}` isn't supported in most email clients",
+                        property, value
+                    ),
+                    "lay out the email with tables instead",
+                );
+            }
+        }
+    }
+
+    fn push(&mut self, span: SpanRange, message: String, help: &'static str) {
+        self.lints.push(Lint {
+            span,
+            message,
+            help,
+        });
+    }
+}
+
+/// Whether a table has `th` cells, and so is probably for data rather
+/// than layout.
+fn has_header_cells(body: &ElementBody) -> bool {
+    let block = match body {
+        ElementBody::Block { block } => block,
+        ElementBody::Void { .. } => return false,
+    };
+    let mut found = false;
+    for_each_child(&block.markups, &mut |markup| {
+        if let Markup::Element { name, body, .. } = markup {
+            found |= name_to_string(name.clone()) == "th" || has_header_cells(body);
+        }
+    });
+    found
+}
+
+/// Whether a `script` is one that AMP allows: its own runtime and
+/// components, or structured data.
+fn is_amp_script(attrs: &[Attr]) -> bool {
+    let value = |name| match find_attr(attrs, name) {
+        Some(AttrType::Normal { value }) => static_value(value),
+        _ => None,
+    };
+    value("type").as_deref() == Some("application/ld+json")
+        || value("src").is_some_and(|src| src.starts_with("https://cdn.ampproject.org/"))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{check, Profile};
+    use crate::parse::parse;
+
+    fn lints(profile: Profile, input: &str) -> Vec<String> {
+        let markups = parse(input.parse().unwrap());
+        check(profile, &markups)
+            .into_iter()
+            .map(|lint| lint.message)
+            .collect()
+    }
+
+    #[test]
+    fn email_elements() {
+        assert_eq!(
+            lints(
+                Profile::Email,
+                r#"script { "track()" } form { button { "Go" } } p onclick="go()" { "Hi" }"#
+            ),
+            [
+                "`script` isn't supported in email",
+                "`form` only works in some email clients",
+                "`button` only works in some email clients",
+                "`onclick` handlers aren't allowed in this profile",
+            ],
+        );
+    }
+
+    #[test]
+    fn email_css() {
+        assert_eq!(
+            lints(
+                Profile::Email,
+                r#"div style="display: flex; color: red; position: absolute" {} style { "p {}" }"#
+            ),
+            [
+                "`display: flex` isn't supported in most email clients",
+                "`position: absolute` isn't supported in most email clients",
+                "stylesheets are stripped by some email clients",
+            ],
+        );
+        assert!(lints(Profile::Email, r#"p style=(dynamic) { "Hi" }"#).is_empty());
+    }
+
+    #[test]
+    fn email_tables() {
+        assert_eq!(
+            lints(
+                Profile::Email,
+                r#"
+                table { tr { td { "Layout" } } }
+                table role="presentation" { tr { td { "Layout" } } }
+                table { tr { th { "Name" } } tr { td { "Data" } } }
+                "#
+            ),
+            ["layout `table` without `role=\"presentation\"`"],
+        );
+    }
+
+    #[test]
+    fn amp() {
+        assert_eq!(
+            lints(
+                Profile::Amp,
+                r#"
+                html {
+                    script src="https://cdn.ampproject.org/v0.js" {}
+                    script { "alert(1)" }
+                    style amp-custom { "p {}" }
+                    img src="cat.png";
+                    p style="color: red" {}
+                }
+                "#
+            ),
+            [
+                "`html` element is missing the `amp` attribute",
+                "custom scripts aren't allowed in AMP",
+                "`img` isn't allowed in AMP",
+                "inline styles aren't allowed in AMP",
+            ],
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use proc_macro2::{Span, TokenStream};
+use proc_macro_error::{abort, emit_error};
+use quote::quote;
+use syn::{
+    parenthesized,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Ident, LitStr, Token, Type, Visibility,
+};
+
+pub fn expand(input: TokenStream) -> TokenStream {
+    let routes = match syn::parse2::<Routes>(input) {
+        Ok(routes) => routes,
+        Err(error) => abort!(error.span(), "{}", error),
+    };
+    routes.0.iter().map(Route::expand).collect()
+}
+
+struct Routes(Vec<Route>);
+
+/// A single route, e.g. `pub user_profile(id: u32) => "/users/{id}";`.
+struct Route {
+    vis: Visibility,
+    name: Ident,
+    params: Vec<(Ident, Type)>,
+    path: LitStr,
+}
+
+impl Parse for Routes {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut routes = Vec::new();
+        while !input.is_empty() {
+            routes.push(input.parse()?);
+        }
+        Ok(Routes(routes))
+    }
+}
+
+impl Parse for Route {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let vis = input.parse()?;
+        let name = input.parse()?;
+        let mut params = Vec::new();
+        if input.peek(syn::token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            let punctuated =
+                Punctuated::<(Ident, Type), Token![,]>::parse_terminated_with(&content, |input| {
+                    let name = input.parse()?;
+                    input.parse::<Token![:]>()?;
+                    Ok((name, input.parse()?))
+                })?;
+            params.extend(punctuated);
+        }
+        input.parse::<Token![=>]>()?;
+        let path = input.parse()?;
+        input.parse::<Token![;]>()?;
+        Ok(Route {
+            vis,
+            name,
+            params,
+            path,
+        })
+    }
+}
+
+/// A piece of a route's path.
+#[derive(Debug, PartialEq)]
+enum Segment {
+    Static(String),
+    Param(String),
+}
+
+impl Route {
+    fn expand(&self) -> TokenStream {
+        let Route {
+            vis,
+            name,
+            params,
+            path,
+        } = self;
+        let segments = match split_path(&path.value()) {
+            Ok(segments) => segments,
+            Err(message) => abort!(path, "{}", message),
+        };
+        let mut attrs = TokenStream::new();
+        for (param, _) in params {
+            if !segments.contains(&Segment::Param(param.to_string())) {
+                emit_error!(param, "`{}` is not used in the route's path", param);
+                // Don't pile a warning on top of the error
+                attrs = quote!(#[allow(unused_variables)]);
+            }
+        }
+        // Out of reach of the parameters, which could be called `route`
+        let route = Ident::new("__maud_route", Span::mixed_site());
+        let pushes = segments.iter().map(|segment| match segment {
+            Segment::Static(text) => quote!(#route.__push_str(#text);),
+            Segment::Param(param) => match params.iter().find(|(name, _)| name == param) {
+                Some((name, _)) => quote!(#route.__push_param(&#name);),
+                None => abort!(
+                    path,
+                    "`{{{}}}` does not match any of the route's parameters", param;
+                    help = "add `{}` to the parameter list", param
+                ),
+            },
+        });
+        let param_names = params.iter().map(|(name, _)| name);
+        let param_types = params.iter().map(|(_, ty)| ty);
+        let doc = format!("Returns the path `{}`.", path.value());
+        quote! {
+            #[doc = #doc]
+            #attrs
+            #vis fn #name(#(#param_names: #param_types),*) -> maud::Route {
+                let mut #route = maud::Route::__new();
+                #(#pushes)*
+                #route
+            }
+        }
+    }
+}
+
+/// Splits a path like `/users/{id}` into static text and parameters.
+fn split_path(path: &str) -> Result<Vec<Segment>, String> {
+    if !path.starts_with('/') {
+        return Err("route paths must start with `/`".into());
+    }
+    let mut segments = Vec::new();
+    let mut rest = path;
+    while let Some(open) = rest.find('{') {
+        if open > 0 {
+            segments.push(Segment::Static(rest[..open].into()));
+        }
+        let close = match rest[open..].find('}') {
+            Some(close) => open + close,
+            None => return Err("unclosed `{` in route path".into()),
+        };
+        let param = &rest[open + 1..close];
+        if param.is_empty() || param.contains('{') {
+            return Err(format!("invalid parameter `{{{}}}` in route path", param));
+        }
+        segments.push(Segment::Param(param.into()));
+        rest = &rest[close + 1..];
+    }
+    if rest.contains('}') {
+        return Err("unmatched `}` in route path".into());
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Static(rest.into()));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{split_path, Segment};
+
+    #[test]
+    fn paths() {
+        assert_eq!(split_path("/"), Ok(vec![Segment::Static("/".into())]));
+        assert_eq!(
+            split_path("/users/{id}/posts/{slug}.html"),
+            Ok(vec![
+                Segment::Static("/users/".into()),
+                Segment::Param("id".into()),
+                Segment::Static("/posts/".into()),
+                Segment::Param("slug".into()),
+                Segment::Static(".html".into()),
+            ])
+        );
+        assert!(split_path("users").is_err());
+        assert!(split_path("/users/{id").is_err());
+        assert!(split_path("/users/{}").is_err());
+        assert!(split_path("/users/id}").is_err());
+    }
+}
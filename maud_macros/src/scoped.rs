@@ -0,0 +1,323 @@
+use proc_macro2::Span;
+use proc_macro_error::{abort, emit_error, SpanRange};
+use quote::{quote, quote_spanned};
+
+use crate::{ast::*, hash::fnv1a};
+
+/// Applies the `@style` block in a template, if there is one.
+///
+/// Every element in the template gets a class derived from the stylesheet,
+/// and every selector in the stylesheet is restricted to that class. This
+/// keeps the styles from leaking into other components.
+pub fn apply(markups: &mut [Markup]) {
+    let mut styles = Vec::new();
+    find_styles(markups, &mut styles);
+    let mut styles = styles.into_iter();
+    let (css, span) = match styles.next() {
+        Some(style) => style,
+        None => return,
+    };
+    for (_, span) in styles {
+        emit_error!(span, "a template can only have one `@style` block");
+    }
+    if css.to_ascii_lowercase().contains("</style") {
+        abort!(span, "`@style` blocks cannot contain `</style`");
+    }
+    let scope = format!("maud-{:08x}", fnv1a(css.as_bytes()));
+    let scoped_css = scope_stylesheet(&css, &scope);
+    scope_markups(markups, &scope, &scoped_css);
+}
+
+fn find_styles(markups: &[Markup], styles: &mut Vec<(String, SpanRange)>) {
+    for markup in markups {
+        match markup {
+            Markup::Style { css, .. } => styles.push((css.clone(), markup.span())),
+            Markup::Block(block)
+            | Markup::Suspend { body: block, .. }
+            | Markup::Provide { body: block, .. }
+            | Markup::Portal { body: block, .. } => find_styles(&block.markups, styles),
+            Markup::Element {
+                body: ElementBody::Block { block },
+                ..
+            } => find_styles(&block.markups, styles),
+            Markup::Special { segments } => {
+                for segment in segments {
+                    find_styles(&segment.body.markups, styles);
+                }
+            }
+            Markup::Match { arms, .. } => {
+                for arm in arms {
+                    find_styles(&arm.body.markups, styles);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn scope_markups(markups: &mut [Markup], scope: &str, scoped_css: &str) {
+    for markup in markups {
+        match markup {
+            Markup::Style {
+                css, scope: slot, ..
+            } => {
+                *css = scoped_css.to_string();
+                *slot = Some(scope.to_string());
+            }
+            Markup::Block(block)
+            | Markup::Suspend { body: block, .. }
+            | Markup::Provide { body: block, .. }
+            | Markup::Portal { body: block, .. } => {
+                scope_markups(&mut block.markups, scope, scoped_css)
+            }
+            Markup::Element { attrs, body, .. } => {
+                add_scope_class(attrs, scope);
+                if let ElementBody::Block { block } = body {
+                    scope_markups(&mut block.markups, scope, scoped_css);
+                }
+            }
+            Markup::Special { segments } => {
+                for segment in segments {
+                    scope_markups(&mut segment.body.markups, scope, scoped_css);
+                }
+            }
+            Markup::Match { arms, .. } => {
+                for arm in arms {
+                    scope_markups(&mut arm.body.markups, scope, scoped_css);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn add_scope_class(attrs: &mut Vec<Attr>, scope: &str) {
+    let scope_literal = |prefix: &str| Markup::Literal {
+        content: format!("{}{}", prefix, scope),
+        span: SpanRange::call_site(),
+    };
+    // If there's an explicit `class` attribute, append to that instead, so we
+    // don't end up with two of them
+    for attr in attrs.iter_mut() {
+        if let Attr::Named { named_attr } = attr {
+            if name_to_string(named_attr.name.clone()) == "class" {
+                let markups = match &mut named_attr.attr_type {
+                    AttrType::Normal { value } => {
+                        let old_value = std::mem::replace(
+                            value,
+                            Markup::ParseError {
+                                span: SpanRange::call_site(),
+                            },
+                        );
+                        vec![old_value, scope_literal(" ")]
+                    }
+                    // `class=[value]` becomes the scope, then the value if
+                    // there is one
+                    AttrType::Optional { toggler } => {
+                        let cond = &toggler.cond;
+                        let class = quote_spanned!(Span::mixed_site()=> __maud_class);
+                        vec![
+                            scope_literal(""),
+                            Markup::Special {
+                                segments: vec![Special {
+                                    at_span: SpanRange::call_site(),
+                                    head: quote!(if let ::core::option::Option::Some(#class) = (#cond)),
+                                    body: Block {
+                                        markups: vec![
+                                            Markup::Literal {
+                                                content: " ".to_string(),
+                                                span: SpanRange::call_site(),
+                                            },
+                                            Markup::Splice {
+                                                expr: class,
+                                                outer_span: SpanRange::call_site(),
+                                            },
+                                        ],
+                                        outer_span: SpanRange::call_site(),
+                                    },
+                                }],
+                            },
+                        ]
+                    }
+                    // An empty `class` has nothing to keep
+                    AttrType::Empty { .. } => vec![scope_literal("")],
+                    // Only `data` takes fields, so this isn't a `class`
+                    AttrType::Fields { .. } => continue,
+                };
+                named_attr.attr_type = AttrType::Normal {
+                    value: Markup::Block(Block {
+                        markups,
+                        outer_span: SpanRange::call_site(),
+                    }),
+                };
+                return;
+            }
+        }
+    }
+    attrs.push(Attr::Class {
+        dot_span: SpanRange::call_site(),
+        name: scope_literal(""),
+        toggler: None,
+    });
+}
+
+/// Restricts every selector in a stylesheet to elements with the given
+/// class.
+///
+/// The class is attached to the last compound selector, so `.card p:hover`
+/// becomes `.card p.scope:hover`.
+fn scope_stylesheet(css: &str, scope: &str) -> String {
+    let mut result = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(open) = find_outside_strings(rest, '{') {
+        let prelude = &rest[..open];
+        let close = matching_brace(rest, open);
+        let body = &rest[open + 1..close];
+        let trimmed = prelude.trim_start();
+        if let Some(at_rule) = trimmed.strip_prefix('@') {
+            result.push_str(prelude);
+            result.push('{');
+            let keyword = at_rule
+                .split(|c: char| !c.is_alphanumeric() && c != '-')
+                .next()
+                .unwrap_or("");
+            // Rules nested in these contain selectors; others (like
+            // `@keyframes` and `@font-face`) don't
+            if matches!(keyword, "media" | "supports" | "container" | "layer") {
+                result.push_str(&scope_stylesheet(body, scope));
+            } else {
+                result.push_str(body);
+            }
+        } else {
+            let leading_len = prelude.len() - trimmed.len();
+            result.push_str(&prelude[..leading_len]);
+            let selectors = split_top_level(trimmed, ',')
+                .into_iter()
+                .map(|selector| scope_selector(selector.trim(), scope))
+                .collect::<Vec<_>>();
+            result.push_str(&selectors.join(", "));
+            result.push_str(" {");
+            result.push_str(body);
+        }
+        result.push('}');
+        rest = rest.get(close + 1..).unwrap_or("");
+    }
+    result.push_str(rest);
+    result
+}
+
+fn scope_selector(selector: &str, scope: &str) -> String {
+    // Find the start of the last compound selector
+    let mut depth = 0;
+    let mut last_compound = 0;
+    for (i, c) in selector.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ' ' | '>' | '+' | '~' if depth == 0 => last_compound = i + 1,
+            _ => {}
+        }
+    }
+    // Insert the class before any pseudo-classes or pseudo-elements
+    let mut depth = 0;
+    let mut insert_at = selector.len();
+    for (i, c) in selector[last_compound..].char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            ':' if depth == 0 => {
+                insert_at = last_compound + i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    format!(
+        "{}.{}{}",
+        &selector[..insert_at],
+        scope,
+        &selector[insert_at..]
+    )
+}
+
+fn find_outside_strings(css: &str, needle: char) -> Option<usize> {
+    let mut quote = None;
+    for (i, c) in css.char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, c) if c == needle => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn matching_brace(css: &str, open: usize) -> usize {
+    let mut depth = 0;
+    let mut quote = None;
+    for (i, c) in css[open..].char_indices() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return open + i;
+                }
+            }
+            _ => {}
+        }
+    }
+    css.len()
+}
+
+fn split_top_level(selectors: &str, separator: char) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in selectors.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            ')' | ']' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                result.push(&selectors[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    result.push(&selectors[start..]);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{scope_selector, scope_stylesheet};
+
+    #[test]
+    fn selectors() {
+        assert_eq!(scope_selector("p", "s"), "p.s");
+        assert_eq!(scope_selector(".card p:hover", "s"), ".card p.s:hover");
+        assert_eq!(scope_selector("ul > li::before", "s"), "ul > li.s::before");
+        assert_eq!(
+            scope_selector("a[href^='http:']", "s"),
+            "a[href^='http:'].s"
+        );
+        assert_eq!(scope_selector("li:not(.a b)", "s"), "li.s:not(.a b)");
+    }
+
+    #[test]
+    fn stylesheet() {
+        assert_eq!(
+            scope_stylesheet(
+                ".title, h1 { color: red }\n@media (max-width: 600px) { p { margin: 0 } }\n\
+                 @keyframes spin { from { opacity: 0 } }\n",
+                "s"
+            ),
+            ".title.s, h1.s { color: red }\n@media (max-width: 600px) { p.s { margin: 0 } }\n\
+             @keyframes spin { from { opacity: 0 } }\n",
+        );
+    }
+}
@@ -0,0 +1,487 @@
+/// The keywords that can follow an `@`.
+pub const KEYWORDS: &[&str] = &[
+    "if", "else", "while", "for", "match", "let", "style", "json_ld", "break", "continue",
+    "return", "flush", "minify", "quotes", "suspend", "pi", "profile", "provide", "consume",
+    "portal",
+];
+
+/// Every attribute in the HTML standard, along with the event handlers,
+/// the obsolete attributes that browsers still support, and the common SVG
+/// ones. A near miss for one of these is probably a typo.
+const KNOWN_ATTRIBUTES: &[&str] = &[
+    "abbr",
+    "accept",
+    "accept-charset",
+    "accesskey",
+    "action",
+    "align",
+    "alink",
+    "allow",
+    "allowfullscreen",
+    "alpha",
+    "alt",
+    "archive",
+    "as",
+    "async",
+    "attributeName",
+    "autocapitalize",
+    "autocomplete",
+    "autocorrect",
+    "autofocus",
+    "autoplay",
+    "axis",
+    "background",
+    "begin",
+    "bgcolor",
+    "blocking",
+    "border",
+    "bordercolor",
+    "calcMode",
+    "cellpadding",
+    "cellspacing",
+    "char",
+    "charoff",
+    "charset",
+    "checked",
+    "cite",
+    "class",
+    "classid",
+    "clear",
+    "clipPathUnits",
+    "closedby",
+    "code",
+    "codebase",
+    "codetype",
+    "color",
+    "colorspace",
+    "cols",
+    "colspan",
+    "command",
+    "commandfor",
+    "compact",
+    "content",
+    "contenteditable",
+    "controls",
+    "coords",
+    "crossorigin",
+    "cx",
+    "cy",
+    "d",
+    "data",
+    "datafld",
+    "dataformatas",
+    "datapagesize",
+    "datasrc",
+    "datetime",
+    "declare",
+    "decoding",
+    "default",
+    "defer",
+    "dir",
+    "dirname",
+    "disabled",
+    "download",
+    "draggable",
+    "dur",
+    "dx",
+    "dy",
+    "enctype",
+    "enterkeyhint",
+    "event",
+    "exportparts",
+    "face",
+    "fetchpriority",
+    "fill",
+    "filterUnits",
+    "for",
+    "form",
+    "formaction",
+    "formenctype",
+    "formmethod",
+    "formnovalidate",
+    "formtarget",
+    "fr",
+    "frame",
+    "frameborder",
+    "from",
+    "gradientTransform",
+    "gradientUnits",
+    "headers",
+    "height",
+    "hidden",
+    "high",
+    "href",
+    "hreflang",
+    "hspace",
+    "http-equiv",
+    "id",
+    "imagesizes",
+    "imagesrcset",
+    "inert",
+    "inputmode",
+    "integrity",
+    "is",
+    "ismap",
+    "itemid",
+    "itemprop",
+    "itemref",
+    "itemscope",
+    "itemtype",
+    "keyPoints",
+    "keySplines",
+    "keyTimes",
+    "kind",
+    "label",
+    "lang",
+    "language",
+    "lengthAdjust",
+    "link",
+    "list",
+    "loading",
+    "longdesc",
+    "loop",
+    "low",
+    "lowsrc",
+    "manifest",
+    "marginheight",
+    "marginwidth",
+    "markerHeight",
+    "markerUnits",
+    "markerWidth",
+    "mask",
+    "maskContentUnits",
+    "maskUnits",
+    "max",
+    "maxlength",
+    "media",
+    "method",
+    "methods",
+    "min",
+    "minlength",
+    "multiple",
+    "muted",
+    "name",
+    "nohref",
+    "nomodule",
+    "nonce",
+    "noresize",
+    "noshade",
+    "novalidate",
+    "nowrap",
+    "offset",
+    "onabort",
+    "onafterprint",
+    "onanimationcancel",
+    "onanimationend",
+    "onanimationiteration",
+    "onanimationstart",
+    "onauxclick",
+    "onbeforeinput",
+    "onbeforematch",
+    "onbeforeprint",
+    "onbeforetoggle",
+    "onbeforeunload",
+    "onblur",
+    "oncancel",
+    "oncanplay",
+    "oncanplaythrough",
+    "onchange",
+    "onclick",
+    "onclose",
+    "oncommand",
+    "oncontextlost",
+    "oncontextmenu",
+    "oncontextrestored",
+    "oncopy",
+    "oncuechange",
+    "oncut",
+    "ondblclick",
+    "ondrag",
+    "ondragend",
+    "ondragenter",
+    "ondragleave",
+    "ondragover",
+    "ondragstart",
+    "ondrop",
+    "ondurationchange",
+    "onemptied",
+    "onended",
+    "onerror",
+    "onfocus",
+    "onfocusin",
+    "onfocusout",
+    "onformdata",
+    "ongotpointercapture",
+    "onhashchange",
+    "oninput",
+    "oninvalid",
+    "onkeydown",
+    "onkeypress",
+    "onkeyup",
+    "onlanguagechange",
+    "onload",
+    "onloadeddata",
+    "onloadedmetadata",
+    "onloadstart",
+    "onlostpointercapture",
+    "onmessage",
+    "onmessageerror",
+    "onmousedown",
+    "onmouseenter",
+    "onmouseleave",
+    "onmousemove",
+    "onmouseout",
+    "onmouseover",
+    "onmouseup",
+    "onoffline",
+    "ononline",
+    "onpagehide",
+    "onpagereveal",
+    "onpageshow",
+    "onpageswap",
+    "onpaste",
+    "onpause",
+    "onplay",
+    "onplaying",
+    "onpointercancel",
+    "onpointerdown",
+    "onpointerenter",
+    "onpointerleave",
+    "onpointermove",
+    "onpointerout",
+    "onpointerover",
+    "onpointerup",
+    "onpopstate",
+    "onprogress",
+    "onratechange",
+    "onrejectionhandled",
+    "onreset",
+    "onresize",
+    "onscroll",
+    "onscrollend",
+    "onsecuritypolicyviolation",
+    "onseeked",
+    "onseeking",
+    "onselect",
+    "onselectionchange",
+    "onselectstart",
+    "onslotchange",
+    "onstalled",
+    "onstorage",
+    "onsubmit",
+    "onsuspend",
+    "ontimeupdate",
+    "ontoggle",
+    "ontouchcancel",
+    "ontouchend",
+    "ontouchmove",
+    "ontouchstart",
+    "ontransitioncancel",
+    "ontransitionend",
+    "ontransitionrun",
+    "ontransitionstart",
+    "onunhandledrejection",
+    "onunload",
+    "onvolumechange",
+    "onwaiting",
+    "onwheel",
+    "opacity",
+    "open",
+    "optimum",
+    "orient",
+    "part",
+    "path",
+    "pathLength",
+    "pattern",
+    "patternContentUnits",
+    "patternTransform",
+    "patternUnits",
+    "ping",
+    "placeholder",
+    "playsinline",
+    "points",
+    "popover",
+    "popovertarget",
+    "popovertargetaction",
+    "poster",
+    "preload",
+    "preserveAspectRatio",
+    "profile",
+    "r",
+    "readonly",
+    "referrerpolicy",
+    "refX",
+    "refY",
+    "rel",
+    "repeatCount",
+    "required",
+    "result",
+    "rev",
+    "reversed",
+    "role",
+    "rotate",
+    "rows",
+    "rowspan",
+    "rules",
+    "rx",
+    "ry",
+    "sandbox",
+    "scheme",
+    "scope",
+    "scoped",
+    "scrolling",
+    "selected",
+    "shadowrootclonable",
+    "shadowrootdelegatesfocus",
+    "shadowrootmode",
+    "shadowrootserializable",
+    "shape",
+    "size",
+    "sizes",
+    "slot",
+    "span",
+    "spellcheck",
+    "src",
+    "srcdoc",
+    "srclang",
+    "srcset",
+    "standby",
+    "start",
+    "stdDeviation",
+    "step",
+    "stroke",
+    "style",
+    "summary",
+    "tabindex",
+    "target",
+    "text",
+    "textLength",
+    "title",
+    "to",
+    "transform",
+    "translate",
+    "type",
+    "urn",
+    "usemap",
+    "valign",
+    "value",
+    "values",
+    "valuetype",
+    "version",
+    "viewBox",
+    "vlink",
+    "vspace",
+    "width",
+    "wrap",
+    "writingsuggestions",
+    "x",
+    "x1",
+    "x2",
+    "xmlns",
+    "y",
+    "y1",
+    "y2",
+];
+
+/// Finds the candidate closest to `name`, if any is close enough to be a
+/// likely typo.
+pub fn did_you_mean<'a>(name: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    // Allow one edit for short names, and one more per three characters
+    let max_distance = (name.chars().count() / 3).max(1);
+    candidates
+        .iter()
+        .map(|&candidate| (edit_distance(name, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Suggests a known attribute for a name that is probably misspelled.
+///
+/// Returns `None` for names that are known, or not close to any known name.
+pub fn attribute(name: &str) -> Option<&'static str> {
+    // Short names are too likely to match by accident, and custom attributes
+    // always have a prefix or namespace
+    if name.len() < 4
+        || name.contains(['-', ':'])
+        || KNOWN_ATTRIBUTES
+            .iter()
+            .any(|known| known.eq_ignore_ascii_case(name))
+    {
+        return None;
+    }
+    did_you_mean(name, KNOWN_ATTRIBUTES)
+}
+
+/// The number of insertions, deletions, substitutions and transpositions of
+/// adjacent characters needed to turn `a` into `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    // `rows[i][j]` is the distance between `a[..i]` and `b[..j]`
+    let mut rows = vec![(0..=b.len()).collect::<Vec<_>>()];
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            row[j] = (rows[i - 1][j] + 1)
+                .min(row[j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(rows[i - 2][j - 2] + 1);
+            }
+        }
+        rows.push(row);
+    }
+    rows[a.len()][b.len()]
+}
+
+// maudfmt and maud_ast compile this module in too, but run their own tests
+#[cfg(all(test, not(any(maudfmt, maud_ast))))]
+mod test {
+    use super::{attribute, did_you_mean, edit_distance, KEYWORDS};
+
+    #[test]
+    fn distance() {
+        assert_eq!(edit_distance("for", "for"), 0);
+        assert_eq!(edit_distance("fro", "for"), 1);
+        assert_eq!(edit_distance("mathc", "match"), 1);
+        assert_eq!(edit_distance("wihle", "while"), 1);
+        assert_eq!(edit_distance("contniue", "continue"), 1);
+        assert_eq!(edit_distance("", "if"), 2);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn keywords() {
+        assert_eq!(did_you_mean("fro", KEYWORDS), Some("for"));
+        assert_eq!(did_you_mean("mathc", KEYWORDS), Some("match"));
+        assert_eq!(did_you_mean("esle", KEYWORDS), Some("else"));
+        assert_eq!(did_you_mean("retrun", KEYWORDS), Some("return"));
+        assert_eq!(did_you_mean("component", KEYWORDS), None);
+    }
+
+    #[test]
+    fn attributes() {
+        assert_eq!(attribute("clas"), Some("class"));
+        assert_eq!(attribute("titel"), Some("title"));
+        assert_eq!(attribute("placehoder"), Some("placeholder"));
+        assert_eq!(attribute("class"), None);
+        assert_eq!(attribute("for"), None);
+        assert_eq!(attribute("data-clas"), None);
+        assert_eq!(attribute("on:click"), None);
+        assert_eq!(attribute("popover"), None);
+        for name in [
+            "size",
+            "srcdoc",
+            "scoped",
+            "valign",
+            "viewBox",
+            "onpointerdown",
+        ] {
+            assert_eq!(attribute(name), None, "{}", name);
+        }
+        assert_eq!(attribute("ONCLICK"), None);
+        assert_eq!(attribute("onclck"), Some("onclick"));
+    }
+}
@@ -0,0 +1,212 @@
+use proc_macro2::{Literal, TokenStream};
+use proc_macro_error::{emit_call_site_warning, emit_warning, SpanRange};
+use quote::quote;
+use std::{
+    collections::{HashMap, HashSet},
+    env, fs,
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
+    time::SystemTime,
+};
+
+use crate::{ast::*, lint::for_each_child};
+
+/// The environment variable that points to the generated Tailwind CSS,
+/// relative to the crate root.
+const CSS_PATH_VAR: &str = "MAUD_TAILWIND_CSS";
+
+/// Warns about class names that don't appear in the project's Tailwind
+/// output, and returns tokens that make the template depend on that file.
+///
+/// Does nothing if `MAUD_TAILWIND_CSS` isn't set.
+pub fn check(markups: &[Markup]) -> TokenStream {
+    let path = match env::var_os(CSS_PATH_VAR) {
+        Some(path) => path,
+        None => return TokenStream::new(),
+    };
+    let mut path = PathBuf::from(path);
+    if path.is_relative() {
+        if let Some(manifest_dir) = env::var_os("CARGO_MANIFEST_DIR") {
+            path = PathBuf::from(manifest_dir).join(path);
+        }
+    }
+    let known = match load(path.clone()) {
+        Some(known) => known,
+        None => {
+            emit_call_site_warning!(
+                "could not read Tailwind CSS from `{}`", path.display();
+                help = "check the `{}` environment variable", CSS_PATH_VAR
+            );
+            return TokenStream::new();
+        }
+    };
+    for (class, span) in classes(markups) {
+        if !known.contains(&class) {
+            emit_warning!(span, "unknown Tailwind class `{}`", class);
+        }
+    }
+    // Mention the file with `include_bytes!`, so that the template is
+    // checked again whenever the CSS changes
+    let path = Literal::string(&path.to_string_lossy());
+    quote!(
+        const _: &[u8] = include_bytes!(#path);
+    )
+}
+
+/// Loads the set of classes defined in a CSS file, caching the result until
+/// the file changes.
+fn load(path: PathBuf) -> Option<Arc<HashSet<String>>> {
+    type Cache = HashMap<PathBuf, (Option<SystemTime>, Arc<HashSet<String>>)>;
+    static CACHE: OnceLock<Mutex<Cache>> = OnceLock::new();
+    let mut cache = CACHE.get_or_init(Default::default).lock().ok()?;
+    let modified = fs::metadata(&path).ok()?.modified().ok();
+    if let Some((cached, known)) = cache.get(&path) {
+        if modified.is_some() && *cached == modified {
+            return Some(known.clone());
+        }
+    }
+    let css = fs::read_to_string(&path).ok()?;
+    let known = Arc::new(class_selectors(&css));
+    cache.insert(path, (modified, known.clone()));
+    Some(known)
+}
+
+/// Collects every class name that is known at compile time, along with
+/// where it appears in the template.
+fn classes(markups: &[Markup]) -> Vec<(String, SpanRange)> {
+    let mut result = Vec::new();
+    collect_classes(markups, &mut result);
+    result
+}
+
+fn collect_classes(markups: &[Markup], result: &mut Vec<(String, SpanRange)>) {
+    for_each_child(markups, &mut |markup| {
+        if let Markup::Element { attrs, body, .. } = markup {
+            for attr in attrs {
+                match attr {
+                    Attr::Class { name, .. } => match name {
+                        Markup::Symbol { symbol } => {
+                            result.push((name_to_string(symbol.clone()), name.span()));
+                        }
+                        Markup::Literal { content, span } => {
+                            result.push((content.clone(), *span));
+                        }
+                        _ => {}
+                    },
+                    Attr::Named { named_attr } => {
+                        if let AttrType::Normal {
+                            value: Markup::Literal { content, span },
+                        } = &named_attr.attr_type
+                        {
+                            if name_to_string(named_attr.name.clone()) == "class" {
+                                result.extend(
+                                    content
+                                        .split_ascii_whitespace()
+                                        .map(|class| (class.to_string(), *span)),
+                                );
+                            }
+                        }
+                    }
+                    Attr::Id { .. } | Attr::Spread { .. } => {}
+                }
+            }
+            if let ElementBody::Block { block } = body {
+                collect_classes(&block.markups, result);
+            }
+        }
+    });
+}
+
+/// Extracts the class names used in the selectors of a stylesheet.
+///
+/// This errs on the side of finding too many classes: things like `.5rem`
+/// in a declaration will be picked up as well, which is harmless since we
+/// only use the result to look for typos.
+fn class_selectors(css: &str) -> HashSet<String> {
+    let mut result = HashSet::new();
+    let mut chars = css.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // Skip comments
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = '\0';
+                for c in chars.by_ref() {
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+            }
+            '.' => {
+                let mut class = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c == '\\' {
+                        // Escaped character, e.g. `.hover\:bg-blue-500`
+                        chars.next();
+                        if let Some(c) = chars.next() {
+                            class.push(c);
+                        }
+                    } else if c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+                        chars.next();
+                        class.push(c);
+                    } else {
+                        break;
+                    }
+                }
+                if !class.is_empty() {
+                    result.insert(class);
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{class_selectors, classes};
+    use crate::parse::parse;
+
+    #[test]
+    fn selectors() {
+        let css = r#"
+            /* .commented-out { } */
+            .flex { display: flex }
+            .items-center, .p-4 > .gap-2 { align-items: center }
+            .hover\:bg-blue-500:hover { background: blue }
+            @media (min-width: 640px) { .sm\:w-1\/2 { width: 50% } }
+        "#;
+        let mut classes = class_selectors(css).into_iter().collect::<Vec<_>>();
+        classes.sort();
+        assert_eq!(
+            classes,
+            [
+                "flex",
+                "gap-2",
+                "hover:bg-blue-500",
+                "items-center",
+                "p-4",
+                "sm:w-1/2"
+            ],
+        );
+    }
+
+    #[test]
+    fn template_classes() {
+        let markups = parse(
+            r#"div.felx."hover:bg-blue-500" { p class="p-4 gap-2" { @if true { span.items-center {} } } }"#
+                .parse()
+                .unwrap(),
+        );
+        let classes = classes(&markups)
+            .into_iter()
+            .map(|(class, _)| class)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            classes,
+            ["felx", "hover:bg-blue-500", "p-4", "gap-2", "items-center"]
+        );
+    }
+}
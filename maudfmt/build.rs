@@ -0,0 +1,5 @@
+fn main() {
+    // Tell the parser that it's running outside of a macro, so that it
+    // reports errors through `diagnostics` rather than the compiler
+    println!("cargo:rustc-cfg=maudfmt");
+}
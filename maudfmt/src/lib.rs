@@ -0,0 +1,258 @@
+//! A formatter for Maud templates.
+//!
+//! This reprints the contents of every `html!` (and `try_html!`) block in a
+//! Rust source file with consistent indentation, attribute wrapping and
+//! brace style. Rust expressions inside templates, like splices and the
+//! conditions of `@if`, are left as written.
+//!
+//! Templates are read with the same parser as `html!` itself. Those that
+//! contain comments, or that have errors, are left untouched.
+//!
+//! # Example
+//!
+//! ```rust
+//! let source = r#"
+//! fn page() -> Markup {
+//!     html! { ul { li { "One" } li { "Two" } } }
+//! }
+//! "#;
+//! let expected = r#"
+//! fn page() -> Markup {
+//!     html! {
+//!         ul {
+//!             li { "One" }
+//!             li { "Two" }
+//!         }
+//!     }
+//! }
+//! "#;
+//! assert_eq!(maudfmt::format_source(source, &Default::default()).unwrap(), expected);
+//! ```
+
+#![doc(html_root_url = "https://docs.rs/maudfmt/0.24.0")]
+
+use proc_macro2::{Delimiter, Group, LexError, TokenStream, TokenTree};
+use std::{error, fmt};
+
+use crate::print::Printer;
+
+// The parser is compiled in from `maud_macros`, as a proc-macro crate can't
+// be linked into anything else. Only some of what it defines is used here.
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/ast.rs"]
+mod ast;
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/diagnostics.rs"]
+mod diagnostics;
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/parse.rs"]
+mod parse;
+#[path = "../../maud_macros/src/pretty.rs"]
+mod pretty;
+mod print;
+#[allow(dead_code)]
+#[path = "../../maud_macros/src/suggest.rs"]
+mod suggest;
+
+/// Options for formatting.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The maximum width of each line, in characters.
+    pub max_width: usize,
+    /// The number of spaces to indent each level by.
+    pub indent: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        // Match rustfmt
+        Config {
+            max_width: 100,
+            indent: 4,
+        }
+    }
+}
+
+/// An error from reading a Rust source file.
+#[derive(Debug)]
+pub struct Error(LexError);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "could not parse Rust source: {}", self.0)
+    }
+}
+
+impl error::Error for Error {}
+
+/// Formats every template in a Rust source file.
+pub fn format_source(source: &str, config: &Config) -> Result<String, Error> {
+    let tokens = source.parse::<TokenStream>().map_err(Error)?;
+    let mut templates = Vec::new();
+    find_templates(tokens, &mut templates);
+    let mut result = source.to_string();
+    // Replace from the end, so that earlier byte offsets stay valid
+    for group in templates.iter().rev() {
+        if let Some(formatted) = format_group(source, group, config) {
+            result.replace_range(group.span().byte_range(), &formatted);
+        }
+    }
+    Ok(result)
+}
+
+/// Formats the contents of a single template, without the surrounding
+/// `html! { ... }`.
+///
+/// Returns `None` if the template contains comments, or has errors.
+///
+/// ```rust
+/// assert_eq!(
+///     maudfmt::format_template(r#"p{"Hello"}br;"#, &Default::default()).unwrap(),
+///     "p { \"Hello\" }\nbr;\n",
+/// );
+/// ```
+pub fn format_template(template: &str, config: &Config) -> Option<String> {
+    let source = format!("{{{}}}", template);
+    let group = match source.parse::<TokenStream>().ok()?.into_iter().next()? {
+        TokenTree::Group(group) => group,
+        _ => return None,
+    };
+    let markups = parse(&source, &group)?;
+    let printer = Printer {
+        source: &source,
+        config,
+    };
+    let mut out = String::new();
+    printer.markups(&markups, 0, &mut out);
+    same_template(&markups, &out).then_some(out)
+}
+
+/// Finds the bodies of every `html!` and `try_html!` invocation.
+fn find_templates(tokens: TokenStream, templates: &mut Vec<Group>) {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut i = 0;
+    while i < tokens.len() {
+        match (&tokens[i], tokens.get(i + 1), tokens.get(i + 2)) {
+            (
+                TokenTree::Ident(ident),
+                Some(TokenTree::Punct(bang)),
+                Some(TokenTree::Group(group)),
+            ) if (ident == "html" || ident == "try_html")
+                && bang.as_char() == '!'
+                && group.delimiter() == Delimiter::Brace =>
+            {
+                // Nested templates are left as written, along with the
+                // splices around them
+                templates.push(group.clone());
+                i += 3;
+            }
+            (TokenTree::Group(group), _, _) => {
+                find_templates(group.stream(), templates);
+                i += 1;
+            }
+            _ => i += 1,
+        }
+    }
+}
+
+/// Formats a template, including its braces.
+fn format_group(source: &str, group: &Group, config: &Config) -> Option<String> {
+    let markups = parse(source, group)?;
+    let start = group.span().byte_range().start;
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let column = source[line_start..start].chars().count();
+    let base_indent = source[line_start..]
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect::<String>();
+
+    let printer = Printer { source, config };
+    match &markups[..] {
+        [] => return Some("{}".into()),
+        [markup] => {
+            if let Some(line) = printer.inline(markup) {
+                if !line.contains('\n')
+                    && column + line.chars().count() + 4 <= config.max_width
+                    && same_template(&markups, &line)
+                {
+                    return Some(format!("{{ {} }}", line));
+                }
+            }
+        }
+        _ => {}
+    }
+
+    let config = Config {
+        max_width: config.max_width.saturating_sub(base_indent.chars().count()),
+        ..config.clone()
+    };
+    let printer = Printer {
+        source,
+        config: &config,
+    };
+    let mut body = String::new();
+    printer.markups(&markups, 1, &mut body);
+    if !same_template(&markups, &body) {
+        return None;
+    }
+    let mut result = String::from("{\n");
+    for line in body.lines() {
+        if !line.is_empty() {
+            result.push_str(&base_indent);
+            result.push_str(line);
+        }
+        result.push('\n');
+    }
+    result.push_str(&base_indent);
+    result.push('}');
+    Some(result)
+}
+
+/// Parses the contents of a template.
+///
+/// Returns `None` if the template has errors, or contains comments, which
+/// would be lost when reprinting it.
+fn parse(source: &str, group: &Group) -> Option<Vec<ast::Markup>> {
+    if has_comments(source, group) {
+        return None;
+    }
+    parse_tokens(group.stream())
+}
+
+fn parse_tokens(tokens: TokenStream) -> Option<Vec<ast::Markup>> {
+    let (markups, messages) = diagnostics::capture(|| parse::parse(tokens));
+    if messages
+        .iter()
+        .any(|message| message.level == diagnostics::Level::Error)
+    {
+        return None;
+    }
+    markups
+}
+
+/// Whether the formatted contents of a template parse back to the same
+/// tree, so that formatting hasn't changed what it means.
+fn same_template(markups: &[ast::Markup], formatted: &str) -> bool {
+    let reparsed = formatted.parse::<TokenStream>().ok().and_then(parse_tokens);
+    reparsed.is_some_and(|reparsed| pretty::print(&reparsed) == pretty::print(markups))
+}
+
+/// Whether there is anything but whitespace between the tokens of a group.
+fn has_comments(source: &str, group: &Group) -> bool {
+    let range = group.span().byte_range();
+    // Skip the delimiters
+    let mut last_end = range.start + 1;
+    for token in group.stream() {
+        let token_range = token.span().byte_range();
+        if !source[last_end..token_range.start].trim().is_empty() {
+            return true;
+        }
+        if let TokenTree::Group(group) = &token {
+            if has_comments(source, group) {
+                return true;
+            }
+        }
+        last_end = token_range.end;
+    }
+    !source[last_end..range.end - 1].trim().is_empty()
+}
@@ -0,0 +1,80 @@
+//! Formats the `html!` templates in Rust source files.
+//!
+//! ```text
+//! maudfmt [--check] [FILE]...
+//! ```
+//!
+//! Files are formatted in place. With `--check`, the names of unformatted
+//! files are printed instead, and the exit status is 1 if there are any.
+//! With no files, a single source file is read from standard input and
+//! written to standard output.
+
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    process::ExitCode,
+};
+
+fn main() -> ExitCode {
+    let mut check = false;
+    let mut paths = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--check" => check = true,
+            "-h" | "--help" => {
+                println!("usage: maudfmt [--check] [FILE]...");
+                return ExitCode::SUCCESS;
+            }
+            _ => paths.push(arg),
+        }
+    }
+    let config = maudfmt::Config::default();
+
+    if paths.is_empty() {
+        let mut source = String::new();
+        if let Err(error) = io::stdin().read_to_string(&mut source) {
+            eprintln!("maudfmt: {}", error);
+            return ExitCode::FAILURE;
+        }
+        return match maudfmt::format_source(&source, &config) {
+            Ok(formatted) if check && formatted != source => ExitCode::FAILURE,
+            Ok(_) if check => ExitCode::SUCCESS,
+            Ok(formatted) => {
+                let _ = io::stdout().write_all(formatted.as_bytes());
+                ExitCode::SUCCESS
+            }
+            Err(error) => {
+                eprintln!("maudfmt: {}", error);
+                ExitCode::FAILURE
+            }
+        };
+    }
+
+    let mut status = ExitCode::SUCCESS;
+    for path in paths {
+        let result = fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|source| {
+                let formatted =
+                    maudfmt::format_source(&source, &config).map_err(|e| e.to_string())?;
+                Ok((source, formatted))
+            });
+        match result {
+            Ok((source, formatted)) if formatted != source => {
+                if check {
+                    println!("{}", path);
+                    status = ExitCode::FAILURE;
+                } else if let Err(error) = fs::write(&path, formatted) {
+                    eprintln!("maudfmt: {}: {}", path, error);
+                    status = ExitCode::FAILURE;
+                }
+            }
+            Ok(_) => {}
+            Err(error) => {
+                eprintln!("maudfmt: {}: {}", path, error);
+                status = ExitCode::FAILURE;
+            }
+        }
+    }
+    status
+}
@@ -0,0 +1,401 @@
+//! Reprints a parsed template.
+//!
+//! The layout comes from the parser's tree, but everything that isn't
+//! reformatted, like Rust expressions, is copied from the source through
+//! the spans in that tree, so it stays as written.
+
+use proc_macro_error::SpanRange;
+
+use crate::{ast::*, Config};
+
+pub struct Printer<'a> {
+    pub source: &'a str,
+    pub config: &'a Config,
+}
+
+/// An attribute, as it's printed.
+enum PrintedAttr {
+    /// A `.class` or `#id` shorthand, which is attached to the previous
+    /// attribute without a space.
+    Shorthand(String),
+    /// A `name=value` attribute, an empty `name` attribute, or a `..rest`
+    /// spread.
+    Named(String),
+}
+
+impl<'a> Printer<'a> {
+    /// Prints the markups of a template, each on its own line.
+    pub fn markups(&self, markups: &[Markup], indent: usize, out: &mut String) {
+        for (i, markup) in markups.iter().enumerate() {
+            if i > 0 && self.blank_between(&markups[i - 1], markup) {
+                out.push('\n');
+            }
+            self.push_indent(indent, out);
+            self.markup(markup, indent, out);
+            out.push('\n');
+        }
+    }
+
+    /// Prints a markup on one line, if it's simple enough.
+    ///
+    /// Elements and control structures are simple if their bodies only
+    /// contain text, splices, and void or empty elements.
+    pub fn inline(&self, markup: &Markup) -> Option<String> {
+        let markup = desugared(markup);
+        if let Some(segments) = self.segments(markup) {
+            let segments = segments
+                .into_iter()
+                .map(|(head, body)| Some(format!("{} {}", head, self.inline_body(&body.markups)?)))
+                .collect::<Option<Vec<_>>>()?;
+            return Some(segments.join(" "));
+        }
+        match markup {
+            Markup::Block(block) => self.inline_body(&block.markups),
+            Markup::Element { name, attrs, body } => {
+                let mut line = self.element_head(name, attrs);
+                match body {
+                    ElementBody::Void { .. } => line.push(';'),
+                    ElementBody::Block { block } => {
+                        line.push(' ');
+                        line.push_str(&self.inline_body(&block.markups)?);
+                    }
+                }
+                Some(line)
+            }
+            Markup::Match { .. } => None,
+            _ => match self.statement(markup) {
+                Some(statement) => Some(format!("{};", statement)),
+                None => Some(self.text(markup.span()).to_string()),
+            },
+        }
+    }
+
+    fn inline_body(&self, markups: &[Markup]) -> Option<String> {
+        if markups.is_empty() {
+            return Some("{}".into());
+        }
+        let mut parts = Vec::new();
+        for markup in markups {
+            if !self.is_leaf(markup) {
+                return None;
+            }
+            parts.push(self.inline(markup)?);
+        }
+        Some(format!("{{ {} }}", parts.join(" ")))
+    }
+
+    fn is_leaf(&self, markup: &Markup) -> bool {
+        match markup {
+            Markup::Literal { .. } | Markup::Splice { .. } => true,
+            Markup::Element { body, .. } => match body {
+                ElementBody::Void { .. } => true,
+                ElementBody::Block { block } => block.markups.is_empty(),
+            },
+            _ => self.statement(markup).is_some(),
+        }
+    }
+
+    fn markup(&self, markup: &Markup, indent: usize, out: &mut String) {
+        if let Some(line) = self.inline(markup) {
+            if self.fits(&line, out) {
+                out.push_str(&line);
+                return;
+            }
+        }
+        let markup = desugared(markup);
+        if let Some(segments) = self.segments(markup) {
+            for (i, (head, body)) in segments.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(' ');
+                }
+                // The whole chain didn't fit on one line, so expand every
+                // branch
+                out.push_str(head);
+                out.push_str(" {\n");
+                self.markups(&body.markups, indent + 1, out);
+                self.push_indent(indent, out);
+                out.push('}');
+            }
+            return;
+        }
+        match markup {
+            Markup::Block(block) => self.body(&block.markups, indent, out),
+            Markup::Element { name, attrs, body } => {
+                let head = self.element_head(name, attrs);
+                // Leave room for the ` {` or `;` that follows
+                if self.fits(&format!("{} {{", head), out) {
+                    out.push_str(&head);
+                } else {
+                    // Keep the name and shorthands on the first line, and
+                    // put each named attribute on a line of its own
+                    let name = self.element_name(name);
+                    out.push_str(name.unwrap_or_default());
+                    let mut line_is_empty = name.is_none();
+                    let mut after_named = false;
+                    for attr in self.attrs(attrs) {
+                        match attr {
+                            PrintedAttr::Shorthand(shorthand) => {
+                                if after_named {
+                                    out.push(' ');
+                                }
+                                out.push_str(&shorthand);
+                                after_named = false;
+                            }
+                            PrintedAttr::Named(named) => {
+                                if !line_is_empty {
+                                    out.push('\n');
+                                    self.push_indent(indent + 1, out);
+                                }
+                                out.push_str(&named);
+                                after_named = true;
+                            }
+                        }
+                        line_is_empty = false;
+                    }
+                }
+                match body {
+                    ElementBody::Void { .. } => out.push(';'),
+                    ElementBody::Block { block } => {
+                        out.push(' ');
+                        self.body(&block.markups, indent, out);
+                    }
+                }
+            }
+            Markup::Match {
+                at_span,
+                arms,
+                arms_span,
+                ..
+            } => {
+                out.push_str(self.head(*at_span, *arms_span));
+                out.push_str(" {\n");
+                for arm in arms {
+                    self.push_indent(indent + 1, out);
+                    let pattern = self.text(span_tokens(arm.head.clone()));
+                    out.push_str(pattern.trim_end_matches("=>").trim_end());
+                    out.push_str(" => ");
+                    if self.text(arm.body.span()).starts_with('{') {
+                        self.body(&arm.body.markups, indent + 1, out);
+                    } else {
+                        // An arm without braces, which ends with a comma
+                        for (i, markup) in arm.body.markups.iter().enumerate() {
+                            if i > 0 {
+                                out.push(' ');
+                            }
+                            self.markup(markup, indent + 1, out);
+                        }
+                        out.push(',');
+                    }
+                    out.push('\n');
+                }
+                self.push_indent(indent, out);
+                out.push('}');
+            }
+            _ => match self.statement(markup) {
+                Some(statement) => {
+                    out.push_str(statement);
+                    out.push(';');
+                }
+                None => out.push_str(self.text(markup.span())),
+            },
+        }
+    }
+
+    /// Prints a block, on one line if it's simple enough.
+    fn body(&self, markups: &[Markup], indent: usize, out: &mut String) {
+        if let Some(line) = self.inline_body(markups) {
+            if self.fits(&line, out) {
+                out.push_str(&line);
+                return;
+            }
+        }
+        out.push_str("{\n");
+        self.markups(markups, indent + 1, out);
+        self.push_indent(indent, out);
+        out.push('}');
+    }
+
+    /// The heads and bodies of an `@if` chain, a loop, or another special
+    /// form with a body.
+    fn segments<'m>(&self, markup: &'m Markup) -> Option<Vec<(&'a str, &'m Block)>> {
+        match markup {
+            Markup::Special { segments } => Some(
+                segments
+                    .iter()
+                    .map(|segment| {
+                        (
+                            self.head(segment.at_span, segment.body.span()),
+                            &segment.body,
+                        )
+                    })
+                    .collect(),
+            ),
+            Markup::Suspend { at_span, body, .. }
+            | Markup::Provide { at_span, body, .. }
+            | Markup::Portal { at_span, body, .. } => {
+                Some(vec![(self.head(*at_span, body.span()), body)])
+            }
+            _ => None,
+        }
+    }
+
+    /// The source of a statement, like `@let` or `@break`, without its `;`.
+    fn statement(&self, markup: &Markup) -> Option<&'a str> {
+        match markup {
+            Markup::Let { .. }
+            | Markup::Control { .. }
+            | Markup::Return { .. }
+            | Markup::Flush { .. }
+            | Markup::Minify { .. }
+            | Markup::MinimalQuotes { .. }
+            | Markup::Profile { .. }
+            | Markup::ProcessingInstruction { .. } => {
+                let start = markup.span().first.byte_range().start;
+                let source = &self.source[start..self.end(markup)];
+                Some(source.trim_end_matches(';').trim_end())
+            }
+            _ => None,
+        }
+    }
+
+    fn element_name(&self, name: &proc_macro2::TokenStream) -> Option<&'a str> {
+        let name = self.text(span_tokens(name.clone()));
+        // The `.class` and `#id` shorthands for `div` take their name's
+        // span from the `.` or `#`
+        (name != "." && name != "#").then_some(name)
+    }
+
+    fn element_head(&self, name: &proc_macro2::TokenStream, attrs: &[Attr]) -> String {
+        let mut head = self.element_name(name).unwrap_or_default().to_string();
+        let mut after_named = false;
+        for attr in self.attrs(attrs) {
+            match attr {
+                PrintedAttr::Shorthand(shorthand) => {
+                    if after_named {
+                        head.push(' ');
+                    }
+                    head.push_str(&shorthand);
+                    after_named = false;
+                }
+                PrintedAttr::Named(named) => {
+                    if !head.is_empty() {
+                        head.push(' ');
+                    }
+                    head.push_str(&named);
+                    after_named = true;
+                }
+            }
+        }
+        head
+    }
+
+    fn attrs(&self, attrs: &[Attr]) -> Vec<PrintedAttr> {
+        attrs
+            .iter()
+            .map(|attr| match attr {
+                Attr::Class { name, toggler, .. } => {
+                    let mut shorthand = format!(".{}", self.text(name.span()));
+                    if let Some(toggler) = toggler {
+                        shorthand.push_str(self.text(toggler.cond_span));
+                    }
+                    PrintedAttr::Shorthand(shorthand)
+                }
+                Attr::Id { name, .. } => {
+                    PrintedAttr::Shorthand(format!("#{}", self.text(name.span())))
+                }
+                Attr::Spread { expr_span, .. } => {
+                    PrintedAttr::Named(format!("..{}", self.text(*expr_span)))
+                }
+                Attr::Named { named_attr } => {
+                    let name_span = span_tokens(named_attr.name.clone());
+                    let mut named = self.text(name_span).to_string();
+                    match &named_attr.attr_type {
+                        AttrType::Normal { value } => {
+                            named.push('=');
+                            named.push_str(self.text(value.span()));
+                        }
+                        AttrType::Optional { toggler } => {
+                            named.push('=');
+                            named.push_str(self.text(toggler.cond_span));
+                        }
+                        AttrType::Empty { toggler } => {
+                            // The `?` of the old syntax isn't in the tree
+                            let after = &self.source[name_span.last.byte_range().end..];
+                            if after.trim_start().starts_with('?') {
+                                named.push('?');
+                            }
+                            if let Some(toggler) = toggler {
+                                named.push_str(self.text(toggler.cond_span));
+                            }
+                        }
+                        AttrType::Fields { value_span, .. } => {
+                            named.push_str("..=");
+                            named.push_str(self.text(*value_span));
+                        }
+                    }
+                    PrintedAttr::Named(named)
+                }
+            })
+            .collect()
+    }
+
+    /// The source of a special form's head, from its `@` to its body.
+    fn head(&self, at_span: SpanRange, body_span: SpanRange) -> &'a str {
+        let start = at_span.first.byte_range().start;
+        let end = body_span.first.byte_range().start;
+        self.source[start..end].trim_end()
+    }
+
+    fn text(&self, span: SpanRange) -> &'a str {
+        &self.source[span.first.byte_range().start..span.last.byte_range().end]
+    }
+
+    /// Where a markup ends in the source.
+    fn end(&self, markup: &Markup) -> usize {
+        match desugared(markup) {
+            // The `;` is made up if it was left out, so end at the keyword
+            // or label before it
+            Markup::Control { tokens, .. } => {
+                let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+                tokens[tokens.len() - 2].span().byte_range().end
+            }
+            markup => markup.span().last.byte_range().end,
+        }
+    }
+
+    /// Whether there was a blank line between two markups.
+    fn blank_between(&self, previous: &Markup, markup: &Markup) -> bool {
+        let start = markup.span().first.byte_range().start;
+        self.source
+            .get(self.end(previous)..start)
+            .is_some_and(|between| between.matches('\n').count() > 1)
+    }
+
+    /// Whether the text fits on the current line.
+    fn fits(&self, text: &str, out: &str) -> bool {
+        let line_start = out.rfind('\n').map_or(0, |i| i + 1);
+        let column = out[line_start..].chars().count();
+        !text.contains('\n') && column + text.chars().count() <= self.config.max_width
+    }
+
+    fn push_indent(&self, indent: usize, out: &mut String) {
+        for _ in 0..indent * self.config.indent {
+            out.push(' ');
+        }
+    }
+}
+
+/// The loop in a `@for await`, which the parser turns into a block that
+/// sets up the stream and then loops over it.
+fn desugared(markup: &Markup) -> &Markup {
+    match markup {
+        // Every other block has the span of its braces
+        Markup::Block(block) if block.span().first.byte_range().len() == 1 => {
+            match block.markups.last() {
+                Some(special @ Markup::Special { .. }) if block.markups.len() == 2 => special,
+                _ => markup,
+            }
+        }
+        _ => markup,
+    }
+}
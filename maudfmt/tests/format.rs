@@ -0,0 +1,214 @@
+use maudfmt::{format_source, format_template, Config};
+
+fn format(template: &str) -> String {
+    let formatted = format_template(template, &Config::default()).unwrap();
+    // Formatting should be idempotent
+    assert_eq!(
+        format_template(&formatted, &Config::default()).as_deref(),
+        Some(&*formatted)
+    );
+    formatted
+}
+
+#[test]
+fn elements() {
+    assert_eq!(
+        format(r#"div.card#main{h1{"Title"}p.lead{"Hello, "(name)"!"}br;}"#),
+        concat!(
+            "div.card#main {\n",
+            "    h1 { \"Title\" }\n",
+            "    p.lead { \"Hello, \" (name) \"!\" }\n",
+            "    br;\n",
+            "}\n",
+        ),
+    );
+}
+
+#[test]
+fn empty_bodies() {
+    assert_eq!(format("div {   }\n.spacer{}"), "div {}\n.spacer {}\n");
+}
+
+#[test]
+fn attributes() {
+    assert_eq!(
+        format(
+            r#"a  href = "/"  .nav-link.active[is_active]  { "Home" } input type="checkbox" checked[done];"#
+        ),
+        concat!(
+            "a href=\"/\" .nav-link.active[is_active] { \"Home\" }\n",
+            "input type=\"checkbox\" checked[done];\n",
+        ),
+    );
+}
+
+#[test]
+fn spread_attributes() {
+    assert_eq!(
+        format(r#"button.btn   ..rest { "Go" } p ..( extra ) {} li data..=(row) {}"#),
+        "button.btn ..rest { \"Go\" }\np ..( extra ) {}\nli data..=(row) {}\n",
+    );
+}
+
+#[test]
+fn long_attributes_are_wrapped() {
+    assert_eq!(
+        format(
+            r#"input.search type="search" name="query" placeholder="Search the documentation" autocomplete="off" value=(query);"#
+        ),
+        concat!(
+            "input.search\n",
+            "    type=\"search\"\n",
+            "    name=\"query\"\n",
+            "    placeholder=\"Search the documentation\"\n",
+            "    autocomplete=\"off\"\n",
+            "    value=(query);\n",
+        ),
+    );
+}
+
+#[test]
+fn control_structures() {
+    assert_eq!(
+        format(
+            r#"@if user.is_admin { a href="/admin" { "Admin" } } @else { "Guest" }
+            @for item in &items { li { (item) } }
+            @let total = items.len();
+            @if total > 0 { "Total: " (total) }"#
+        ),
+        concat!(
+            "@if user.is_admin {\n",
+            "    a href=\"/admin\" { \"Admin\" }\n",
+            "} @else {\n",
+            "    \"Guest\"\n",
+            "}\n",
+            "@for item in &items {\n",
+            "    li { (item) }\n",
+            "}\n",
+            "@let total = items.len();\n",
+            "@if total > 0 { \"Total: \" (total) }\n",
+        ),
+    );
+}
+
+#[test]
+fn match_arms() {
+    assert_eq!(
+        format(
+            r#"@match pony { Pony::Rarity => "Rarity", Pony::Applejack => { p { "Applejack" } } _ => {} }"#
+        ),
+        concat!(
+            "@match pony {\n",
+            "    Pony::Rarity => \"Rarity\",\n",
+            "    Pony::Applejack => {\n",
+            "        p { \"Applejack\" }\n",
+            "    }\n",
+            "    _ => {}\n",
+            "}\n",
+        ),
+    );
+}
+
+#[test]
+fn loops_are_printed_as_written() {
+    assert_eq!(
+        format(r#"@'rows: for await row in rows{@for c in row{@if c.done{@break 'rows;}(c)}}"#),
+        concat!(
+            "@'rows: for await row in rows {\n",
+            "    @for c in row {\n",
+            "        @if c.done { @break 'rows; }\n",
+            "        (c)\n",
+            "    }\n",
+            "}\n",
+        ),
+    );
+}
+
+#[test]
+fn blank_lines_are_kept() {
+    assert_eq!(
+        format("h1 { \"Title\" }\n\n\n\np { \"Body\" }"),
+        "h1 { \"Title\" }\n\np { \"Body\" }\n",
+    );
+}
+
+#[test]
+fn rust_code_is_left_as_written() {
+    assert_eq!(
+        format("@for (i,x) in xs.iter().enumerate() { (x+i) }"),
+        "@for (i,x) in xs.iter().enumerate() { (x+i) }\n",
+    );
+}
+
+#[test]
+fn context() {
+    assert_eq!(
+        format(r#"@provide(Theme::Dark){@consume(theme: Theme); p{(theme.is_some())}}"#),
+        "@provide(Theme::Dark) {\n    @consume(theme: Theme);\n    p { (theme.is_some()) }\n}\n",
+    );
+}
+
+#[test]
+fn portals() {
+    assert_eq!(
+        format(r##"@portal(target="#modals"){dialog{"Hi"}}"##),
+        "@portal(target=\"#modals\") {\n    dialog { \"Hi\" }\n}\n",
+    );
+}
+
+#[test]
+fn suspend_slots() {
+    assert_eq!(
+        format(r#"@suspend(load_feed()){p{"Loading"}}"#),
+        "@suspend(load_feed()) {\n    p { \"Loading\" }\n}\n",
+    );
+}
+
+#[test]
+fn invalid_templates_are_rejected() {
+    assert_eq!(format_template("p \"unbraced\"", &Config::default()), None);
+    assert_eq!(format_template("@unknown { }", &Config::default()), None);
+}
+
+#[test]
+fn source_files() {
+    let source = r#"
+fn page(name: &str) -> Markup {
+    let greeting = html!{p{"Hello, "(name)}};
+    html! {
+      (DOCTYPE)
+      html { body { (greeting) } }
+    }
+}
+"#;
+    let expected = r#"
+fn page(name: &str) -> Markup {
+    let greeting = html!{ p { "Hello, " (name) } };
+    html! {
+        (DOCTYPE)
+        html {
+            body { (greeting) }
+        }
+    }
+}
+"#;
+    assert_eq!(format_source(source, &Config::default()).unwrap(), expected);
+}
+
+#[test]
+fn templates_with_comments_are_skipped() {
+    let source =
+        "fn f() -> Markup {\n    html! {\n        // Greeting\n        p{\"Hi\"}\n    }\n}\n";
+    assert_eq!(format_source(source, &Config::default()).unwrap(), source);
+}
+
+#[test]
+fn nested_templates_are_left_alone() {
+    let source = "html! { p { (html! {b{\"x\"}}) } }";
+    assert_eq!(format_source(source, &Config::default()).unwrap(), source);
+}
+
+#[test]
+fn invalid_rust_is_an_error() {
+    assert!(format_source("fn f() { \"unterminated", &Config::default()).is_err());
+}